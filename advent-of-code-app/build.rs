@@ -23,7 +23,7 @@ fn main() {
 
     let commit_hash = env::var("COMMIT_HASH").unwrap_or_else(|_| {
         let output = Command::new("git")
-            .args(&["rev-parse", "HEAD"])
+            .args(["rev-parse", "HEAD"])
             .output()
             .expect("Failed to execute git command");
         String::from_utf8_lossy(&output.stdout).trim().to_string()
@@ -31,7 +31,7 @@ fn main() {
 
     let commit_hash_short = env::var("COMMIT_HASH_SHORT").unwrap_or_else(|_| {
         let output = Command::new("git")
-            .args(&["rev-parse", "--short", "HEAD"])
+            .args(["rev-parse", "--short", "HEAD"])
             .output()
             .expect("Failed to execute git command");
         String::from_utf8_lossy(&output.stdout).trim().to_string()
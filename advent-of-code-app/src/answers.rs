@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use advent_of_code_solutions::aoc_solution::{Answer, SolveError};
+
+/// Known-good answers, keyed by year, day and part.
+///
+/// The file groups the answers of a day in a table named `<year>.<day>`:
+///
+/// ```toml
+/// [2024.5]
+/// part_1 = "143"
+/// part_2 = 123
+/// ```
+#[derive(Default)]
+pub struct Answers {
+    expected: BTreeMap<(u32, u32, u32), String>,
+}
+
+/// How an answer compares to the expected one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected } => write!(f, "fail, expected {}", expected),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl Answers {
+    /// Loads the answers from `path`. A missing file means every answer is unknown.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                Self::parse(&content).map_err(|error| format!("{}: {}", path.display(), error))
+            }
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(format!("{}: {}", path.display(), error)),
+        }
+    }
+
+    fn parse(content: &str) -> Result<Self, String> {
+        let table: toml::Table = content
+            .parse()
+            .map_err(|error: toml::de::Error| error.to_string())?;
+        let mut expected = BTreeMap::new();
+
+        for (year_key, days) in &table {
+            let year = parse_key(year_key, "year")?;
+            let days = days
+                .as_table()
+                .ok_or_else(|| format!("[{}] must be a table of days", year_key))?;
+
+            for (day_key, parts) in days {
+                let day = parse_key(day_key, "day")?;
+                let parts = parts.as_table().ok_or_else(|| {
+                    format!("[{}.{}] must be a table of parts", year_key, day_key)
+                })?;
+
+                for (part_key, value) in parts {
+                    let part = match part_key.as_str() {
+                        "part_1" => 1,
+                        "part_2" => 2,
+                        _ => {
+                            return Err(format!(
+                                "[{}.{}] has unknown key '{}', expected 'part_1' or 'part_2'",
+                                year_key, day_key, part_key
+                            ))
+                        }
+                    };
+                    // Numbers may be written without quotes.
+                    let answer = match value {
+                        toml::Value::String(text) => text.clone(),
+                        toml::Value::Integer(number) => number.to_string(),
+                        _ => {
+                            return Err(format!(
+                                "[{}.{}] {} must be a string or an integer",
+                                year_key, day_key, part_key
+                            ))
+                        }
+                    };
+                    expected.insert((year, day, part), answer);
+                }
+            }
+        }
+
+        Ok(Answers { expected })
+    }
+
+    /// Compares the result of a part with the expected answer. Errors never pass.
+    pub fn check(
+        &self,
+        year: u32,
+        day: u32,
+        part: u32,
+        result: &Result<Answer, SolveError>,
+    ) -> Verdict {
+        match (self.expected.get(&(year, day, part)), result) {
+            (None, _) => Verdict::Unknown,
+            (Some(expected), Ok(answer)) if *answer == expected.as_str() => Verdict::Pass,
+            (Some(expected), _) => Verdict::Fail {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+fn parse_key(key: &str, what: &str) -> Result<u32, String> {
+    key.parse()
+        .map_err(|_| format!("'{}' is not a {}", key, what))
+}
+
+mod test {
+    #[test]
+    /// Answers are matched by year, day and part; numbers may be quoted or not.
+    fn test_answers_check() {
+        use super::{Answers, Verdict};
+        use advent_of_code_solutions::aoc_solution::{Answer, SolveError};
+
+        let answers = Answers::parse(
+            r#"
+[2024.5]
+part_1 = "143"
+part_2 = 123
+
+[2024.07]
+part_1 = "3749"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            answers.check(2024, 5, 1, &Ok(Answer::from(143u64))),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(2024, 5, 2, &Ok(Answer::from(123u64))),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(2024, 7, 1, &Ok(Answer::from(1u64))),
+            Verdict::Fail {
+                expected: "3749".to_string()
+            }
+        );
+        assert_eq!(
+            answers.check(2024, 5, 1, &Err(SolveError::unsolvable("no answer"))),
+            Verdict::Fail {
+                expected: "143".to_string()
+            }
+        );
+        assert_eq!(
+            answers.check(2024, 7, 2, &Ok(Answer::from(1u64))),
+            Verdict::Unknown
+        );
+        assert!(Answers::parse("[2024.5]\npart_3 = 1").is_err());
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use clap::Args;
+use serde_json::{json, Value};
+
+use crate::output::Build;
+use crate::selection::{Input, Selection};
+use crate::table;
+
+/// Options of the `bench` subcommand.
+#[derive(Args)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+    /// Untimed runs per part before measuring.
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    pub warmup: u32,
+    /// Timed runs per part.
+    #[arg(short = 'n', long, value_name = "N", default_value_t = 20,
+          value_parser = clap::value_parser!(u32).range(1..))]
+    pub runs: u32,
+    /// Writes the measurements to this file for later comparison.
+    #[arg(long, value_name = "FILE")]
+    pub save_baseline: Option<PathBuf>,
+    /// Compares the measurements with a baseline written by `--save-baseline`.
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+    /// Slowdown of the median, in percent, above which a part counts as a regression.
+    #[arg(long, value_name = "PERCENT", default_value_t = 10.0)]
+    pub threshold: f64,
+}
+
+/// Summary statistics of the timed runs of one part.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Computes the statistics of a non-empty set of samples.
+    /// The 95th percentile uses the nearest-rank method, the standard deviation is the
+    /// sample standard deviation.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "at least one sample is needed");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+        let p95 = sorted[(n * 95).div_ceil(100) - 1];
+
+        let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            p95,
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        }
+    }
+}
+
+/// The measurements of one part, or why it could not be measured.
+struct PartBench {
+    year: u32,
+    day: u32,
+    part: u32,
+    stats: Result<Stats, String>,
+}
+
+/// Medians of a baseline file, keyed by year, day and part.
+type Baseline = BTreeMap<(u32, u32, u32), Duration>;
+
+/// Benchmarks every selected part. Returns `false` if a part failed or regressed.
+pub fn run(args: &BenchArgs, build: &Build, inputs: &[Input]) -> bool {
+    let baseline = args.baseline.as_ref().map(|path| {
+        load_baseline(path).unwrap_or_else(|error| {
+            eprintln!("Could not load baseline {}: {}", path.display(), error);
+            std::process::exit(2);
+        })
+    });
+
+    println!(
+        "Benchmarking {} day(s): {} warmup and {} timed run(s) per part",
+        inputs.len(),
+        args.warmup,
+        args.runs
+    );
+
+    let mut results = Vec::new();
+    for Input { entry, text, .. } in inputs {
+        let aoc = entry.build();
+        for part in 1..=2 {
+            let solve = || match part {
+                1 => aoc.solve_part_1(text),
+                _ => aoc.solve_part_2(text),
+            };
+
+            for _ in 0..args.warmup {
+                let _ = black_box(solve());
+            }
+
+            let mut samples = Vec::with_capacity(args.runs as usize);
+            let mut error = None;
+            for _ in 0..args.runs {
+                let start = Instant::now();
+                let result = black_box(solve());
+                samples.push(start.elapsed());
+                if let Err(e) = result {
+                    error = Some(e.to_string());
+                    break;
+                }
+            }
+
+            results.push(PartBench {
+                year: entry.year,
+                day: entry.day,
+                part,
+                stats: match error {
+                    Some(error) => Err(error),
+                    None => Ok(Stats::from_samples(&samples)),
+                },
+            });
+        }
+    }
+
+    let regressions = print(&results, baseline.as_ref(), args.threshold);
+
+    if let Some(path) = &args.save_baseline {
+        if let Err(error) = fs::write(path, baseline_json(build, &results)) {
+            eprintln!("Could not write baseline {}: {}", path.display(), error);
+            std::process::exit(2);
+        }
+        println!("Baseline saved to {}", path.display());
+    }
+
+    let failed = results
+        .iter()
+        .filter(|result| result.stats.is_err())
+        .count();
+    failed == 0 && regressions == 0
+}
+
+/// Prints the statistics, compared with the baseline if there is one.
+/// Returns the number of regressions.
+fn print(results: &[PartBench], baseline: Option<&Baseline>, threshold: f64) -> usize {
+    let mut header = vec![
+        "Year", "Day", "Part", "Min", "Median", "Mean", "p95", "Stddev",
+    ];
+    if baseline.is_some() {
+        header.extend(["Baseline", "Change"]);
+    }
+
+    let mut regressions = 0;
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .map(|result| {
+            let mut row = vec![
+                result.year.to_string(),
+                format!("{:02}", result.day),
+                result.part.to_string(),
+            ];
+            let stats = match &result.stats {
+                Ok(stats) => stats,
+                Err(error) => {
+                    row.push(format!("error: {}", error));
+                    return row;
+                }
+            };
+            row.extend(
+                [stats.min, stats.median, stats.mean, stats.p95, stats.stddev]
+                    .map(|duration| format!("{:.2?}", duration)),
+            );
+
+            if let Some(baseline) = baseline {
+                match baseline.get(&(result.year, result.day, result.part)) {
+                    Some(&before) => {
+                        let change = percent_change(before, stats.median);
+                        let flag = if change > threshold {
+                            regressions += 1;
+                            " REGRESSION"
+                        } else {
+                            ""
+                        };
+                        row.push(format!("{:.2?}", before));
+                        row.push(format!("{:+.1}%{}", change, flag));
+                    }
+                    None => row.extend(["-".to_string(), "new".to_string()]),
+                }
+            }
+
+            row
+        })
+        .collect();
+
+    table::print_grid::<Vec<String>>(&header, &rows, None, &[1, 3, 4, 5, 6, 7, 8]);
+
+    if baseline.is_some() {
+        println!(
+            "Regressions above {:.1}% of the baseline median: {}",
+            threshold, regressions
+        );
+    }
+
+    regressions
+}
+
+/// How much slower `after` is than `before`, in percent.
+fn percent_change(before: Duration, after: Duration) -> f64 {
+    let before = before.as_nanos().max(1) as f64;
+    (after.as_nanos() as f64 - before) / before * 100.0
+}
+
+fn baseline_json(build: &Build, results: &[PartBench]) -> String {
+    let parts: Vec<Value> = results
+        .iter()
+        .filter_map(|result| {
+            let stats = result.stats.as_ref().ok()?;
+            Some(json!({
+                "year": result.year,
+                "day": result.day,
+                "part": result.part,
+                "min_ns": stats.min.as_nanos() as u64,
+                "median_ns": stats.median.as_nanos() as u64,
+                "mean_ns": stats.mean.as_nanos() as u64,
+                "p95_ns": stats.p95.as_nanos() as u64,
+                "stddev_ns": stats.stddev.as_nanos() as u64,
+            }))
+        })
+        .collect();
+
+    let document = json!({
+        "build_date": build.date,
+        "commit_hash": build.commit,
+        "parts": parts,
+    });
+    serde_json::to_string_pretty(&document).expect("JSON values always serialize")
+}
+
+fn load_baseline(path: &Path) -> Result<Baseline, String> {
+    let content = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let document: Value = serde_json::from_str(&content).map_err(|error| error.to_string())?;
+
+    let parts = document["parts"]
+        .as_array()
+        .ok_or("expected a 'parts' array")?;
+
+    parts
+        .iter()
+        .map(|part| {
+            let field = |name: &str| {
+                part[name]
+                    .as_u64()
+                    .ok_or_else(|| format!("expected '{}' to be a number", name))
+            };
+            let key = (
+                field("year")? as u32,
+                field("day")? as u32,
+                field("part")? as u32,
+            );
+            Ok((key, Duration::from_nanos(field("median_ns")?)))
+        })
+        .collect()
+}
+
+mod test {
+    #[test]
+    /// Statistics of a small, hand-checked set of samples.
+    fn test_bench_stats() {
+        use super::Stats;
+        use std::time::Duration;
+
+        let samples: Vec<Duration> = [5, 1, 4, 2, 3].map(Duration::from_micros).to_vec();
+        let stats = Stats::from_samples(&samples);
+
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert_eq!(stats.mean, Duration::from_micros(3));
+        assert_eq!(stats.p95, Duration::from_micros(5));
+        // Sample variance of 1..=5 is 2.5.
+        assert_eq!(stats.stddev, Duration::from_nanos(1581));
+
+        let even = Stats::from_samples(&[2, 4].map(Duration::from_micros));
+        assert_eq!(even.median, Duration::from_micros(3));
+    }
+}
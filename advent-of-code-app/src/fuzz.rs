@@ -0,0 +1,630 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use advent_of_code_solutions::aoc_solution::{ParamInfo, PuzzleParams};
+use advent_of_code_solutions::registry::{self, SolutionEntry};
+use clap::Args;
+
+use crate::heap;
+use crate::selection::{self, Selection};
+
+/// The checked-in regression corpus, replayed by the tests of the app.
+const CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/corpus");
+
+/// Options of the `fuzz` subcommand.
+#[derive(Args)]
+pub struct FuzzArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+    /// Mutated inputs tried per part.
+    #[arg(short = 'n', long, value_name = "N", default_value_t = 200)]
+    pub cases: u32,
+    /// Seed of the mutations; the same seed tries the same inputs again.
+    #[arg(long, value_name = "SEED", default_value_t = 1)]
+    pub seed: u64,
+    /// How long a part may take on one input before it counts as hanging.
+    #[arg(long, value_name = "MS", default_value_t = 2000)]
+    pub timeout: u64,
+    /// How much heap a part may hold, in MiB, before it counts as allocating without bound.
+    #[arg(long, value_name = "MIB", default_value_t = 512)]
+    pub max_heap: usize,
+    /// Where failing inputs are saved, as `<YEAR>/dayNN/part<P>-<HASH>.txt`, next to a
+    /// `.params` file with the overrides they failed with.
+    #[arg(long, value_name = "DIR", default_value = CORPUS)]
+    pub corpus: PathBuf,
+}
+
+/// The limits one part has to stay within on one input.
+#[derive(Debug, Clone, Copy)]
+pub struct Budget {
+    pub time: Duration,
+    /// Bytes held at any one time.
+    pub heap: usize,
+}
+
+/// How a part misbehaved on an input. Wrong answers and a `SolveError` are fine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Panic(String),
+    Timeout,
+    Heap(usize),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::Panic(message) => write!(f, "panicked: {}", message),
+            Failure::Timeout => write!(f, "ran out of time"),
+            Failure::Heap(bytes) => write!(f, "held {} MiB of heap", bytes >> 20),
+        }
+    }
+}
+
+/// A worker that exceeded its budget. Threads cannot be stopped, so it keeps running until
+/// it finishes or the fuzzer exits.
+pub struct Straggler {
+    label: String,
+    since: Instant,
+    handle: JoinHandle<()>,
+}
+
+/// Feeds mutated inputs to both parts of every selected day, half of them with one setting
+/// overridden, and saves the inputs that make a part panic, hang or allocate without bound
+/// to the corpus. Overflows only panic in debug builds, so those find more. Returns `false`
+/// if a part failed.
+pub fn run(args: &FuzzArgs) -> bool {
+    if !heap::is_counting() {
+        eprintln!("Fuzzing needs the counting allocator; build without the dhat-heap feature");
+        std::process::exit(2);
+    }
+
+    let budget = Budget {
+        time: Duration::from_millis(args.timeout),
+        heap: args.max_heap << 20,
+    };
+    // Inputs of other days stand in for uploads of the wrong puzzle.
+    let donors: Vec<String> = registry::all_solutions()
+        .iter()
+        .flat_map(|entry| examples(entry.source))
+        .collect();
+    let mut rng = Rng::new(args.seed);
+
+    // Panics are expected here and reported with the input that caused them.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut failures = 0;
+    let mut stragglers = Vec::new();
+    'days: for entry in args.selection.entries() {
+        let settings = entry.build().params();
+        let mut seeds = examples(entry.source);
+        let path = selection::input_path(&args.selection.inputs, entry);
+        seeds.extend(fs::read_to_string(path));
+        let seeds = if seeds.is_empty() { &donors } else { &seeds };
+
+        for part in 1..=2 {
+            for _ in 0..args.cases {
+                let mut input = seeds[rng.below(seeds.len())].clone();
+                for _ in 0..=rng.below(3) {
+                    input = mutate(&mut rng, &input, &donors);
+                }
+
+                let mut params = PuzzleParams::new();
+                if !settings.is_empty() && rng.below(2) == 0 {
+                    let setting = &settings[rng.below(settings.len())];
+                    let values = param_values(setting);
+                    params.set(setting.name, values[rng.below(values.len())].clone());
+                }
+
+                let Err(failure) = check(entry, part, &params, &input, budget, &mut stragglers)
+                else {
+                    continue;
+                };
+                failures += 1;
+                let saved = save(&args.corpus, entry, part, &params, &input);
+                eprintln!(
+                    "Day {:02} {} part {}{} {}; input saved to {}",
+                    entry.day,
+                    entry.year,
+                    part,
+                    with_params(&params),
+                    failure,
+                    saved.unwrap_or_else(|error| format!("nowhere: {}", error)),
+                );
+                if let Failure::Panic(_) = failure {
+                    // Later failures of the same part are mostly the same bug.
+                    break;
+                }
+                // The part keeps running on its thread, so the day's other part would be
+                // measured against it; later days may still see it in their time and heap.
+                eprintln!("Skipping the rest of the day, the part keeps running");
+                continue 'days;
+            }
+        }
+        println!(
+            "Day {:02} {}: {} inputs per part",
+            entry.day, entry.year, args.cases
+        );
+        stragglers.retain(|straggler| {
+            let finished = straggler.handle.is_finished();
+            if finished {
+                eprintln!(
+                    "{} finished {:.1} s after it ran out of its budget",
+                    straggler.label,
+                    straggler.since.elapsed().as_secs_f64()
+                );
+            }
+            !finished
+        });
+    }
+
+    panic::set_hook(hook);
+    for straggler in &stragglers {
+        eprintln!(
+            "{} is still running {:.1} s after it ran out of its budget; exiting stops it",
+            straggler.label,
+            straggler.since.elapsed().as_secs_f64()
+        );
+    }
+    println!("{} failures", failures);
+    failures == 0
+}
+
+/// Values to try instead of the default of a setting: the other choices, or numbers around
+/// the default and at the edges of the integer types.
+fn param_values(param: &ParamInfo) -> Vec<String> {
+    if !param.choices.is_empty() {
+        return param
+            .choices
+            .iter()
+            .filter(|&&choice| choice != param.default)
+            .map(|choice| choice.to_string())
+            .collect();
+    }
+    let default: u64 = param.default.parse().unwrap_or(1);
+    [0, 1, default / 2, default * 2]
+        .iter()
+        .map(u64::to_string)
+        .chain(["-1", "4294967296", "18446744073709551615"].map(String::from))
+        .collect()
+}
+
+/// The overrides of a case for messages, like ` with size=6, bytes=12`.
+fn with_params(params: &PuzzleParams) -> String {
+    if params.is_empty() {
+        return String::new();
+    }
+    format!(" with {}", format_params(params))
+}
+
+fn format_params(params: &PuzzleParams) -> String {
+    params
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Solves `part` of `input` with `params` on a worker thread and watches its time and heap.
+/// Rejected params count as an error like any other. A part that exceeds the budget cannot
+/// be stopped; its thread is added to `stragglers`.
+fn check(
+    entry: &'static SolutionEntry,
+    part: u32,
+    params: &PuzzleParams,
+    input: &str,
+    budget: Budget,
+    stragglers: &mut Vec<Straggler>,
+) -> Result<(), Failure> {
+    let (sender, receiver) = mpsc::channel();
+    let label = format!(
+        "Day {:02} {} part {}{}",
+        entry.day,
+        entry.year,
+        part,
+        with_params(params)
+    );
+    let params = params.clone();
+    let input = input.to_string();
+    let before = heap::in_use();
+    let start = Instant::now();
+
+    let handle = thread::Builder::new()
+        .stack_size(8 << 20) // Like the main thread the CLI solves on
+        .spawn(move || {
+            let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+                let mut aoc = entry.build();
+                aoc.configure(&params)?;
+                match part {
+                    1 => aoc.solve_part_1(&input),
+                    _ => aoc.solve_part_2(&input),
+                }
+            }));
+            let _ = sender.send(solved.map(drop).map_err(|payload| {
+                payload
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "with a non-string payload".to_string())
+            }));
+        })
+        .expect("a worker thread can be spawned");
+
+    let failure = loop {
+        match receiver.recv_timeout(Duration::from_millis(5)) {
+            Ok(solved) => return solved.map_err(Failure::Panic),
+            Err(RecvTimeoutError::Disconnected) => unreachable!("the worker always reports"),
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+        let held = heap::in_use().saturating_sub(before);
+        if held > budget.heap {
+            break Failure::Heap(held);
+        }
+        if start.elapsed() > budget.time {
+            break Failure::Timeout;
+        }
+    };
+    stragglers.push(Straggler {
+        label,
+        since: Instant::now(),
+        handle,
+    });
+    Err(failure)
+}
+
+/// Writes a failing input to `<corpus>/<year>/dayNN/part<P>-<HASH>.txt`, and its overrides
+/// to a `.params` file beside it, and returns the path. The hash keeps the same case from
+/// being saved twice.
+fn save(
+    corpus: &Path,
+    entry: &SolutionEntry,
+    part: u32,
+    params: &PuzzleParams,
+    input: &str,
+) -> Result<String, String> {
+    let mut hasher = DefaultHasher::new();
+    input.hash(&mut hasher);
+    let params = format_params(params);
+    if !params.is_empty() {
+        params.hash(&mut hasher);
+    }
+    let dir = corpus
+        .join(entry.year.to_string())
+        .join(format!("day{:02}", entry.day));
+    let path = dir.join(format!("part{}-{:016x}.txt", part, hasher.finish()));
+    fs::create_dir_all(&dir)
+        .and_then(|()| fs::write(&path, input))
+        .and_then(|()| {
+            if params.is_empty() {
+                Ok(())
+            } else {
+                fs::write(path.with_extension("params"), params)
+            }
+        })
+        .map_err(|error| error.to_string())?;
+    Ok(path.display().to_string())
+}
+
+/// The example inputs in the tests of a solution: its raw string literals and the string
+/// literals with more than one line.
+fn examples(source: &str) -> Vec<String> {
+    let mut examples = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find(['r', '"']) {
+        let literal = &rest[start..];
+        let (text, len) = if let Some(raw) = literal.strip_prefix("r#\"") {
+            match raw.find("\"#") {
+                Some(end) => (raw[..end].to_string(), end + 5),
+                None => break,
+            }
+        } else if let Some(quoted) = literal
+            .strip_prefix('"')
+            .filter(|_| !rest[..start].ends_with('\''))
+        // Not the character literal '"'
+        {
+            match string_literal(quoted) {
+                Some((text, end)) => (text, end + 2),
+                None => break,
+            }
+        } else {
+            (String::new(), 1)
+        };
+        if text.trim().contains('\n') {
+            examples.push(text);
+        }
+        rest = &literal[len..];
+    }
+    examples
+}
+
+/// Unescapes the body of a string literal up to its closing quote, returning the text and
+/// the length of the body in the source.
+fn string_literal(body: &str) -> Option<(String, usize)> {
+    let mut text = String::new();
+    let mut chars = body.char_indices().peekable();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '"' => return Some((text, i)),
+            '\\' => match chars.next() {
+                Some((_, 'n')) => text.push('\n'),
+                Some((_, 't')) => text.push('\t'),
+                Some((_, 'r')) => text.push('\r'),
+                // A line continuation skips the line break and the indentation
+                Some((_, '\n')) => while chars.next_if(|(_, ch)| ch.is_whitespace()).is_some() {},
+                Some((_, escaped)) => text.push(escaped),
+                None => break,
+            },
+            _ => text.push(ch),
+        }
+    }
+    None
+}
+
+/// A xorshift generator, so that a seed reproduces the same inputs.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// A number in `0..n`, or 0 if `n` is 0.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n.max(1) as u64) as usize
+    }
+
+    /// A position in `text` that does not split a character.
+    fn boundary(&mut self, text: &str) -> usize {
+        let mut i = self.below(text.len() + 1);
+        while !text.is_char_boundary(i) {
+            i -= 1;
+        }
+        i
+    }
+}
+
+/// Replacements that tend to break parsers: extreme numbers, separators and the tiles
+/// of the grid puzzles.
+const TOKENS: &[&str] = &[
+    "0",
+    "-1",
+    "4294967296",
+    "18446744073709551616",
+    "99999999999999999999",
+    "",
+    " ",
+    "\n",
+    "\n\n",
+    "#",
+    ".",
+    ",",
+    ":",
+    "|",
+    "-",
+    "S",
+    "E",
+    "^",
+    "@",
+    "O",
+    "[",
+];
+
+/// Changes `text` the way uploads go wrong: truncated, with CRLF line endings or trailing
+/// whitespace, another day's input, lines lost or doubled, or odd tokens in between.
+fn mutate(rng: &mut Rng, text: &str, donors: &[String]) -> String {
+    let lines: Vec<&str> = text.split_inclusive('\n').collect();
+    let token = TOKENS[rng.below(TOKENS.len())];
+    match rng.below(10) {
+        0 => text[..rng.boundary(text)].to_string(),
+        1 => text.replace('\n', "\r\n"),
+        2 => lines
+            .iter()
+            .map(|line| match line.strip_suffix('\n') {
+                Some(line) => format!("{} \t\n", line),
+                None => format!("{}  ", line),
+            })
+            .collect(),
+        3 if !donors.is_empty() => donors[rng.below(donors.len())].clone(),
+        4 if !lines.is_empty() => {
+            let mut lines = lines;
+            lines.remove(rng.below(lines.len()));
+            lines.concat()
+        }
+        5 if !lines.is_empty() => {
+            let mut lines = lines;
+            let i = rng.below(lines.len());
+            lines.insert(i, lines[i]);
+            lines.concat()
+        }
+        6 if text.contains(|ch: char| ch.is_ascii_digit()) => {
+            // Replaces one whole number
+            let digits: Vec<usize> = text
+                .char_indices()
+                .filter(|&(i, ch)| {
+                    ch.is_ascii_digit() && !text[..i].ends_with(|ch: char| ch.is_ascii_digit())
+                })
+                .map(|(i, _)| i)
+                .collect();
+            let start = digits[rng.below(digits.len())];
+            let end = text[start..]
+                .find(|ch: char| !ch.is_ascii_digit())
+                .map_or(text.len(), |len| start + len);
+            let number = ["0", "-1", "4294967296", "18446744073709551616"][rng.below(4)];
+            format!("{}{}{}", &text[..start], number, &text[end..])
+        }
+        7 if !text.is_empty() => {
+            let start = rng.boundary(text);
+            let end = text[start..]
+                .chars()
+                .next()
+                .map_or(start, |ch| start + ch.len_utf8());
+            format!("{}{}{}", &text[..start], token, &text[end..])
+        }
+        8 => {
+            let at = rng.boundary(text);
+            format!("{}{}{}", &text[..at], token, &text[at..])
+        }
+        9 => {
+            let alphabet = b" \n#.,:-0123456789abcxyzSE";
+            (0..rng.below(64))
+                .map(|_| alphabet[rng.below(alphabet.len())] as char)
+                .collect()
+        }
+        _ => String::new(),
+    }
+}
+
+mod test {
+    #[test]
+    /// Every input of the regression corpus is solved within the budget without panicking,
+    /// with the overrides saved beside it.
+    fn test_fuzz_corpus() {
+        use super::{check, Budget, CORPUS};
+        use advent_of_code_solutions::aoc_solution::PuzzleParams;
+        use advent_of_code_solutions::registry;
+        use std::fs;
+        use std::time::Duration;
+
+        // Generous, because the tests run in parallel and share the heap counter
+        let budget = Budget {
+            time: Duration::from_secs(20),
+            heap: 1 << 30,
+        };
+        let mut cases = 0;
+        for year in fs::read_dir(CORPUS).unwrap().map(|dir| dir.unwrap().path()) {
+            for day in fs::read_dir(&year).unwrap().map(|dir| dir.unwrap().path()) {
+                let number = |path: &std::path::Path, prefix: &str| -> u32 {
+                    let name = path.file_name().unwrap().to_str().unwrap();
+                    let digits = name.strip_prefix(prefix).unwrap_or(name);
+                    digits[..digits.find('-').unwrap_or(digits.len())]
+                        .parse()
+                        .unwrap()
+                };
+                let entry = registry::find(number(&year, ""), number(&day, "day")).unwrap();
+                for case in fs::read_dir(&day).unwrap().map(|file| file.unwrap().path()) {
+                    if case
+                        .extension()
+                        .is_some_and(|extension| extension == "params")
+                    {
+                        continue;
+                    }
+                    let input = fs::read_to_string(&case).unwrap();
+                    let params: PuzzleParams = fs::read_to_string(case.with_extension("params"))
+                        .map_or(PuzzleParams::new(), |params| params.parse().unwrap());
+                    let part = number(&case, "part");
+                    assert_eq!(
+                        check(entry, part, &params, &input, budget, &mut Vec::new()),
+                        Ok(()),
+                        "{}",
+                        case.display()
+                    );
+                    cases += 1;
+                }
+            }
+        }
+        assert!(cases > 0, "the corpus in {} is empty", CORPUS);
+    }
+
+    #[test]
+    /// A short fuzz with a fixed seed: every solution answers or fails with an error on
+    /// mutated examples, and on its examples with each setting overridden, without
+    /// panicking or running out of its budget.
+    fn test_fuzz_all_solutions() {
+        use super::{check, examples, mutate, param_values, Budget, Rng};
+        use advent_of_code_solutions::aoc_solution::PuzzleParams;
+        use advent_of_code_solutions::registry;
+        use std::time::Duration;
+
+        // Generous, because the tests run in parallel and share the heap counter
+        let budget = Budget {
+            time: Duration::from_secs(20),
+            heap: 1 << 30,
+        };
+        let donors: Vec<String> = registry::all_solutions()
+            .iter()
+            .flat_map(|entry| examples(entry.source))
+            .collect();
+        let mut rng = Rng::new(25);
+        for entry in registry::all_solutions() {
+            let seeds = examples(entry.source);
+            let seeds = if seeds.is_empty() { &donors } else { &seeds };
+            for part in 1..=2 {
+                for _ in 0..32 {
+                    let seed = &seeds[rng.below(seeds.len())];
+                    let input = mutate(&mut rng, seed, &donors);
+                    assert_eq!(
+                        check(
+                            entry,
+                            part,
+                            &PuzzleParams::new(),
+                            &input,
+                            budget,
+                            &mut Vec::new()
+                        ),
+                        Ok(()),
+                        "Day {:02} {} part {} on {:?}",
+                        entry.day,
+                        entry.year,
+                        part,
+                        input
+                    );
+                }
+            }
+            for setting in entry.build().params() {
+                for value in param_values(setting) {
+                    let params = PuzzleParams::from_iter([(setting.name, value)]);
+                    for part in 1..=2 {
+                        for input in seeds {
+                            assert_eq!(
+                                check(entry, part, &params, input, budget, &mut Vec::new()),
+                                Ok(()),
+                                "Day {:02} {} part {} with {:?} on {:?}",
+                                entry.day,
+                                entry.year,
+                                part,
+                                params,
+                                input
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    /// Examples are found in sources, and the same seed mutates them the same way.
+    fn test_fuzz_mutations() {
+        use super::{examples, mutate, Rng};
+
+        let source = "let input = r#\"\n1 2\n3 4\n\"#;\nlet other = \"5 6\\n\\\n    7 8\\n\";\n\
+                      let single = \"title\";";
+        let found = examples(source);
+        assert_eq!(found, ["\n1 2\n3 4\n", "5 6\n7 8\n"]);
+
+        let mutations = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..50)
+                .map(|_| mutate(&mut rng, &found[0], &found))
+                .collect::<Vec<_>>()
+        };
+        let first = mutations(7);
+        assert_eq!(first, mutations(7));
+        assert!(first.contains(&"\r\n1 2\r\n3 4\r\n".to_string()));
+        assert!(first.iter().any(|input| input.len() < found[0].len()));
+    }
+}
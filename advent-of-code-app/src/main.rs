@@ -1,76 +1,205 @@
-use std::collections::BTreeMap;
-use std::fs::File;
-use std::io::prelude::*;
 use std::path::PathBuf;
-use std::time::Instant;
 
-use advent_of_code_solutions::advent_of_code_2022;
-use advent_of_code_solutions::advent_of_code_2023;
-use advent_of_code_solutions::advent_of_code_2024;
-use advent_of_code_solutions::aoc_solution::AoCSolution;
-use clap::Parser;
+use advent_of_code_solutions::aoc_solution::PuzzleParams;
+use clap::{Parser, Subcommand};
+
+use crate::answers::{Answers, Verdict};
+use crate::bench::BenchArgs;
+use crate::fuzz::FuzzArgs;
+use crate::output::{Build, Format};
+use crate::profile::ProfileArgs;
+use crate::runner::{DayRun, PartRun};
+use crate::selection::{Input, Selection};
+use crate::trace::TraceArgs;
+
+mod answers;
+mod bench;
+mod fuzz;
+mod heap;
+mod output;
+mod profile;
+mod runner;
+mod selection;
+mod table;
+mod trace;
 
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
 static ALLOC: dhat::Alloc = dhat::Alloc;
 
+#[cfg(not(feature = "dhat-heap"))]
+#[global_allocator]
+static ALLOC: heap::CountingAlloc = heap::CountingAlloc;
+
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
-    #[arg(short, long, value_name = "YEAR")]
-    year: u32,
-    #[arg(short, long, value_name = "DAY")]
-    day: u32,
-    #[arg(short, long, value_name = "FILE")]
-    input: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    selection: Selection,
+    /// Expected answers to verify against; a missing file leaves every answer unknown.
+    #[arg(long, value_name = "FILE", default_value = "answers.toml")]
+    answers: PathBuf,
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Reports peak heap, number of allocations and bytes allocated for every part.
+    #[arg(long)]
+    profile_heap: bool,
+    /// Prints the intermediate values that explain each answer, for the puzzles that
+    /// record any, e.g. the press counts of every claw machine of day 13 2024.
+    #[arg(long)]
+    explain: bool,
+    /// Overrides a puzzle setting, e.g. `--param size=6` for the example of day 18 2024.
+    /// Answers are not checked when a setting is overridden.
+    #[arg(long = "param", value_name = "NAME=VALUE", requires = "day", value_parser = parse_param)]
+    params: Vec<(String, String)>,
+    #[command(flatten)]
+    trace: TraceArgs,
 }
 
-fn main() -> std::io::Result<()> {
-    let cli = Cli::parse();
-    let day = cli.day;
-    let year = cli.year;
-    let input = cli.input;
-
-    let build_date = env!("BUILD_DATE");
-    let commit_hash = env!("COMMIT_HASH_SHORT");
-
-    println!("Build Date  : {}", build_date);
-    println!("Commit Hash : {}", commit_hash);
-
-    let mut f = File::open(input)?;
-    let mut buffer = String::new();
-    f.read_to_string(&mut buffer)?;
-
-    let mut solutions: BTreeMap<u32, BTreeMap<u32, Box<dyn AoCSolution>>> = BTreeMap::new();
+/// Parses the value of `--param`.
+fn parse_param(text: &str) -> Result<(String, String), String> {
+    text.split_once('=')
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .ok_or_else(|| format!("expected NAME=VALUE, got '{}'", text))
+}
 
-    let mut solutions_2022: BTreeMap<u32, Box<dyn AoCSolution>> = BTreeMap::new();
-    let mut solutions_2023: BTreeMap<u32, Box<dyn AoCSolution>> = BTreeMap::new();
-    let mut solutions_2024: BTreeMap<u32, Box<dyn AoCSolution>> = BTreeMap::new();
+#[derive(Subcommand)]
+enum Command {
+    /// Times every selected part over many runs and compares with a baseline.
+    Bench(BenchArgs),
+    /// Measures time and peak heap of every selected part and regenerates the
+    /// runtime metadata table of the solutions crate.
+    Profile(ProfileArgs),
+    /// Feeds mutated inputs to every selected part and saves the inputs that make a part
+    /// panic, hang or allocate without bound to a regression corpus.
+    Fuzz(FuzzArgs),
+}
 
-    let _ = advent_of_code_2022(&mut solutions_2022);
-    let _ = advent_of_code_2023(&mut solutions_2023);
-    let _ = advent_of_code_2024(&mut solutions_2024);
+fn main() {
+    let cli = Cli::parse();
 
-    solutions.insert(2022, solutions_2022);
-    solutions.insert(2023, solutions_2023);
-    solutions.insert(2024, solutions_2024);
+    let build = Build {
+        date: env!("BUILD_DATE"),
+        commit: env!("COMMIT_HASH"),
+    };
+
+    // Machine-readable formats carry the build information themselves.
+    if cli.format == Format::Text {
+        println!("Build Date  : {}", build.date);
+        println!("Commit Hash : {}", env!("COMMIT_HASH_SHORT"));
+    }
+
+    let ok = match &cli.command {
+        Some(Command::Bench(args)) => bench::run(args, &build, &args.selection.load()),
+        Some(Command::Profile(args)) => profile::run(args, &args.selection.load()),
+        Some(Command::Fuzz(args)) => fuzz::run(args),
+        None => solve(&cli, &build),
+    };
+
+    if !ok {
+        std::process::exit(1);
+    }
+}
 
-    let aoc = solutions.get(&year).unwrap().get(&day).unwrap();
+/// Solves the selected days once and prints the results.
+/// Returns `false` if a part failed or is wrong.
+fn solve(cli: &Cli, build: &Build) -> bool {
+    let answers = Answers::load(&cli.answers).unwrap_or_else(|error| {
+        eprintln!("Could not load answers: {}", error);
+        std::process::exit(2);
+    });
+
+    if cli.profile_heap && !heap::is_counting() {
+        eprintln!(
+            "--profile-heap needs the counting allocator; build without the dhat-heap feature"
+        );
+        std::process::exit(2);
+    }
+
+    let inputs: Vec<Input> = cli.selection.load();
+
+    let params: PuzzleParams = cli.params.iter().cloned().collect();
+    if let Err(error) = inputs[0].entry.build().configure(&params) {
+        eprintln!("{}", error);
+        std::process::exit(2);
+    }
 
     #[cfg(feature = "dhat-heap")]
     let _profiler = dhat::Profiler::new_heap();
 
-    println!("Run AoC Solution for day {:02} {}", day, year);
-
-    let start_part_1 = Instant::now();
-    let part_1 = aoc.part_1_final(buffer.as_str());
-    let duration_part_1 = start_part_1.elapsed();
-    println!("Part 1: {}, {:?}", part_1, duration_part_1);
-
-    let start_part_2 = Instant::now();
-    let part_2 = aoc.part_2_final(buffer.as_str());
-    let duration_part_2 = start_part_2.elapsed();
-    println!("Part 2: {}, {:?}", part_2, duration_part_2);
+    if cli.format == Format::Text && cli.selection.is_single_day() {
+        let entry = inputs[0].entry;
+        println!("Run AoC Solution for day {:02} {}", entry.day, entry.year);
+    }
+
+    let runs: Vec<DayRun> = inputs
+        .iter()
+        .map(|input| runner::run(input, &params, &answers, cli.profile_heap, cli.explain))
+        .collect();
+
+    match cli.format {
+        Format::Text if cli.selection.is_single_day() => {
+            for run in &runs {
+                println!("Input: {}", run.source);
+                for (part, result) in (1..).zip(&run.parts) {
+                    report(part, result);
+                }
+            }
+        }
+        Format::Text => {
+            table::print(&runs);
+            if cli.profile_heap {
+                table::print_heap(&runs);
+            }
+        }
+        format => print!("{}", output::render(format, build, &runs)),
+    }
+
+    let traced = match &cli.trace.trace {
+        Some(path) => trace::run(&cli.trace, path, &inputs[0], &params),
+        None => true,
+    };
+
+    runs.iter().all(DayRun::is_ok) && traced
+}
 
-    Ok(())
+/// Prints the result of one part. Failures and wrong answers go to stderr.
+fn report(part: u32, run: &PartRun) {
+    match (&run.result, &run.verdict) {
+        (Ok(answer), Verdict::Fail { .. }) => eprintln!(
+            "Part {}: {}, {:?} [{}]",
+            part, answer, run.duration, run.verdict
+        ),
+        (Ok(answer), _) => println!(
+            "Part {}: {}, {:?} [{}]",
+            part, answer, run.duration, run.verdict
+        ),
+        (Err(error), _) => eprintln!(
+            "Part {}: {}, {:?} [{}]",
+            part, error, run.duration, run.verdict
+        ),
+    }
+
+    if let Some(heap) = &run.heap {
+        println!(
+            "        heap: peak {} B, {} allocations, {} B allocated{}",
+            heap.peak,
+            heap.allocations,
+            heap.allocated,
+            if heap.peak > table::ESP32_S3_SRAM {
+                " [exceeds the ESP32-S3 SRAM]"
+            } else {
+                ""
+            }
+        );
+    }
+
+    if let Some(explain) = &run.explanation {
+        for line in explain.to_text().lines() {
+            println!("        {}", line);
+        }
+    }
 }
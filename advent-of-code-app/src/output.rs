@@ -0,0 +1,284 @@
+use std::fmt::Write;
+
+use advent_of_code_solutions::explain::Explain;
+use clap::ValueEnum;
+use serde_json::{json, Value};
+
+use crate::answers::Verdict;
+use crate::runner::{DayRun, PartRun};
+
+/// How the results are written to stdout.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human-readable lines for a single day, a table otherwise.
+    Text,
+    Json,
+    Csv,
+    JunitXml,
+}
+
+/// The build the results were produced with, as embedded by `build.rs`.
+pub struct Build {
+    pub date: &'static str,
+    pub commit: &'static str,
+}
+
+/// Renders the runs in one of the machine-readable formats.
+pub fn render(format: Format, build: &Build, runs: &[DayRun]) -> String {
+    match format {
+        Format::Text => unreachable!("text output is printed by the caller"),
+        Format::Json => json(build, runs),
+        Format::Csv => csv(build, runs),
+        Format::JunitXml => junit_xml(build, runs),
+    }
+}
+
+fn verdict_name(verdict: &Verdict) -> &'static str {
+    match verdict {
+        Verdict::Pass => "pass",
+        Verdict::Fail { .. } => "fail",
+        Verdict::Unknown => "unknown",
+    }
+}
+
+fn json_part(part: &PartRun) -> Value {
+    json!({
+        "answer": part.result.as_ref().ok().map(|answer| answer.to_string()),
+        "error": part.result.as_ref().err().map(|error| error.to_string()),
+        "verdict": verdict_name(&part.verdict),
+        "expected": match &part.verdict {
+            Verdict::Fail { expected } => Some(expected),
+            _ => None,
+        },
+        "duration_ns": part.duration.as_nanos() as u64,
+        "heap": part.heap.map(|heap| json!({
+            "peak_bytes": heap.peak,
+            "allocations": heap.allocations,
+            "allocated_bytes": heap.allocated,
+        })),
+        "explanation": part.explanation.as_ref().map(|explain| explain
+            .facts()
+            .iter()
+            .map(|fact| json!({"name": fact.name, "value": fact.value}))
+            .collect::<Vec<_>>()),
+        "explanation_skipped": part.explanation.as_ref().map(Explain::skipped),
+    })
+}
+
+fn json(build: &Build, runs: &[DayRun]) -> String {
+    let results: Vec<Value> = runs
+        .iter()
+        .map(|run| {
+            json!({
+                "year": run.year,
+                "day": run.day,
+                "title": run.title,
+                "input": run.source,
+                "part_1": json_part(&run.parts[0]),
+                "part_2": json_part(&run.parts[1]),
+            })
+        })
+        .collect();
+
+    let document = json!({
+        "build_date": build.date,
+        "commit_hash": build.commit,
+        "results": results,
+    });
+    serde_json::to_string_pretty(&document).expect("JSON values always serialize")
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn csv(build: &Build, runs: &[DayRun]) -> String {
+    let mut out = String::from(
+        "build_date,commit_hash,year,day,title,input,\
+         part_1,part_1_error,part_1_verdict,part_1_ns,\
+         part_1_peak_bytes,part_1_allocations,part_1_allocated_bytes,\
+         part_2,part_2_error,part_2_verdict,part_2_ns,\
+         part_2_peak_bytes,part_2_allocations,part_2_allocated_bytes\n",
+    );
+
+    for run in runs {
+        let mut fields = vec![
+            build.date.to_string(),
+            build.commit.to_string(),
+            run.year.to_string(),
+            run.day.to_string(),
+            run.title.to_string(),
+            run.source.clone(),
+        ];
+        for part in &run.parts {
+            let (answer, error) = match &part.result {
+                Ok(answer) => (answer.to_string(), String::new()),
+                Err(error) => (String::new(), error.to_string()),
+            };
+            fields.extend([
+                answer,
+                error,
+                verdict_name(&part.verdict).to_string(),
+                part.duration.as_nanos().to_string(),
+            ]);
+            // The heap columns stay empty unless the heap was profiled.
+            fields.extend(match part.heap {
+                Some(heap) => [heap.peak, heap.allocations, heap.allocated].map(|n| n.to_string()),
+                None => Default::default(),
+            });
+        }
+        let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Escapes text for use in XML attributes and character data.
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// One test suite per year and one test case per part. Errors become `<error>`,
+/// wrong answers `<failure>`. JUnit wants seconds, so the nanoseconds go into a property.
+fn junit_xml(build: &Build, runs: &[DayRun]) -> String {
+    let mut years: Vec<u32> = runs.iter().map(|run| run.year).collect();
+    years.dedup();
+
+    let parts = || runs.iter().flat_map(|run| run.parts.iter());
+    let total_seconds: f64 = parts().map(|part| part.duration.as_secs_f64()).sum();
+    let failures = parts()
+        .filter(|part| part.result.is_ok() && !part.is_ok())
+        .count();
+    let errors = parts().filter(|part| part.result.is_err()).count();
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"advent-of-code\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.9}\">",
+        parts().count(),
+        failures,
+        errors,
+        total_seconds
+    );
+
+    for year in years {
+        let days: Vec<&DayRun> = runs.iter().filter(|run| run.year == year).collect();
+        let suite_parts = || days.iter().flat_map(|run| run.parts.iter());
+        let _ = writeln!(
+            out,
+            "  <testsuite name=\"aoc{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.9}\">",
+            year,
+            suite_parts().count(),
+            suite_parts()
+                .filter(|part| part.result.is_ok() && !part.is_ok())
+                .count(),
+            suite_parts().filter(|part| part.result.is_err()).count(),
+            suite_parts()
+                .map(|part| part.duration.as_secs_f64())
+                .sum::<f64>()
+        );
+        let _ = writeln!(out, "    <properties>");
+        let _ = writeln!(
+            out,
+            "      <property name=\"build_date\" value=\"{}\"/>",
+            xml_escape(build.date)
+        );
+        let _ = writeln!(
+            out,
+            "      <property name=\"commit_hash\" value=\"{}\"/>",
+            xml_escape(build.commit)
+        );
+        let _ = writeln!(out, "    </properties>");
+
+        for run in days {
+            for (number, part) in (1..).zip(&run.parts) {
+                let _ = writeln!(
+                    out,
+                    "    <testcase classname=\"aoc{}.day{:02}\" name=\"part_{}: {}\" time=\"{:.9}\">",
+                    run.year,
+                    run.day,
+                    number,
+                    xml_escape(run.title),
+                    part.duration.as_secs_f64()
+                );
+                let _ = write!(
+                    out,
+                    "      <properties><property name=\"input\" value=\"{}\"/>\
+                     <property name=\"duration_ns\" value=\"{}\"/>",
+                    xml_escape(&run.source),
+                    part.duration.as_nanos()
+                );
+                if let Some(heap) = &part.heap {
+                    let _ = write!(
+                        out,
+                        "<property name=\"peak_bytes\" value=\"{}\"/>\
+                         <property name=\"allocations\" value=\"{}\"/>\
+                         <property name=\"allocated_bytes\" value=\"{}\"/>",
+                        heap.peak, heap.allocations, heap.allocated
+                    );
+                }
+                let _ = writeln!(out, "</properties>");
+                match (&part.result, &part.verdict) {
+                    (Err(error), _) => {
+                        let _ = writeln!(
+                            out,
+                            "      <error message=\"{}\"/>",
+                            xml_escape(&error.to_string())
+                        );
+                    }
+                    (Ok(answer), Verdict::Fail { expected }) => {
+                        let _ = writeln!(
+                            out,
+                            "      <failure message=\"expected {}, got {}\"/>",
+                            xml_escape(expected),
+                            xml_escape(&answer.to_string())
+                        );
+                    }
+                    (Ok(answer), _) => {
+                        let _ = writeln!(
+                            out,
+                            "      <system-out>{}</system-out>",
+                            xml_escape(&answer.to_string())
+                        );
+                    }
+                }
+                let _ = writeln!(out, "    </testcase>");
+            }
+        }
+        let _ = writeln!(out, "  </testsuite>");
+    }
+
+    out.push_str("</testsuites>\n");
+    out
+}
+
+mod test {
+    #[test]
+    /// Fields with separators are quoted and markup characters are escaped.
+    fn test_output_escaping() {
+        use super::{csv_field, xml_escape};
+
+        assert_eq!(csv_field("Historian Hysteria"), "Historian Hysteria");
+        assert_eq!(csv_field("co,de"), "\"co,de\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(xml_escape("a < b & \"c\""), "a &lt; b &amp; &quot;c&quot;");
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use advent_of_code_solutions::aoc_solution::RuntimeProfile;
+use advent_of_code_solutions::registry;
+use advent_of_code_solutions::runtime_profiles::PROFILES;
+use clap::Args;
+
+use crate::bench::Stats;
+use crate::heap;
+use crate::selection::{Input, Selection};
+use crate::table;
+
+/// Options of the `profile` subcommand.
+#[derive(Args)]
+pub struct ProfileArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+    /// Timed runs per part; the median becomes the host time.
+    #[arg(short = 'n', long, value_name = "N", default_value_t = 5,
+          value_parser = clap::value_parser!(u32).range(1..))]
+    pub runs: u32,
+    /// How many times slower the ESP32 is than this machine. Device times are only an
+    /// estimate: the host time multiplied by this factor, which is recorded in the table.
+    #[arg(long, value_name = "FACTOR", default_value_t = 50.0)]
+    pub device_factor: f64,
+    /// The generated metadata table. Parts that are not profiled keep their entry.
+    #[arg(long, value_name = "FILE",
+          default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/../advent-of-code-solutions/src/runtime_profiles.rs"))]
+    pub output: PathBuf,
+}
+
+/// Profiles every selected part on its reference input and regenerates the metadata
+/// table. Returns `false` if a part failed.
+pub fn run(args: &ProfileArgs, inputs: &[Input]) -> bool {
+    if !heap::is_counting() {
+        eprintln!("Profiling needs the counting allocator; build without the dhat-heap feature");
+        std::process::exit(2);
+    }
+
+    let mut profiles: BTreeMap<(u32, u32, u32), RuntimeProfile> =
+        PROFILES.iter().copied().collect();
+    let mut measured = Vec::new();
+    let mut ok = true;
+
+    for Input { entry, text, .. } in inputs {
+        let aoc = entry.build();
+        for part in 1..=2 {
+            let solve = || match part {
+                1 => aoc.solve_part_1(text),
+                _ => aoc.solve_part_2(text),
+            };
+
+            // The first run doubles as warmup and heap measurement.
+            let (result, heap) = heap::measure(solve);
+            if let Err(error) = result {
+                eprintln!(
+                    "Day {:02} {} part {}: {}",
+                    entry.day, entry.year, part, error
+                );
+                ok = false;
+                continue;
+            }
+
+            let samples: Vec<Duration> = (0..args.runs)
+                .map(|_| {
+                    let start = Instant::now();
+                    let _ = std::hint::black_box(solve());
+                    start.elapsed()
+                })
+                .collect();
+            let host = Stats::from_samples(&samples).median;
+
+            let profile = RuntimeProfile {
+                host,
+                device: host, // Scaled once the factor is known
+                peak_heap: heap.peak,
+            };
+            measured.push((entry.year, entry.day, part));
+            profiles.insert((entry.year, entry.day, part), profile);
+        }
+    }
+
+    let factor = args.device_factor;
+    // Every entry of the table is estimated with the same factor
+    for profile in profiles.values_mut() {
+        profile.device = profile.host.mul_f64(factor);
+    }
+
+    let rows: Vec<Vec<String>> = measured
+        .iter()
+        .map(|&(year, day, part)| {
+            let profile = profiles[&(year, day, part)];
+            vec![
+                year.to_string(),
+                format!("{:02}", day),
+                part.to_string(),
+                format!("{:.2?}", profile.host),
+                format!("{:.2?}", profile.device),
+                profile.peak_heap.to_string(),
+            ]
+        })
+        .collect();
+
+    table::print_grid::<Vec<String>>(
+        &[
+            "Year",
+            "Day",
+            "Part",
+            "Host",
+            "Device (est.)",
+            "Peak heap [B]",
+        ],
+        &rows,
+        None,
+        &[1, 3, 4, 5],
+    );
+
+    if let Err(error) = fs::write(&args.output, generate(&profiles, factor)) {
+        eprintln!("Could not write {}: {}", args.output.display(), error);
+        std::process::exit(2);
+    }
+    println!("Runtime profiles written to {}", args.output.display());
+
+    let unmeasured = registry::all_solutions()
+        .iter()
+        .flat_map(|entry| (1..=2).map(move |part| (entry.year, entry.day, part)))
+        .filter(|key| !profiles.contains_key(key))
+        .count();
+    if unmeasured > 0 {
+        println!(
+            "{} parts have no profile and keep the hand-timed countdown of device_baseline",
+            unmeasured
+        );
+    }
+
+    ok
+}
+
+/// Renders the metadata table as the `runtime_profiles` module of the solutions crate.
+fn generate(profiles: &BTreeMap<(u32, u32, u32), RuntimeProfile>, factor: f64) -> String {
+    let mut out = String::from(
+        "//! Measured runtime profiles of the solutions, keyed by year, day and part.\n\
+         //!\n\
+         //! Generated by `advent-of-code-app profile`; do not edit by hand.\n\n",
+    );
+    if !profiles.is_empty() {
+        out.push_str("use std::time::Duration;\n\n");
+    }
+    out.push_str("use crate::aoc_solution::RuntimeProfile;\n\n");
+    let _ = write!(
+        out,
+        "/// How many times slower than the profiling machine the ESP32 is taken to be. Every\n\
+         /// device time is an estimate: the host time multiplied by this factor.\n\
+         pub const DEVICE_FACTOR: f64 = {:?};\n\n",
+        factor
+    );
+    out.push_str("/// Every measured part, ordered by year, day and part.\n");
+
+    if profiles.is_empty() {
+        out.push_str("pub static PROFILES: &[((u32, u32, u32), RuntimeProfile)] = &[];\n");
+    } else {
+        out.push_str("pub static PROFILES: &[((u32, u32, u32), RuntimeProfile)] = &[\n");
+        for ((year, day, part), profile) in profiles {
+            let _ = write!(
+                out,
+                "    (\n        ({}, {}, {}),\n        RuntimeProfile {{\n            \
+                 host: Duration::from_nanos({}),\n            \
+                 device: Duration::from_nanos({}),\n            \
+                 peak_heap: {},\n        }},\n    ),\n",
+                year,
+                day,
+                part,
+                profile.host.as_nanos(),
+                profile.device.as_nanos(),
+                profile.peak_heap
+            );
+        }
+        out.push_str("];\n");
+    }
+
+    out.push_str(
+        "\n/// Returns the profile of one part, if it has been measured.\n\
+         pub fn lookup(year: u32, day: u32, part: u32) -> Option<RuntimeProfile> {\n    \
+         PROFILES\n        \
+         .iter()\n        \
+         .find(|(key, _)| *key == (year, day, part))\n        \
+         .map(|(_, profile)| *profile)\n\
+         }\n",
+    );
+    out
+}
+
+mod test {
+    #[test]
+    /// The table records every profile and the device factor they were estimated with.
+    fn test_profile_generate() {
+        use super::generate;
+        use advent_of_code_solutions::aoc_solution::RuntimeProfile;
+        use std::collections::BTreeMap;
+        use std::time::Duration;
+
+        let profiles = BTreeMap::from([(
+            (2024, 6, 2),
+            RuntimeProfile {
+                host: Duration::from_millis(700),
+                device: Duration::from_secs(35),
+                peak_heap: 4096,
+            },
+        )]);
+        let table = generate(&profiles, 50.0);
+        assert!(table.contains("pub const DEVICE_FACTOR: f64 = 50.0;\n"));
+        assert!(table.contains("        (2024, 6, 2),\n"));
+        assert!(table.contains("device: Duration::from_nanos(35000000000),"));
+        assert!(generate(&BTreeMap::new(), 50.0).contains("= &[];\n"));
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::{Duration, Instant};
+
+use advent_of_code_solutions::aoc_solution::{Answer, PuzzleParams, SolveError};
+use advent_of_code_solutions::explain::Explain;
+
+use crate::answers::{Answers, Verdict};
+use crate::heap::{self, HeapStats};
+use crate::selection::Input;
+
+/// The outcome of one part of a puzzle.
+pub struct PartRun {
+    pub result: Result<Answer, SolveError>,
+    pub duration: Duration,
+    pub verdict: Verdict,
+    /// Heap usage, if it was profiled.
+    pub heap: Option<HeapStats>,
+    /// The intermediate facts behind the answer, if they were recorded.
+    pub explanation: Option<Explain>,
+}
+
+impl PartRun {
+    /// A part is fine if it was solved and the answer is not known to be wrong.
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok() && !matches!(self.verdict, Verdict::Fail { .. })
+    }
+}
+
+/// The outcome of both parts of one day.
+pub struct DayRun {
+    pub year: u32,
+    pub day: u32,
+    pub title: &'static str,
+    /// Where the input came from: a file path or `<stdin>`.
+    pub source: String,
+    pub parts: [PartRun; 2],
+}
+
+impl DayRun {
+    pub fn is_ok(&self) -> bool {
+        self.parts.iter().all(PartRun::is_ok)
+    }
+}
+
+/// The most facts recorded per part with `--explain`; later facts are only counted.
+pub const EXPLAIN_LIMIT: usize = 100;
+
+/// Solves both parts of `input` with the puzzle settings overridden by `params`, timing
+/// each part and checking it against `answers`. Answers are only known for the real
+/// puzzle, so they are not checked if `params` overrides anything.
+/// With `profile_heap`, the heap usage of each part is recorded as well, and with
+/// `explain` the first `EXPLAIN_LIMIT` facts that explain each answer.
+pub fn run(
+    input: &Input,
+    params: &PuzzleParams,
+    answers: &Answers,
+    profile_heap: bool,
+    explain: bool,
+) -> DayRun {
+    let entry = input.entry;
+    let mut aoc = entry.build();
+    let configured = aoc.configure(params);
+
+    let part = |number: u32| {
+        let mut explanation = explain.then(|| Explain::new(EXPLAIN_LIMIT));
+        let mut solve = || {
+            let start = Instant::now();
+            let result = match (&configured, number, &mut explanation) {
+                (Err(error), _, _) => Err(error.clone()),
+                (Ok(()), 1, None) => aoc.solve_part_1(&input.text),
+                (Ok(()), _, None) => aoc.solve_part_2(&input.text),
+                (Ok(()), 1, Some(explain)) => aoc.explain_part_1(&input.text, explain),
+                (Ok(()), _, Some(explain)) => aoc.explain_part_2(&input.text, explain),
+            };
+            (result, start.elapsed())
+        };
+
+        let ((result, duration), heap) = if profile_heap {
+            let (run, heap) = heap::measure(solve);
+            (run, Some(heap))
+        } else {
+            (solve(), None)
+        };
+
+        PartRun {
+            verdict: if params.is_empty() {
+                answers.check(entry.year, entry.day, number, &result)
+            } else {
+                Verdict::Unknown
+            },
+            result,
+            duration,
+            heap,
+            explanation,
+        }
+    };
+
+    DayRun {
+        year: entry.year,
+        day: entry.day,
+        title: entry.title,
+        source: input.source.clone(),
+        parts: [part(1), part(2)],
+    }
+}
@@ -0,0 +1,157 @@
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+
+use advent_of_code_solutions::registry::{self, SolutionEntry};
+use clap::Args;
+
+/// Which puzzles to run and where their inputs come from.
+#[derive(Args)]
+#[command(group(clap::ArgGroup::new("target").required(true).args(["year", "all"])))]
+pub struct Selection {
+    /// Runs every registered day of this year unless `--day` is given.
+    #[arg(short, long, value_name = "YEAR")]
+    pub year: Option<u32>,
+    #[arg(short, long, value_name = "DAY", requires = "year")]
+    pub day: Option<u32>,
+    /// Runs every registered day of every year.
+    #[arg(short, long, conflicts_with_all = ["year", "day"])]
+    pub all: bool,
+    /// Puzzle inputs of a single day, each solved in turn; `-` reads stdin. Defaults to
+    /// `<INPUTS>/<YEAR>/dayNN.txt`, or to stdin if that file is missing and stdin is piped.
+    #[arg(short, long, value_name = "FILE", num_args = 1.., requires = "day")]
+    pub input: Vec<PathBuf>,
+    /// Directory with the puzzle inputs, laid out as `<YEAR>/dayNN.txt`.
+    #[arg(long, value_name = "DIR", default_value = "inputs")]
+    pub inputs: PathBuf,
+}
+
+/// A puzzle input together with the solution it is meant for.
+pub struct Input {
+    pub entry: &'static SolutionEntry,
+    /// Where the input came from: a file path or `<stdin>`.
+    pub source: String,
+    pub text: String,
+}
+
+impl Selection {
+    /// Whether a single day was asked for.
+    pub fn is_single_day(&self) -> bool {
+        self.day.is_some()
+    }
+
+    /// Looks up the selected solutions. Exits with status 2 if a selected year or day
+    /// has none.
+    pub fn entries(&self) -> Vec<&'static SolutionEntry> {
+        let entries: Vec<&'static SolutionEntry> = match (self.year, self.day) {
+            (Some(year), Some(day)) => registry::find(year, day).into_iter().collect(),
+            (Some(year), None) => registry::all_solutions()
+                .iter()
+                .filter(|entry| entry.year == year)
+                .collect(),
+            _ => registry::all_solutions().iter().collect(),
+        };
+
+        match (self.year, self.day) {
+            (Some(year), Some(day)) if entries.is_empty() => {
+                eprintln!("No solution for day {:02} {}", day, year);
+                std::process::exit(2);
+            }
+            (Some(year), None) if entries.is_empty() => {
+                eprintln!("No solutions for {}", year);
+                std::process::exit(2);
+            }
+            _ => {}
+        }
+
+        entries
+    }
+
+    /// Looks up the selected solutions and reads their inputs.
+    ///
+    /// A single day must have all its inputs; when running many days, days without an
+    /// input file are skipped. Exits with status 2 if nothing can be run.
+    pub fn load(&self) -> Vec<Input> {
+        let entries = self.entries();
+
+        if let [entry] = entries.as_slice() {
+            if self.is_single_day() {
+                return self.load_single_day(entry);
+            }
+        }
+
+        let mut loaded = Vec::new();
+        for entry in entries {
+            let path = input_path(&self.inputs, entry);
+            match fs::read_to_string(&path) {
+                Ok(text) => loaded.push(Input {
+                    entry,
+                    source: path.display().to_string(),
+                    text,
+                }),
+                Err(error) => eprintln!(
+                    "Skipping day {:02} {}: {}: {}",
+                    entry.day,
+                    entry.year,
+                    path.display(),
+                    error
+                ),
+            }
+        }
+
+        if loaded.is_empty() {
+            eprintln!("No inputs found in {}", self.inputs.display());
+            std::process::exit(2);
+        }
+
+        loaded
+    }
+
+    /// Reads every `--input` of a single day, or its conventional input file.
+    fn load_single_day(&self, entry: &'static SolutionEntry) -> Vec<Input> {
+        let stdin = Path::new("-");
+        let mut paths = self.input.clone();
+        if paths.is_empty() {
+            let path = input_path(&self.inputs, entry);
+            paths.push(if !path.exists() && !io::stdin().is_terminal() {
+                stdin.to_path_buf()
+            } else {
+                path
+            });
+        }
+
+        if paths.iter().filter(|path| *path == stdin).count() > 1 {
+            eprintln!("stdin can only be read once");
+            std::process::exit(2);
+        }
+
+        paths
+            .iter()
+            .map(|path| {
+                let (source, read) = if path == stdin {
+                    ("<stdin>".to_string(), io::read_to_string(io::stdin()))
+                } else {
+                    (path.display().to_string(), fs::read_to_string(path))
+                };
+                match read {
+                    Ok(text) => Input {
+                        entry,
+                        source,
+                        text,
+                    },
+                    Err(error) => {
+                        eprintln!("Could not read {}: {}", source, error);
+                        std::process::exit(2);
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// The conventional location of a day's input: `<inputs>/<year>/dayNN.txt`.
+pub fn input_path(inputs: &Path, entry: &SolutionEntry) -> PathBuf {
+    inputs
+        .join(entry.year.to_string())
+        .join(format!("day{:02}.txt", entry.day))
+}
@@ -0,0 +1,171 @@
+use std::time::Duration;
+
+use crate::answers::Verdict;
+use crate::runner::{DayRun, PartRun};
+
+/// The SRAM of the ESP32-S3; parts that need more heap will not run on the device.
+pub const ESP32_S3_SRAM: usize = 512 * 1024;
+
+const COLUMNS: usize = 9;
+const HEADER: [&str; COLUMNS] = [
+    "Year", "Day", "Title", "Part 1", "Check", "Time", "Part 2", "Check", "Time",
+];
+
+/// Prints one row per day with answers, verdicts and timings, followed by the totals.
+pub fn print(runs: &[DayRun]) {
+    let mut rows: Vec<[String; COLUMNS]> = runs
+        .iter()
+        .map(|run| {
+            [
+                run.year.to_string(),
+                format!("{:02}", run.day),
+                run.title.to_string(),
+                answer(&run.parts[0]),
+                check(&run.parts[0].verdict),
+                time(run.parts[0].duration),
+                answer(&run.parts[1]),
+                check(&run.parts[1].verdict),
+                time(run.parts[1].duration),
+            ]
+        })
+        .collect();
+
+    let total_part_1: Duration = runs.iter().map(|run| run.parts[0].duration).sum();
+    let total_part_2: Duration = runs.iter().map(|run| run.parts[1].duration).sum();
+    let failed = runs.iter().filter(|run| !run.is_ok()).count();
+    rows.push([
+        "Total".to_string(),
+        String::new(),
+        format!("{} days, {} failed", runs.len(), failed),
+        String::new(),
+        String::new(),
+        time(total_part_1),
+        String::new(),
+        String::new(),
+        time(total_part_2),
+    ]);
+
+    let (totals, days) = rows.split_last().expect("the totals row is always present");
+    // Day numbers and timings are right-aligned.
+    print_grid(&HEADER, days, Some(totals), &[1, 5, 8]);
+
+    let verdicts: Vec<&Verdict> = runs
+        .iter()
+        .flat_map(|run| run.parts.iter().map(|part| &part.verdict))
+        .collect();
+    let count =
+        |wanted: fn(&Verdict) -> bool| verdicts.iter().filter(|verdict| wanted(verdict)).count();
+    println!(
+        "Answers: {} passed, {} failed, {} unknown",
+        count(|verdict| *verdict == Verdict::Pass),
+        count(|verdict| matches!(verdict, Verdict::Fail { .. })),
+        count(|verdict| *verdict == Verdict::Unknown),
+    );
+    println!("Total time: {}", time(total_part_1 + total_part_2));
+}
+
+/// Prints the heap usage of every profiled part and flags parts that exceed the SRAM.
+pub fn print_heap(runs: &[DayRun]) {
+    let rows: Vec<Vec<String>> = runs
+        .iter()
+        .flat_map(|run| {
+            (1..).zip(&run.parts).filter_map(move |(part, part_run)| {
+                let heap = part_run.heap?;
+                Some(vec![
+                    run.year.to_string(),
+                    format!("{:02}", run.day),
+                    part.to_string(),
+                    heap.peak.to_string(),
+                    heap.allocations.to_string(),
+                    heap.allocated.to_string(),
+                    if heap.peak > ESP32_S3_SRAM {
+                        "EXCEEDS SRAM".to_string()
+                    } else {
+                        "ok".to_string()
+                    },
+                ])
+            })
+        })
+        .collect();
+
+    println!();
+    print_grid::<Vec<String>>(
+        &[
+            "Year",
+            "Day",
+            "Part",
+            "Peak [B]",
+            "Allocations",
+            "Allocated [B]",
+            "ESP32-S3",
+        ],
+        &rows,
+        None,
+        &[1, 3, 4, 5],
+    );
+}
+
+/// Prints `rows` below `header` in aligned columns, with an optional `footer` row set off
+/// by a rule. Columns listed in `right_aligned` are right-aligned, all others left-aligned.
+pub fn print_grid<R: AsRef<[String]>>(
+    header: &[&str],
+    rows: &[R],
+    footer: Option<&R>,
+    right_aligned: &[usize],
+) {
+    // Every column is as wide as its widest cell.
+    let mut widths: Vec<usize> = header.iter().map(|title| title.chars().count()).collect();
+    for row in rows.iter().chain(footer) {
+        for (width, cell) in widths.iter_mut().zip(row.as_ref()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let header: Vec<String> = header.iter().map(|title| title.to_string()).collect();
+    let rule: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+
+    let print_row = |row: &[String]| {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, &width))| {
+                if right_aligned.contains(&column) {
+                    format!("{:>width$}", cell)
+                } else {
+                    format!("{:<width$}", cell)
+                }
+            })
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    };
+
+    print_row(&header);
+    print_row(&rule);
+    for row in rows {
+        print_row(row.as_ref());
+    }
+    if let Some(footer) = footer {
+        print_row(&rule);
+        print_row(footer.as_ref());
+    }
+}
+
+fn answer(part: &PartRun) -> String {
+    match &part.result {
+        Ok(answer) => answer.to_string(),
+        Err(error) => format!("error: {}", error),
+    }
+}
+
+fn check(verdict: &Verdict) -> String {
+    match verdict {
+        Verdict::Pass => "pass".to_string(),
+        Verdict::Fail { expected } => format!("FAIL ({})", expected),
+        Verdict::Unknown => "?".to_string(),
+    }
+}
+
+fn time(duration: Duration) -> String {
+    format!("{:.2?}", duration)
+}
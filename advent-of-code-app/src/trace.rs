@@ -0,0 +1,334 @@
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use advent_of_code_solutions::aoc_solution::PuzzleParams;
+use advent_of_code_solutions::trace::Trace;
+use clap::Args;
+use serde_json::json;
+
+use crate::selection::Input;
+
+/// Options that record the simulation of one part of a single day.
+#[derive(Args)]
+pub struct TraceArgs {
+    /// Records the simulation of a part into FILE as text (`.txt`), JSON (`.json`), an
+    /// animated GIF (`.gif`) or an animated PNG (`.png`, `.apng`). Only puzzles that
+    /// simulate a grid over time record frames.
+    #[arg(long, value_name = "FILE", requires = "day")]
+    pub trace: Option<PathBuf>,
+    /// The part whose simulation is recorded.
+    #[arg(long, value_name = "PART", default_value_t = 1,
+          value_parser = clap::value_parser!(u32).range(1..=2))]
+    pub trace_part: u32,
+    /// The most frames to record; later frames are counted but dropped.
+    #[arg(long, value_name = "N", default_value_t = 1000)]
+    pub trace_frames: usize,
+    /// How long each frame of an animation is shown, in milliseconds.
+    #[arg(long, value_name = "MS", default_value_t = 100)]
+    pub trace_delay: u16,
+}
+
+/// The file formats a trace is written in, chosen by the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Export {
+    Text,
+    Json,
+    Gif,
+    Apng,
+}
+
+impl Export {
+    fn for_path(path: &Path) -> Result<Self, String> {
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        match extension.map(str::to_ascii_lowercase).as_deref() {
+            Some("txt") => Ok(Export::Text),
+            Some("json") => Ok(Export::Json),
+            Some("gif") => Ok(Export::Gif),
+            Some("png" | "apng") => Ok(Export::Apng),
+            _ => Err(format!(
+                "{}: expected a .txt, .json, .gif, .png or .apng file",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// Solves the traced part of `input` with `params` and writes its frames to `path`.
+/// Returns `false` if the part records no frames or the file cannot be written.
+pub fn run(args: &TraceArgs, path: &Path, input: &Input, params: &PuzzleParams) -> bool {
+    let export = match Export::for_path(path) {
+        Ok(export) => export,
+        Err(error) => {
+            eprintln!("{}", error);
+            return false;
+        }
+    };
+
+    let entry = input.entry;
+    let mut aoc = entry.build();
+    let mut trace = Trace::new(args.trace_frames);
+    let result = aoc.configure(params).and_then(|()| match args.trace_part {
+        1 => aoc.trace_part_1(&input.text, &mut trace),
+        _ => aoc.trace_part_2(&input.text, &mut trace),
+    });
+
+    if trace.frames().is_empty() {
+        eprintln!(
+            "Day {:02} {} part {} records no trace",
+            entry.day, entry.year, args.trace_part
+        );
+        return false;
+    }
+
+    let header = json!({
+        "year": entry.year,
+        "day": entry.day,
+        "part": args.trace_part,
+        "answer": result.as_ref().ok().map(|answer| answer.to_string()),
+        "error": result.as_ref().err().map(|error| error.to_string()),
+    });
+    let written = File::create(path)
+        .map_err(|error| error.to_string())
+        .and_then(|file| {
+            let mut out = BufWriter::new(file);
+            match export {
+                Export::Text => out
+                    .write_all(trace.to_text().as_bytes())
+                    .map_err(|error| error.to_string()),
+                Export::Json => out
+                    .write_all(to_json(header, &trace).as_bytes())
+                    .map_err(|error| error.to_string()),
+                Export::Gif => write_gif(&mut out, &trace, args.trace_delay),
+                Export::Apng => write_apng(&mut out, &trace, args.trace_delay),
+            }?;
+            out.flush().map_err(|error| error.to_string())
+        });
+
+    match written {
+        Ok(()) => {
+            eprintln!(
+                "Trace of part {}: {} frames written to {}{}",
+                args.trace_part,
+                trace.frames().len(),
+                path.display(),
+                match trace.skipped() {
+                    0 => String::new(),
+                    skipped => format!(", {} more not recorded", skipped),
+                }
+            );
+            true
+        }
+        Err(error) => {
+            eprintln!("Could not write the trace to {}: {}", path.display(), error);
+            false
+        }
+    }
+}
+
+/// The trace as one JSON document: `header` extended by the frames and the number of
+/// frames that were not recorded.
+fn to_json(mut header: serde_json::Value, trace: &Trace) -> String {
+    let frames: Vec<serde_json::Value> = trace
+        .frames()
+        .iter()
+        .map(|frame| {
+            json!({
+                "step": frame.step,
+                "grid": frame.grid,
+                "events": frame.events,
+            })
+        })
+        .collect();
+    header["skipped"] = json!(trace.skipped());
+    header["frames"] = json!(frames);
+    serde_json::to_string_pretty(&header).unwrap_or_default() + "\n"
+}
+
+/// The frames as pixels: every cell becomes a square of palette indices, so that the
+/// whole image is about 800 pixels wide or high at most.
+struct Raster {
+    width: usize,
+    height: usize,
+    /// RGB triples, one per palette index.
+    palette: Vec<u8>,
+    frames: Vec<Vec<u8>>,
+}
+
+impl Raster {
+    fn new(trace: &Trace) -> Self {
+        let frames = trace.frames();
+        let columns = frames
+            .iter()
+            .flat_map(|frame| frame.grid.iter().map(|row| row.chars().count()))
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let rows = frames
+            .iter()
+            .map(|frame| frame.grid.len())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let scale = (800 / columns.max(rows)).clamp(1, 8);
+
+        // Missing cells of short rows are drawn like an empty tile.
+        let mut tiles = vec![' '];
+        for frame in frames {
+            for ch in frame.grid.iter().flat_map(|row| row.chars()) {
+                if !tiles.contains(&ch) && tiles.len() < 256 {
+                    tiles.push(ch);
+                }
+            }
+        }
+        let palette = tiles.iter().flat_map(|&ch| color(ch)).collect();
+        let index = |ch: char| tiles.iter().position(|&tile| tile == ch).unwrap_or(0) as u8;
+
+        let (width, height) = (columns * scale, rows * scale);
+        let frames = frames
+            .iter()
+            .map(|frame| {
+                let mut pixels = vec![0; width * height];
+                for (y, row) in frame.grid.iter().enumerate() {
+                    for (x, ch) in row.chars().enumerate() {
+                        let tile = index(ch);
+                        for dy in 0..scale {
+                            let start = (y * scale + dy) * width + x * scale;
+                            pixels[start..start + scale].fill(tile);
+                        }
+                    }
+                }
+                pixels
+            })
+            .collect();
+
+        Raster {
+            width,
+            height,
+            palette,
+            frames,
+        }
+    }
+}
+
+/// The colour of a tile in animations. Walls are grey, boxes brown, the robot or guard
+/// red, visited cells and paths blue, counts green; other tiles get a colour of their own.
+fn color(ch: char) -> [u8; 3] {
+    match ch {
+        ' ' | '.' => [16, 16, 32],
+        '#' => [128, 128, 128],
+        '[' | ']' => [184, 124, 60],
+        '@' | '^' | '>' | 'v' | '<' => [230, 50, 50],
+        'X' | 'O' => [70, 130, 220],
+        '1'..='9' => {
+            let count = ch as u8 - b'0';
+            [40, 110 + count * 16, 60]
+        }
+        '+' => [120, 255, 120],
+        _ => {
+            let hash = (ch as u32).wrapping_mul(2_654_435_761);
+            [
+                64 + (hash >> 24) as u8 % 192,
+                64 + (hash >> 16) as u8 % 192,
+                64 + (hash >> 8) as u8 % 192,
+            ]
+        }
+    }
+}
+
+/// Writes the frames as an endlessly looping animated GIF.
+fn write_gif(out: impl Write, trace: &Trace, delay_ms: u16) -> Result<(), String> {
+    let raster = Raster::new(trace);
+    let too_large = |_| "the trace is too large for a GIF".to_string();
+    let (width, height) = (
+        u16::try_from(raster.width).map_err(too_large)?,
+        u16::try_from(raster.height).map_err(too_large)?,
+    );
+
+    let mut encoder = gif::Encoder::new(out, width, height, &raster.palette)
+        .map_err(|error| error.to_string())?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(|error| error.to_string())?;
+    for pixels in &raster.frames {
+        let frame = gif::Frame {
+            width,
+            height,
+            delay: delay_ms / 10, // GIF delays are in hundredths of a second
+            buffer: Cow::Borrowed(pixels),
+            ..gif::Frame::default()
+        };
+        encoder
+            .write_frame(&frame)
+            .map_err(|error| error.to_string())?;
+    }
+    Ok(())
+}
+
+/// Writes the frames as an endlessly looping animated PNG.
+fn write_apng(out: impl Write, trace: &Trace, delay_ms: u16) -> Result<(), String> {
+    let raster = Raster::new(trace);
+    let to_string = |error: png::EncodingError| error.to_string();
+
+    let mut encoder = png::Encoder::new(out, raster.width as u32, raster.height as u32);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(raster.palette.clone());
+    encoder
+        .set_animated(raster.frames.len() as u32, 0)
+        .map_err(to_string)?;
+    encoder.set_frame_delay(delay_ms, 1000).map_err(to_string)?;
+
+    let mut writer = encoder.write_header().map_err(to_string)?;
+    for pixels in &raster.frames {
+        writer.write_image_data(pixels).map_err(to_string)?;
+    }
+    writer.finish().map_err(to_string)
+}
+
+mod test {
+    #[test]
+    /// A small trace is written in every format with its frames and events.
+    fn test_trace_export() {
+        use super::{to_json, write_apng, write_gif, Export, Raster};
+        use advent_of_code_solutions::trace::Trace;
+        use std::path::Path;
+
+        let mut trace = Trace::new(10);
+        trace.frame(0, || "#.\n.@");
+        trace.event("moved");
+        trace.frame(1, || "#@\n..");
+
+        assert_eq!(Export::for_path(Path::new("walk.GIF")), Ok(Export::Gif));
+        assert_eq!(Export::for_path(Path::new("walk.apng")), Ok(Export::Apng));
+        assert!(Export::for_path(Path::new("walk")).is_err());
+
+        let raster = Raster::new(&trace);
+        assert_eq!((raster.width, raster.height), (16, 16));
+        assert_eq!(raster.palette.len(), 4 * 3);
+        assert_eq!(raster.frames.len(), 2);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&to_json(serde_json::json!({"day": 6}), &trace)).unwrap();
+        assert_eq!(json["day"], 6);
+        assert_eq!(json["frames"][1]["grid"][0], "#@");
+        assert_eq!(json["frames"][1]["events"][0], "moved");
+
+        let mut gif = Vec::new();
+        write_gif(&mut gif, &trace, 100).unwrap();
+        let mut decoder = gif::DecodeOptions::new().read_info(&gif[..]).unwrap();
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((frame.width, frame.height, frame.delay), (16, 16, 10));
+            frames += 1;
+        }
+        assert_eq!(frames, 2);
+
+        let mut apng = Vec::new();
+        write_apng(&mut apng, &trace, 100).unwrap();
+        let reader = png::Decoder::new(&apng[..]).read_info().unwrap();
+        let animation = reader.info().animation_control.unwrap();
+        assert_eq!(animation.num_frames, 2);
+    }
+}
@@ -1,4 +1,34 @@
 document.addEventListener("DOMContentLoaded", () => {
+    // Collapsible list of the intermediate facts behind an answer
+    const explanationElement = (facts, skipped) => {
+        const details = document.createElement("details");
+        const summary = document.createElement("summary");
+        summary.textContent = `Explanation (${facts.length} facts)`;
+        details.appendChild(summary);
+
+        const list = document.createElement("ul");
+        for (const fact of facts) {
+            const item = document.createElement("li");
+            if (fact.value.includes("\n")) {
+                // Pictures and other multi-line values keep their lines and columns
+                item.textContent = `${fact.name}:`;
+                const pre = document.createElement("pre");
+                pre.textContent = fact.value.replace(/^\n/, "");
+                item.appendChild(pre);
+            } else {
+                item.textContent = `${fact.name}: ${fact.value}`; // Facts may contain input text
+            }
+            list.appendChild(item);
+        }
+        if (skipped > 0) {
+            const item = document.createElement("li");
+            item.textContent = `(${skipped} more facts not recorded)`;
+            list.appendChild(item);
+        }
+        details.appendChild(list);
+        return details;
+    };
+
     // Countdown management for multiple forms
     const manageCountdown = (formId, countdownId, resultId) => {
         const form = document.getElementById(formId);
@@ -40,6 +70,12 @@ document.addEventListener("DOMContentLoaded", () => {
                         resultElement.innerHTML = `&#9989; Success, Runtime ${data.runtime}, Minimum Free Heap Size [${data.free_heap_size_before}, ${data.free_heap_size_after}]`;
                     } else if (data.complete === -1) {
                         resultElement.innerHTML = `&#10060; Fail, Runtime ${data.runtime}, Minimum Free Heap Size [${data.free_heap_size_before}, ${data.free_heap_size_after}]`;
+                    } else if (data.complete === -2) {
+                        resultElement.innerHTML = `&#9888;&#65039; Error, Runtime ${data.runtime}, Minimum Free Heap Size [${data.free_heap_size_before}, ${data.free_heap_size_after}]: `;
+                        resultElement.appendChild(document.createTextNode(data.error)); // The message may contain input text
+                    }
+                    if (data.explanation && data.explanation.length > 0) {
+                        resultElement.appendChild(explanationElement(data.explanation, data.explanation_skipped));
                     }
                     countdownElement.textContent = "";
                 } else {
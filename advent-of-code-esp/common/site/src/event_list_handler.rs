@@ -1,7 +1,8 @@
 use std::collections::BTreeMap;
 use std::time::Instant;
 
-use advent_of_code_solutions::aoc_solution::AoCSolution;
+use advent_of_code_solutions::aoc_solution::{AoCSolution, PuzzleParams};
+use advent_of_code_solutions::explain::Explain;
 use anyhow::Result;
 use askama::Template;
 use embedded_svc::{http::Method, io::Write};
@@ -26,9 +27,18 @@ pub struct DayTemplate<'a> {
     day: u32,
     image_name: String,
     sol: &'a Box<dyn AoCSolution>,
-    complete: i32
+    complete: i32,
+    countdown_1: u64,
+    countdown_2: u64,
+    /// The settings of the real puzzle as `name=value` pairs; empty if it has none.
+    params: String,
+    /// What each setting controls, shown as a tooltip.
+    params_help: String,
 }
 
+/// Countdown for parts that have neither a profile nor a hand-timed baseline.
+const DEFAULT_COUNTDOWN_SECONDS: u64 = 10;
+
 pub fn load_and_serve_event(
     server: &mut EspHttpServer,
     year: u32,
@@ -61,7 +71,25 @@ pub fn load_and_serve_event(
             day: *day,
             image_name: format!("aoc-{}-{}.avif", year, day),
             sol: &sol,
-            complete: 0
+            complete: 0,
+            countdown_1: sol
+                .part_1_device_seconds()
+                .unwrap_or(DEFAULT_COUNTDOWN_SECONDS),
+            countdown_2: sol
+                .part_2_device_seconds()
+                .unwrap_or(DEFAULT_COUNTDOWN_SECONDS),
+            params: sol
+                .params()
+                .iter()
+                .map(|param| format!("{}={}", param.name, param.default))
+                .collect::<Vec<_>>()
+                .join(" "),
+            params_help: sol
+                .params()
+                .iter()
+                .map(|param| format!("{}: {}", param.name, param.description))
+                .collect::<Vec<_>>()
+                .join("; "),
         };
         let event_string = event.render().unwrap().clone();
         server.fn_handler(
@@ -87,12 +115,14 @@ pub fn load_and_serve_event(
                 let level;
                 let puzzle_upload;
                 let puzzle_answer;
+                let puzzle_params;
 
                 match parse_http_request(&mut request) {
-                    Some((lev, puz_up, puz_ans)) => {
+                    Some((lev, puz_up, puz_ans, puz_par)) => {
                         level = lev;
                         puzzle_upload = puz_up;
                         puzzle_answer = puz_ans;
+                        puzzle_params = puz_par;
                     }
                     None => {
                         request
@@ -102,7 +132,6 @@ pub fn load_and_serve_event(
                     }
                 }
 
-                let complete: i32;
                 let minimum_free_heap_size_before: u32;
                 let minimum_free_heap_size_after: u32;
                 let start = Instant::now();
@@ -110,44 +139,32 @@ pub fn load_and_serve_event(
                     minimum_free_heap_size_before = esp_get_minimum_free_heap_size();
                 }
 
-                if level == "1" {
-                    match puzzle_answer.parse::<String>() {
-                        Ok(int_value) => {
-                            if int_value == sol_clone.part_1_final(&puzzle_upload) {
-                                complete = 1
-                            } else {
-                                complete = -1
-                            }
-                        }
-                        Err(_) => {
-                            request
-                                .into_status_response(413)?
-                                .write_all("Wrong request.".as_bytes())?;
-                            return Ok(());
-                        }
-                    }
-                } else if level == "2" {
-                    match puzzle_answer.parse::<String>() {
-                        Ok(int_value) => {
-                            if int_value == sol_clone.part_2_final(&puzzle_upload) {
-                                complete = 1
-                            } else {
-                                complete = -1
-                            }
-                        }
-                        Err(_) => {
-                            request
-                                .into_status_response(413)?
-                                .write_all("Wrong request.".as_bytes())?;
-                            return Ok(());
-                        }
+                // Overridden settings only apply to this request
+                let mut solution = sol_clone.factory();
+                let configured = puzzle_params
+                    .parse::<PuzzleParams>()
+                    .and_then(|params| solution.configure(&params));
+
+                // A few facts are enough to follow the solution and fit into the heap
+                let mut explain = Explain::new(32);
+                let result = match (configured, level.as_str()) {
+                    (Err(error), "1" | "2") => Err(error),
+                    (Ok(()), "1") => solution.explain_part_1(&puzzle_upload, &mut explain),
+                    (Ok(()), "2") => solution.explain_part_2(&puzzle_upload, &mut explain),
+                    _ => {
+                        request
+                            .into_status_response(413)?
+                            .write_all("Wrong request.".as_bytes())?;
+                        return Ok(());
                     }
-                } else {
-                    request
-                        .into_status_response(413)?
-                        .write_all("Wrong request.".as_bytes())?;
-                    return Ok(());
-                }
+                };
+
+                // 1: correct answer, -1: wrong answer, -2: the input could not be solved
+                let (complete, error) = match result {
+                    Ok(answer) if answer == puzzle_answer.trim() => (1, None),
+                    Ok(_) => (-1, None),
+                    Err(error) => (-2, Some(error.to_string())),
+                };
 
                 let duration = start.elapsed();
                 unsafe {
@@ -160,6 +177,13 @@ pub fn load_and_serve_event(
                     "runtime": format!("{:?}", duration),
                     "free_heap_size_before": minimum_free_heap_size_before,
                     "free_heap_size_after": minimum_free_heap_size_after,
+                    "error": error,
+                    "explanation": explain
+                        .facts()
+                        .iter()
+                        .map(|fact| serde_json::json!({"name": fact.name, "value": fact.value}))
+                        .collect::<Vec<_>>(),
+                    "explanation_skipped": explain.skipped(),
                 });
 
                 // JSON serialisieren
@@ -19,7 +19,10 @@ fn extract_boundary(content_type: &str) -> Option<String> {
     None
 }
 
-fn parse_multipart_form_data(input: &Vec<u8>, boundary: &str) -> Option<(String, String, String)> {
+fn parse_multipart_form_data(
+    input: &Vec<u8>,
+    boundary: &str,
+) -> Option<(String, String, String, String)> {
     let boundary = format!("--{}", boundary);
     let input_str = String::from_utf8_lossy(input);
 
@@ -29,6 +32,7 @@ fn parse_multipart_form_data(input: &Vec<u8>, boundary: &str) -> Option<(String,
     let mut level = String::new();
     let mut puzzle_upload = String::new();
     let mut puzzle_answer = String::new();
+    let mut puzzle_params = String::new();
 
     for part in parts {
         if part.contains("Content-Disposition: form-data; name=\"level\"") {
@@ -64,19 +68,29 @@ fn parse_multipart_form_data(input: &Vec<u8>, boundary: &str) -> Option<(String,
                 .unwrap_or_default()
                 .trim()
                 .to_string();
+        } else if part.contains("Content-Disposition: form-data; name=\"puzzle-params-1\"")
+            || part.contains("Content-Disposition: form-data; name=\"puzzle-params-2\"")
+        {
+            // Optional, empty for the settings of the real puzzle
+            puzzle_params = part
+                .split("\r\n\r\n")
+                .nth(1)
+                .unwrap_or_default()
+                .trim()
+                .to_string();
         }
     }
 
     if level.is_empty() || puzzle_upload.is_empty() || puzzle_answer.is_empty() {
         None
     } else {
-        Some((level, puzzle_upload, puzzle_answer))
+        Some((level, puzzle_upload, puzzle_answer, puzzle_params))
     }
 }
 
 pub fn parse_http_request(
     request: &mut Request<&mut EspHttpConnection>,
-) -> Option<(String, String, String)> {
+) -> Option<(String, String, String, String)> {
     let len = request.content_len().unwrap() as usize;
     if len > MAX_LEN {
         return None;
@@ -99,11 +113,13 @@ pub fn parse_http_request(
     let mut _level = String::new();
     let mut _puzzle_upload = String::new();
     let mut _puzzle_answer = String::new();
+    let mut _puzzle_params = String::new();
     match parse_multipart_form_data(&buf, &boundary) {
-        Some((lev, puz_up, puz_ans)) => {
+        Some((lev, puz_up, puz_ans, puz_par)) => {
             _level = lev;
             _puzzle_upload = puz_up;
             _puzzle_answer = puz_ans;
+            _puzzle_params = puz_par;
         }
         None => {
             return None;
@@ -113,6 +129,6 @@ pub fn parse_http_request(
     if _level.is_empty() || _puzzle_upload.is_empty() || _puzzle_answer.is_empty() {
         None
     } else {
-        Some((_level, _puzzle_upload, _puzzle_answer))
+        Some((_level, _puzzle_upload, _puzzle_answer, _puzzle_params))
     }
 }
@@ -1,17 +1,12 @@
 use std::{thread::sleep, time::Duration};
-use std::collections::BTreeMap;
 
-use advent_of_code_solutions::advent_of_code_2022;
-use advent_of_code_solutions::advent_of_code_2023;
-use advent_of_code_solutions::advent_of_code_2024;
-use advent_of_code_solutions::aoc_solution::AoCSolution;
+use advent_of_code_solutions::registry::solutions_by_year;
 use anyhow::Result;
 use esp_idf_svc::{eventloop::EspSystemEventLoop, hal::prelude::*, http::server::EspHttpServer};
 use site::site;
 use wifi::wifi;
 
 const STACK_SIZE: usize = 20480;
-const NUMBER_OF_YEARS: usize = 3;
 const NUMBER_OF_DAYS: usize = 25;
 
 #[toml_cfg::toml_config]
@@ -41,26 +36,15 @@ fn main() -> Result<()> {
         sysloop,
     )?;
 
+    let aoc = solutions_by_year();
+
     let server_configuration = esp_idf_svc::http::server::Configuration {
         stack_size: STACK_SIZE,
-        max_uri_handlers: ((2 * NUMBER_OF_DAYS * NUMBER_OF_YEARS ) + NUMBER_OF_DAYS + 11),
+        max_uri_handlers: ((2 * NUMBER_OF_DAYS * aoc.len()) + NUMBER_OF_DAYS + 11),
         ..Default::default()
     };
     let mut server = EspHttpServer::new(&server_configuration)?;
 
-    let mut aoc: BTreeMap<u32, BTreeMap<u32, Box<dyn AoCSolution>>> = BTreeMap::new();
-    let mut aoc_2022: BTreeMap<u32, Box<dyn AoCSolution>> = BTreeMap::new();
-    let mut aoc_2023: BTreeMap<u32, Box<dyn AoCSolution>> = BTreeMap::new();
-    let mut aoc_2024: BTreeMap<u32, Box<dyn AoCSolution>> = BTreeMap::new();
-
-    let _ = advent_of_code_2022(&mut aoc_2022);
-    let _ = advent_of_code_2023(&mut aoc_2023);
-    let _ = advent_of_code_2024(&mut aoc_2024);
-
-    aoc.insert(2022, aoc_2022);
-    aoc.insert(2023, aoc_2023);
-    aoc.insert(2024, aoc_2024);
-
     // Ad here your sites
     site(&mut server, &aoc);
 
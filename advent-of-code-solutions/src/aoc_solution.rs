@@ -1,3 +1,248 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::device_baseline;
+use crate::explain::Explain;
+use crate::runtime_profiles;
+use crate::trace::Trace;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    /// A numeric answer that may be negative.
+    Signed(i64),
+    /// A numeric answer that is never negative.
+    Unsigned(u64),
+    /// A textual answer, e.g. a comma-separated list or a password.
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(value) => write!(f, "{}", value),
+            Answer::Unsigned(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        match self {
+            Answer::Text(value) => value == other,
+            _ => self.to_string().as_str() == *other,
+        }
+    }
+}
+
+macro_rules! impl_answer_from {
+    ($variant:ident, $target:ty, $($source:ty),+) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(value: $source) -> Self {
+                    Answer::$variant(value as $target)
+                }
+            }
+        )+
+    };
+}
+
+impl_answer_from!(Signed, i64, i8, i16, i32, i64, isize);
+impl_answer_from!(Unsigned, u64, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/// Describes why a puzzle part could not be solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The input is malformed. `line` and `column` are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input is well-formed, but there is no answer for it.
+    Unsolvable(String),
+    /// The input is a puzzle variant the solver does not handle.
+    Unsupported(String),
+    /// A puzzle parameter is unknown or has a value the puzzle cannot use.
+    InvalidParam(String),
+}
+
+impl SolveError {
+    /// Creates a parse error at the given 1-based position.
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Self {
+        SolveError::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Creates a parse error pointing at `token`, which must be a slice of `text`.
+    /// `line` is the 1-based number of the line `text` was taken from.
+    pub fn parse_at(line: usize, text: &str, token: &str, message: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize).saturating_sub(text.as_ptr() as usize);
+        let column = if offset <= text.len() { offset + 1 } else { 1 };
+        Self::parse(line, column, message)
+    }
+
+    /// Creates an error for input that has no answer.
+    pub fn unsolvable(message: impl Into<String>) -> Self {
+        SolveError::Unsolvable(message.into())
+    }
+
+    /// Creates an error for a puzzle variant the solver does not handle.
+    pub fn unsupported(message: impl Into<String>) -> Self {
+        SolveError::Unsupported(message.into())
+    }
+
+    /// Creates an error for a puzzle parameter that is unknown or has a bad value.
+    pub fn invalid_param(message: impl Into<String>) -> Self {
+        SolveError::InvalidParam(message.into())
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Parse {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "parse error at line {}, column {}: {}",
+                line, column, message
+            ),
+            SolveError::Unsolvable(message) => write!(f, "unsolvable input: {}", message),
+            SolveError::Unsupported(message) => write!(f, "unsupported input: {}", message),
+            SolveError::InvalidParam(message) => write!(f, "invalid parameter: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// A setting of a puzzle besides its input, like the size of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParamInfo {
+    /// The name used in `name=value` overrides.
+    pub name: &'static str,
+    /// The value for the real puzzle.
+    pub default: &'static str,
+    /// What the setting controls, shown in help texts and forms.
+    pub description: &'static str,
+    /// The accepted values of a setting that is not a number; empty for numbers.
+    pub choices: &'static [&'static str],
+}
+
+impl ParamInfo {
+    /// Parses `value` as the type of the setting.
+    pub fn parse<T: FromStr>(&self, value: &str) -> Result<T, SolveError> {
+        value.trim().parse().map_err(|_| {
+            let mut message = format!(
+                "'{}' is not a valid value for '{}' ({}",
+                value, self.name, self.description
+            );
+            if !self.choices.is_empty() {
+                message += &format!(": {}", self.choices.join(", "));
+            }
+            SolveError::invalid_param(message + ")")
+        })
+    }
+}
+
+/// Overrides for the settings of a puzzle, as `name=value` pairs. Settings that are not
+/// overridden keep the value of the real puzzle.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PuzzleParams {
+    values: Vec<(String, String)>,
+}
+
+impl PuzzleParams {
+    pub fn new() -> Self {
+        PuzzleParams::default()
+    }
+
+    /// Overrides the setting `name`; a later override of the same name wins.
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.values.push((name.into(), value.into()));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The overrides in the order they were given.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+impl<N: Into<String>, V: Into<String>> FromIterator<(N, V)> for PuzzleParams {
+    fn from_iter<I: IntoIterator<Item = (N, V)>>(iter: I) -> Self {
+        let mut params = PuzzleParams::new();
+        for (name, value) in iter {
+            params.set(name, value);
+        }
+        params
+    }
+}
+
+/// Parses overrides like `size=6, bytes=12`, separated by commas or whitespace.
+impl FromStr for PuzzleParams {
+    type Err = SolveError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut params = PuzzleParams::new();
+        for pair in text
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|pair| !pair.is_empty())
+        {
+            let (name, value) = pair.split_once('=').ok_or_else(|| {
+                SolveError::invalid_param(format!("expected name=value, got '{}'", pair))
+            })?;
+            params.set(name, value);
+        }
+        Ok(params)
+    }
+}
+
+/// How long a part takes and how much heap it needs, measured on a reference input
+/// by `advent-of-code-app profile`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeProfile {
+    /// Median time on the machine that ran the profiler.
+    pub host: Duration,
+    /// Estimated time on the ESP32: the host time multiplied by
+    /// [`runtime_profiles::DEVICE_FACTOR`].
+    pub device: Duration,
+    /// Peak heap usage in bytes while solving.
+    pub peak_heap: usize,
+}
+
+impl RuntimeProfile {
+    /// The expected device time in whole seconds, rounded up and at least one second.
+    pub fn device_seconds(&self) -> u64 {
+        self.device.as_secs_f64().ceil().max(1.0) as u64
+    }
+}
+
 pub trait AoCSolution: Send + Sync {
     /// Returns the day of the Advent of Code challenge.
     fn day(&self) -> u32;
@@ -11,19 +256,208 @@ pub trait AoCSolution: Send + Sync {
     /// Returns the source code or reference to the solution.
     fn code(&self) -> String;
 
-    /// Solves part 1 of the problem using the given input.
-    fn part_1_final(&self, input: &str) -> String;
+    /// The settings the puzzle takes besides its input.
+    fn params(&self) -> &'static [ParamInfo];
+
+    /// Applies `params` on top of the settings of the real puzzle. Every later solve uses
+    /// them until the next call.
+    fn configure(&mut self, params: &PuzzleParams) -> Result<(), SolveError>;
+
+    /// Solves part 1 of the problem, reporting malformed or unsolvable input as an error.
+    fn solve_part_1(&self, input: &str) -> Result<Answer, SolveError>;
+
+    /// Solves part 1 of the problem using the given input and renders the answer as text.
+    fn part_1_final(&self, input: &str) -> Result<String, SolveError> {
+        self.solve_part_1(input).map(|answer| answer.to_string())
+    }
+
+    /// Returns the measured runtime profile of part 1, if it has been profiled.
+    fn part_1_profile(&self) -> Option<RuntimeProfile>;
+
+    /// Returns the expected ESP32 time of part 1 in whole seconds: the estimate of its
+    /// profile, or the hand-timed baseline if it has not been profiled.
+    fn part_1_device_seconds(&self) -> Option<u64>;
 
-    /// Optionally returns runtime metrics for part 1.
-    fn part_1_runtime(&self) -> usize;
+    /// Solves part 1 while recording the simulation in `trace`, if the puzzle has one.
+    fn trace_part_1(&self, input: &str, trace: &mut Trace) -> Result<Answer, SolveError>;
 
-    /// Solves part 2 of the problem using the given input.
-    fn part_2_final(&self, input: &str) -> String;
+    /// Solves part 1 while recording intermediate facts in `explain`, if the puzzle has any.
+    fn explain_part_1(&self, input: &str, explain: &mut Explain) -> Result<Answer, SolveError>;
 
-    /// Optionally returns runtime metrics for part 2.
-    fn part_2_runtime(&self) -> usize;
+    /// Solves part 2 of the problem, reporting malformed or unsolvable input as an error.
+    fn solve_part_2(&self, input: &str) -> Result<Answer, SolveError>;
 
-    /// Factory method to create a boxed instance of the solution.
-    /// Consider replacing this with a free function or `impl`.
+    /// Solves part 2 of the problem using the given input and renders the answer as text.
+    fn part_2_final(&self, input: &str) -> Result<String, SolveError> {
+        self.solve_part_2(input).map(|answer| answer.to_string())
+    }
+
+    /// Returns the measured runtime profile of part 2, if it has been profiled.
+    fn part_2_profile(&self) -> Option<RuntimeProfile>;
+
+    /// Returns the expected ESP32 time of part 2 in whole seconds: the estimate of its
+    /// profile, or the hand-timed baseline if it has not been profiled.
+    fn part_2_device_seconds(&self) -> Option<u64>;
+
+    /// Solves part 2 while recording the simulation in `trace`, if the puzzle has one.
+    fn trace_part_2(&self, input: &str, trace: &mut Trace) -> Result<Answer, SolveError>;
+
+    /// Solves part 2 while recording intermediate facts in `explain`, if the puzzle has any.
+    fn explain_part_2(&self, input: &str, explain: &mut Explain) -> Result<Answer, SolveError>;
+
+    /// Factory method to create a boxed copy of the solution with the same settings.
     fn factory(&self) -> Box<dyn AoCSolution>;
 }
+
+/// A single day's puzzle. Implementing this is all a day needs; [`Solution`] turns it
+/// into an [`AoCSolution`].
+pub trait Puzzle: Default + Send + Sync + 'static {
+    /// The year of the Advent of Code challenge.
+    const YEAR: u32;
+
+    /// The day of the Advent of Code challenge.
+    const DAY: u32;
+
+    /// The puzzle title as shown on the calendar.
+    const TITLE: &'static str;
+
+    /// The settings the puzzle takes besides its input. `Default` must give them the
+    /// values of the real puzzle.
+    const PARAMS: &'static [ParamInfo] = &[];
+
+    /// Overrides the setting `name`, one of [`Puzzle::PARAMS`].
+    fn set_param(&mut self, name: &str, _value: &str) -> Result<(), SolveError> {
+        Err(unknown_param::<Self>(name))
+    }
+
+    /// Solves part 1 of the puzzle.
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError>;
+
+    /// Solves part 2 of the puzzle.
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError>;
+
+    /// Solves part 1 like [`Puzzle::part_1`], recording the simulation in `trace`. Puzzles
+    /// that simulate a grid over time override this and solve part 1 with a disabled trace.
+    fn trace_part_1(&self, input: &str, _trace: &mut Trace) -> Result<Answer, SolveError> {
+        self.part_1(input)
+    }
+
+    /// Solves part 2 like [`Puzzle::part_2`], recording the simulation in `trace`.
+    fn trace_part_2(&self, input: &str, _trace: &mut Trace) -> Result<Answer, SolveError> {
+        self.part_2(input)
+    }
+
+    /// Solves part 1 like [`Puzzle::part_1`], recording the intermediate facts that explain
+    /// the answer in `explain`. Puzzles with such facts override this and solve part 1 with
+    /// a disabled sink.
+    fn explain_part_1(&self, input: &str, _explain: &mut Explain) -> Result<Answer, SolveError> {
+        self.part_1(input)
+    }
+
+    /// Solves part 2 like [`Puzzle::part_2`], recording intermediate facts in `explain`.
+    fn explain_part_2(&self, input: &str, _explain: &mut Explain) -> Result<Answer, SolveError> {
+        self.part_2(input)
+    }
+}
+
+/// The error for a setting that is not one of `P::PARAMS`.
+pub fn unknown_param<P: Puzzle>(name: &str) -> SolveError {
+    let known: Vec<&str> = P::PARAMS.iter().map(|param| param.name).collect();
+    SolveError::invalid_param(if known.is_empty() {
+        format!("unknown parameter '{}', the puzzle has none", name)
+    } else {
+        format!(
+            "unknown parameter '{}', expected one of {}",
+            name,
+            known.join(", ")
+        )
+    })
+}
+
+/// Implements [`AoCSolution`] once for every [`Puzzle`].
+pub struct Solution<P: Puzzle> {
+    puzzle: P,
+    /// The overrides `puzzle` was configured with, to configure copies the same way.
+    params: PuzzleParams,
+    code: &'static str,
+}
+
+impl<P: Puzzle> Solution<P> {
+    /// Creates the solution; `code` is the source shown next to the puzzle.
+    pub fn new(code: &'static str) -> Self {
+        Solution {
+            puzzle: P::default(),
+            params: PuzzleParams::new(),
+            code,
+        }
+    }
+}
+
+impl<P: Puzzle> AoCSolution for Solution<P> {
+    fn day(&self) -> u32 {
+        P::DAY
+    }
+    fn year(&self) -> u32 {
+        P::YEAR
+    }
+    fn desc(&self) -> String {
+        P::TITLE.to_string()
+    }
+    fn code(&self) -> String {
+        self.code.to_string()
+    }
+
+    fn factory(&self) -> Box<dyn AoCSolution> {
+        let mut solution = Self::new(self.code);
+        solution
+            .configure(&self.params)
+            .expect("the settings were accepted before");
+        Box::new(solution)
+    }
+
+    fn params(&self) -> &'static [ParamInfo] {
+        P::PARAMS
+    }
+
+    fn configure(&mut self, params: &PuzzleParams) -> Result<(), SolveError> {
+        let mut puzzle = P::default();
+        for (name, value) in params.iter() {
+            puzzle.set_param(name, value)?;
+        }
+        self.puzzle = puzzle;
+        self.params = params.clone();
+        Ok(())
+    }
+
+    fn solve_part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        self.puzzle.part_1(input)
+    }
+    fn part_1_profile(&self) -> Option<RuntimeProfile> {
+        runtime_profiles::lookup(P::YEAR, P::DAY, 1)
+    }
+    fn part_1_device_seconds(&self) -> Option<u64> {
+        device_baseline::device_seconds(P::YEAR, P::DAY, 1)
+    }
+    fn trace_part_1(&self, input: &str, trace: &mut Trace) -> Result<Answer, SolveError> {
+        self.puzzle.trace_part_1(input, trace)
+    }
+    fn explain_part_1(&self, input: &str, explain: &mut Explain) -> Result<Answer, SolveError> {
+        self.puzzle.explain_part_1(input, explain)
+    }
+
+    fn solve_part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        self.puzzle.part_2(input)
+    }
+    fn part_2_profile(&self) -> Option<RuntimeProfile> {
+        runtime_profiles::lookup(P::YEAR, P::DAY, 2)
+    }
+    fn part_2_device_seconds(&self) -> Option<u64> {
+        device_baseline::device_seconds(P::YEAR, P::DAY, 2)
+    }
+    fn trace_part_2(&self, input: &str, trace: &mut Trace) -> Result<Answer, SolveError> {
+        self.puzzle.trace_part_2(input, trace)
+    }
+    fn explain_part_2(&self, input: &str, explain: &mut Explain) -> Result<Answer, SolveError> {
+        self.puzzle.explain_part_2(input, explain)
+    }
+}
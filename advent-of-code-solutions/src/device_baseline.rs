@@ -0,0 +1,122 @@
+//! Countdowns of the web page, timed by hand on the ESP32 with earlier versions of the
+//! solutions. They are only a fallback: a part that has a measured runtime profile uses
+//! the estimate of its profile instead, and `advent-of-code-app profile` reports how
+//! many parts still rely on these.
+
+use crate::runtime_profiles;
+
+/// Seconds the ESP32 needs for each part, keyed by year, day and part.
+pub static DEVICE_SECONDS: &[((u32, u32, u32), u64)] = &[
+    ((2022, 1, 1), 10),
+    ((2022, 1, 2), 10),
+    ((2022, 2, 1), 10),
+    ((2022, 2, 2), 10),
+    ((2022, 3, 1), 10),
+    ((2022, 3, 2), 10),
+    ((2022, 4, 1), 10),
+    ((2022, 4, 2), 10),
+    ((2022, 5, 1), 10),
+    ((2022, 5, 2), 10),
+    ((2022, 6, 1), 10),
+    ((2022, 6, 2), 10),
+    ((2023, 1, 1), 10),
+    ((2023, 1, 2), 10),
+    ((2023, 2, 1), 10),
+    ((2023, 2, 2), 10),
+    ((2023, 3, 1), 10),
+    ((2023, 3, 2), 10),
+    ((2023, 4, 1), 10),
+    ((2023, 4, 2), 10),
+    ((2023, 5, 1), 10),
+    ((2023, 5, 2), 10),
+    ((2024, 1, 1), 1),
+    ((2024, 1, 2), 1),
+    ((2024, 2, 1), 1),
+    ((2024, 2, 2), 1),
+    ((2024, 3, 1), 1),
+    ((2024, 3, 2), 1),
+    ((2024, 4, 1), 1),
+    ((2024, 4, 2), 1),
+    ((2024, 5, 1), 3),
+    ((2024, 5, 2), 3),
+    ((2024, 6, 1), 1),
+    ((2024, 6, 2), 70),
+    ((2024, 7, 1), 1),
+    ((2024, 7, 2), 30),
+    ((2024, 8, 1), 1),
+    ((2024, 8, 2), 1),
+    ((2024, 9, 1), 1),
+    ((2024, 9, 2), 6),
+    ((2024, 10, 1), 1),
+    ((2024, 10, 2), 1),
+    ((2024, 11, 1), 1),
+    ((2024, 11, 2), 1),
+    ((2024, 12, 1), 2),
+    ((2024, 12, 2), 2),
+    ((2024, 13, 1), 1),
+    ((2024, 13, 2), 1),
+    ((2024, 14, 1), 1),
+    ((2024, 14, 2), 15),
+    ((2024, 15, 1), 1),
+    ((2024, 15, 2), 2),
+    ((2024, 16, 1), 3),
+    ((2024, 16, 2), 3),
+    ((2024, 17, 1), 1),
+    ((2024, 17, 2), 7),
+    ((2024, 18, 1), 1),
+    ((2024, 18, 2), 1),
+    ((2024, 19, 1), 2),
+    ((2024, 19, 2), 2),
+    ((2024, 20, 1), 11),
+    ((2024, 20, 2), 11),
+    ((2024, 21, 1), 1),
+    ((2024, 21, 2), 1),
+    ((2024, 22, 1), 1),
+    ((2024, 22, 2), 14),
+    ((2024, 23, 1), 8),
+    ((2024, 23, 2), 4),
+    ((2024, 24, 1), 1),
+    ((2024, 24, 2), 1),
+    ((2024, 25, 1), 1),
+    ((2024, 25, 2), 1),
+];
+
+/// Returns the hand-timed seconds of one part, if it has been timed.
+pub fn lookup(year: u32, day: u32, part: u32) -> Option<u64> {
+    DEVICE_SECONDS
+        .iter()
+        .find(|(key, _)| *key == (year, day, part))
+        .map(|(_, seconds)| *seconds)
+}
+
+/// The expected ESP32 time of a part in whole seconds: the estimate of its runtime
+/// profile if it has one, otherwise the hand-timed baseline.
+pub fn device_seconds(year: u32, day: u32, part: u32) -> Option<u64> {
+    runtime_profiles::lookup(year, day, part)
+        .map(|profile| profile.device_seconds())
+        .or_else(|| lookup(year, day, part))
+}
+
+mod test {
+    #[test]
+    /// Every registered part has a baseline, and the slow parts keep their long countdown.
+    fn test_device_baseline() {
+        use crate::device_baseline::{device_seconds, lookup};
+        use crate::registry;
+
+        for entry in registry::all_solutions() {
+            for part in 1..=2 {
+                assert!(
+                    lookup(entry.year, entry.day, part).is_some(),
+                    "day {} {} part {} has no baseline",
+                    entry.day,
+                    entry.year,
+                    part
+                );
+            }
+        }
+        assert_eq!(device_seconds(2024, 6, 2), Some(70));
+        assert_eq!(device_seconds(2024, 7, 1), Some(1));
+        assert_eq!(device_seconds(2021, 1, 1), None);
+    }
+}
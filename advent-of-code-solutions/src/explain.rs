@@ -0,0 +1,90 @@
+use std::fmt::{self, Write};
+
+use crate::utils::bounded::Bounded;
+
+/// A named intermediate value of a solution, like the press counts of one claw machine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fact {
+    pub name: String,
+    pub value: String,
+}
+
+/// Collects the intermediate facts that explain how an answer came about, for teaching.
+/// Facts past the limit are only counted, like the frames of a [`Trace`]: a disabled sink
+/// formats nothing, so solvers record facts unconditionally.
+///
+/// [`Trace`]: crate::trace::Trace
+#[derive(Debug, Clone, Default)]
+pub struct Explain {
+    facts: Bounded<Fact>,
+}
+
+impl Explain {
+    /// A sink for the first `limit` facts.
+    pub fn new(limit: usize) -> Self {
+        Explain {
+            facts: Bounded::new(limit),
+        }
+    }
+
+    /// A sink that records nothing.
+    pub fn disabled() -> Self {
+        Explain::default()
+    }
+
+    /// Whether the next fact will be kept.
+    pub fn is_recording(&self) -> bool {
+        self.facts.has_room()
+    }
+
+    /// Records that `name` is `value`; both are only formatted while recording.
+    pub fn fact(&mut self, name: impl fmt::Display, value: impl fmt::Display) {
+        self.facts.push_with(|| Fact {
+            name: name.to_string(),
+            value: value.to_string(),
+        });
+    }
+
+    pub fn facts(&self) -> &[Fact] {
+        self.facts.items()
+    }
+
+    /// How many facts were past the limit.
+    pub fn skipped(&self) -> usize {
+        self.facts.skipped()
+    }
+
+    /// Renders one `name: value` line per fact.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for fact in self.facts() {
+            let _ = writeln!(text, "{}: {}", fact.name, fact.value);
+        }
+        self.facts.write_skipped(&mut text, "facts");
+        text
+    }
+}
+
+mod test {
+    #[test]
+    /// Facts are kept in order up to the limit; a disabled sink keeps none.
+    fn test_explain() {
+        use crate::explain::Explain;
+
+        let mut explain = Explain::new(2);
+        explain.fact("machine 1", format_args!("A {} times, B {} times", 80, 40));
+        explain.fact("machine 2", "no prize");
+        explain.fact("machine 3", "dropped");
+        assert_eq!(explain.facts()[1].value, "no prize");
+        assert_eq!(explain.skipped(), 1);
+        assert_eq!(
+            explain.to_text(),
+            "machine 1: A 80 times, B 40 times\nmachine 2: no prize\n(1 more facts not recorded)\n"
+        );
+
+        let mut disabled = Explain::disabled();
+        disabled.fact("ignored", 1);
+        assert!(!disabled.is_recording());
+        assert!(disabled.facts().is_empty());
+    }
+}
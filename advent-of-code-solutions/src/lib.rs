@@ -1,28 +1,17 @@
-use std::collections::BTreeMap;
-
-use anyhow::Result;
-use aoc_solution::AoCSolution;
-
 pub mod aoc_solution;
 
-pub mod solutions;
+pub mod device_baseline;
 
-pub mod utils;
+pub mod explain;
 
-mod advent_of_code_2022;
+pub mod registry;
 
-pub fn advent_of_code_2022(solutions: &mut BTreeMap<u32, Box<dyn AoCSolution>>) -> Result<()> {
-    crate::advent_of_code_2022::advent_of_code(solutions)
-}
+pub mod runtime_profiles;
 
-mod advent_of_code_2023;
+pub mod solutions;
 
-pub fn advent_of_code_2023(solutions: &mut BTreeMap<u32, Box<dyn AoCSolution>>) -> Result<()> {
-    crate::advent_of_code_2023::advent_of_code(solutions)
-}
+pub mod trace;
 
-mod advent_of_code_2024;
+pub mod utils;
 
-pub fn advent_of_code_2024(solutions: &mut BTreeMap<u32, Box<dyn AoCSolution>>) -> Result<()> {
-    crate::advent_of_code_2024::advent_of_code(solutions)
-}
+pub use registry::all_solutions;
@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+
+use crate::aoc_solution::{AoCSolution, Puzzle, Solution};
+use crate::solutions::{aoc2022, aoc2023, aoc2024};
+
+/// One registered solution: where it belongs, what it is called and how to build it.
+pub struct SolutionEntry {
+    /// The year of the Advent of Code event.
+    pub year: u32,
+    /// The day of the puzzle within the event.
+    pub day: u32,
+    /// The puzzle title as shown on the calendar.
+    pub title: &'static str,
+    /// The source code of the solution, shown next to the puzzle.
+    pub source: &'static str,
+    /// Creates a fresh instance of the solution.
+    pub constructor: fn() -> Box<dyn AoCSolution>,
+}
+
+impl SolutionEntry {
+    /// Creates a fresh instance of the solution.
+    pub fn build(&self) -> Box<dyn AoCSolution> {
+        (self.constructor)()
+    }
+}
+
+/// Builds the registry table. Every solution is listed exactly once, grouped by year:
+///
+/// ```text
+/// <module> {
+///     <file>::<type>,
+/// }
+/// ```
+///
+/// Year, day and title come from the [`Puzzle`] implementation; `<file>.rs` inside
+/// `solutions/<module>` is embedded as the source of the solution.
+macro_rules! registry {
+    ($($module:ident {
+        $($file:ident :: $ty:ident,)*
+    })*) => {
+        static SOLUTIONS: &[SolutionEntry] = &[$($({
+            const SOURCE: &str = include_str!(concat!(
+                "solutions/",
+                stringify!($module),
+                "/",
+                stringify!($file),
+                ".rs"
+            ));
+            SolutionEntry {
+                year: <$module::$ty as Puzzle>::YEAR,
+                day: <$module::$ty as Puzzle>::DAY,
+                title: <$module::$ty as Puzzle>::TITLE,
+                source: SOURCE,
+                constructor: || Box::new(Solution::<$module::$ty>::new(SOURCE)),
+            }
+        },)*)*];
+    };
+}
+
+registry! {
+    aoc2022 {
+        day01_impl::Day01,
+        day02_impl::Day02,
+        day03_impl::Day03,
+        day04_impl::Day04,
+        day05_impl::Day05,
+        day06_impl::Day06,
+    }
+    aoc2023 {
+        day01_impl::Day01,
+        day02_impl::Day02,
+        day03_impl::Day03,
+        day04_impl::Day04,
+        day05_impl::Day05,
+    }
+    aoc2024 {
+        day01_impl::Day01,
+        day02_impl::Day02,
+        day03_impl::Day03,
+        day04_impl::Day04,
+        day05_impl::Day05,
+        day06_impl::Day06,
+        day07_impl::Day07,
+        day08_impl::Day08,
+        day09_impl::Day09,
+        day10_impl::Day10,
+        day11_impl::Day11,
+        day12_impl::Day12,
+        day13_impl::Day13,
+        day14_impl::Day14,
+        day15_impl::Day15,
+        day16_impl::Day16,
+        day17_impl::Day17,
+        day18_impl::Day18,
+        day19_impl::Day19,
+        day20_impl::Day20,
+        day21_impl::Day21,
+        day22_impl::Day22,
+        day23_impl::Day23,
+        day24_impl::Day24,
+        day25_impl::Day25,
+    }
+}
+
+/// Returns every registered solution, ordered by year and day.
+pub fn all_solutions() -> &'static [SolutionEntry] {
+    SOLUTIONS
+}
+
+/// Returns the solution for the given year and day, if there is one.
+pub fn find(year: u32, day: u32) -> Option<&'static SolutionEntry> {
+    SOLUTIONS
+        .iter()
+        .find(|entry| entry.year == year && entry.day == day)
+}
+
+/// Builds every registered solution, grouped by year and keyed by day.
+pub fn solutions_by_year() -> BTreeMap<u32, BTreeMap<u32, Box<dyn AoCSolution>>> {
+    let mut years: BTreeMap<u32, BTreeMap<u32, Box<dyn AoCSolution>>> = BTreeMap::new();
+    for entry in SOLUTIONS {
+        years
+            .entry(entry.year)
+            .or_default()
+            .insert(entry.day, entry.build());
+    }
+    years
+}
+
+mod test {
+    #[test]
+    /// Every (year, day) pair is registered once, in order, and builds a matching solution.
+    fn test_registry_is_ordered_and_consistent() {
+        use super::all_solutions;
+        let entries = all_solutions();
+        assert!(entries
+            .windows(2)
+            .all(|pair| (pair[0].year, pair[0].day) < (pair[1].year, pair[1].day)));
+        for entry in entries {
+            let solution = entry.build();
+            assert_eq!(solution.year(), entry.year);
+            assert_eq!(solution.day(), entry.day);
+            assert_eq!(solution.desc(), entry.title);
+            assert_eq!(solution.code(), entry.source);
+        }
+    }
+
+    #[test]
+    /// Every declared parameter accepts its default value; unknown parameters are refused.
+    fn test_registry_params() {
+        use super::{all_solutions, find};
+        use crate::aoc_solution::PuzzleParams;
+
+        for entry in all_solutions() {
+            let mut solution = entry.build();
+            let mut defaults = PuzzleParams::new();
+            for param in solution.params() {
+                defaults.set(param.name, param.default);
+            }
+            assert_eq!(solution.configure(&defaults), Ok(()));
+            assert!(solution.configure(&"unknown=1".parse().unwrap()).is_err());
+        }
+
+        let mut day18 = find(2024, 18).unwrap().build();
+        assert!(day18.configure(&"size=six".parse().unwrap()).is_err());
+        assert_eq!(
+            day18.configure(&"size=6, bytes=12".parse().unwrap()),
+            Ok(())
+        );
+        assert_eq!(day18.solve_part_1("0,1\n1,1\n").unwrap(), "12");
+        // Copies keep the settings
+        assert_eq!(day18.factory().solve_part_1("0,1\n1,1\n").unwrap(), "12");
+    }
+}
@@ -0,0 +1,20 @@
+//! Measured runtime profiles of the solutions, keyed by year, day and part.
+//!
+//! Generated by `advent-of-code-app profile`; do not edit by hand.
+
+use crate::aoc_solution::RuntimeProfile;
+
+/// How many times slower than the profiling machine the ESP32 is taken to be. Every
+/// device time is an estimate: the host time multiplied by this factor.
+pub const DEVICE_FACTOR: f64 = 50.0;
+
+/// Every measured part, ordered by year, day and part.
+pub static PROFILES: &[((u32, u32, u32), RuntimeProfile)] = &[];
+
+/// Returns the profile of one part, if it has been measured.
+pub fn lookup(year: u32, day: u32, part: u32) -> Option<RuntimeProfile> {
+    PROFILES
+        .iter()
+        .find(|(key, _)| *key == (year, day, part))
+        .map(|(_, profile)| *profile)
+}
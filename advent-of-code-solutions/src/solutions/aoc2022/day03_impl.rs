@@ -1,6 +1,26 @@
-use super::day03::Day03;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::parse;
+
+/// Day 3: Rucksack Reorganization.
+#[derive(Default)]
+pub struct Day03;
 
 impl Day03 {
+    // Collects the non-empty rucksack lines and makes sure they only contain item types (a-z, A-Z).
+    fn parse_rucksacks(input: &str) -> Result<Vec<&str>, SolveError> {
+        parse::lines(input)
+            .map(
+                |line| match line.text.find(|ch: char| !ch.is_ascii_alphabetic()) {
+                    Some(column) => {
+                        let item = &line.text[column..column + 1];
+                        Err(line.error_at(item, format!("unexpected item type '{}'", item)))
+                    }
+                    None => Ok(line.text),
+                },
+            )
+            .collect()
+    }
+
     // Finds the priority of the common item type that appears in all three given rucksacks.
     // This function helps determine which item type needs to be rearranged.
     fn find_common_char_priority(s1: &str, s2: &str, s3: &str) -> Option<i64> {
@@ -60,26 +80,30 @@ impl Day03 {
 
     // Solves Part 1 of the Rucksack Reorganization puzzle.
     // Finds the sum of priorities of misplaced items within each rucksack.
-    pub fn part_1(&self, _input: &str) -> String {
-        let sum = _input
-            .lines()
-            .filter(|line| !line.trim().is_empty())
+
+    // Solves Part 2 of the Rucksack Reorganization puzzle.
+    // Finds the sum of priorities of common badge items across groups of three Elves.
+}
+
+impl Puzzle for Day03 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let sum = Self::parse_rucksacks(input)?
+            .into_iter()
             .map(|line| {
                 let mid = line.len() / 2; // Calculate the midpoint of the rucksack
                 let (left, right) = line.split_at(mid); // Split the rucksack into two compartments
                 Self::find_common_char_priority(left, right, right).unwrap_or(0)
             })
             .sum::<i64>(); // Sum the priorities of the misplaced items
-        sum.to_string() // Convert the final result to a String
+        Ok(sum.into()) // Convert the final result to an Answer
     }
 
-    // Solves Part 2 of the Rucksack Reorganization puzzle.
-    // Finds the sum of priorities of common badge items across groups of three Elves.
-    pub fn part_2(&self, _input: &str) -> String {
-        let lines: Vec<&str> = _input
-            .lines()
-            .filter(|line| !line.trim().is_empty())
-            .collect();
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let lines = Self::parse_rucksacks(input)?;
 
         let sum = lines
             .chunks(3) // Group the rucksacks in sets of three (each Elf group)
@@ -91,7 +115,7 @@ impl Day03 {
                 }
             })
             .sum::<i64>(); // Sum the priorities of the badge items
-        sum.to_string() // Convert the final result to a String
+        Ok(sum.into()) // Convert the final result to an Answer
     }
 }
 
@@ -100,13 +124,9 @@ mod test {
     /// Test for Part 1 of Day 3: Rucksack Reorganization.
     /// Ensures that the sum of misplaced item priorities is calculated correctly.
     fn test_aoc2022_day03_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2022::Day03;
-        let day03 = Day03 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day03 = Day03;
         let input = r#"
 vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
@@ -115,20 +135,16 @@ wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw
         "#;
-        assert_eq!(day03.part_1(input), "157"); // Check if the result matches the expected value
+        assert_eq!(day03.part_1(input).unwrap(), "157"); // Check if the result matches the expected value
     }
 
     #[test]
     /// Test for Part 2 of Day 3: Rucksack Reorganization.
     /// Verifies the correct calculation of badge priorities for groups of three Elves.
     fn test_aoc2022_day03_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2022::Day03;
-        let day03 = Day03 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day03 = Day03;
         let input = r#"
 vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
@@ -137,6 +153,6 @@ wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw
         "#;
-        assert_eq!(day03.part_2(input), "70"); // Check if the result matches the expected value
+        assert_eq!(day03.part_2(input).unwrap(), "70"); // Check if the result matches the expected value
     }
 }
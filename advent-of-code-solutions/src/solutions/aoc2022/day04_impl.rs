@@ -1,66 +1,64 @@
-use super::day04::Day04;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::interval::Interval;
+use crate::utils::parse::{self, Line};
+
+/// Day 4: Camp Cleanup.
+#[derive(Default)]
+pub struct Day04;
+
+/// A range of section IDs.
+type Range = Interval<i32>;
 
 impl Day04 {
     /// Helper function to parse a line into two ranges.
-    /// Returns a tuple of ranges (start1, end1) and (start2, end2).
-    fn parse_ranges(line: &str) -> ((i32, i32), (i32, i32)) {
-        let pair: Vec<&str> = line.split(",").collect();
-
-        let range1: (i32, i32) = {
-            let nums: Vec<i32> = pair[0]
-                .split("-")
-                .map(|x| x.parse::<i32>().unwrap())
-                .collect();
-            (nums[0], nums[1])
+    /// Returns a tuple of both ranges.
+    fn parse_ranges(line: &Line) -> Result<(Range, Range), SolveError> {
+        // Parses a single "start-end" range.
+        let parse_range = |range: &str| -> Result<Range, SolveError> {
+            let (start, end) = range
+                .split_once('-')
+                .ok_or_else(|| line.error_at(range, "expected a range like 2-4"))?;
+            let start = line.number(start, "a section number")?;
+            let end = line.number(end, "a section number")?;
+            Interval::inclusive(start, end)
+                .ok_or_else(|| line.error_at(range, "range exceeds the number space"))
         };
 
-        let range2: (i32, i32) = {
-            let nums: Vec<i32> = pair[1]
-                .split("-")
-                .map(|x| x.parse::<i32>().unwrap())
-                .collect();
-            (nums[0], nums[1])
-        };
+        let (first, second) = line.split_once(",", "two ranges separated by ','")?;
+        Ok((parse_range(first)?, parse_range(second)?))
+    }
 
-        (range1, range2)
+    /// Parses all non-empty lines into pairs of ranges.
+    fn parse_pairs(input: &str) -> Result<Vec<(Range, Range)>, SolveError> {
+        parse::lines(input)
+            .map(|line| Self::parse_ranges(&line))
+            .collect()
     }
+}
 
-    /// Part 1: Counts the number of assignment pairs where one range fully contains the other.
-    pub fn part_1(&self, _input: &str) -> String {
-        let sum = _input
-            .lines()
-            .filter(|line| !line.trim().is_empty())
-            .map(|line| {
-                let ((start1, end1), (start2, end2)) = Self::parse_ranges(line);
+impl Puzzle for Day04 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 4;
+    const TITLE: &'static str = "Camp Cleanup";
 
-                // Check if one range fully contains the other.
-                if (start2 <= start1 && end1 <= end2) || (start1 <= start2 && end2 <= end1) {
-                    1
-                } else {
-                    0
-                }
-            })
-            .sum::<u32>();
-        sum.to_string() // Convert the final result to a String
+    /// Part 1: Counts the number of assignment pairs where one range fully contains the other.
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let sum = Self::parse_pairs(input)?
+            .into_iter()
+            // Check if one range fully contains the other.
+            .filter(|(first, second)| first.covers(second) || second.covers(first))
+            .count();
+        Ok(sum.into()) // Convert the final result to an Answer
     }
 
     /// Part 2: Counts the number of assignment pairs that overlap at all.
-    pub fn part_2(&self, _input: &str) -> String {
-        let sum = _input
-            .lines()
-            .filter(|line| !line.trim().is_empty())
-            .map(|line| {
-                let ((start1, end1), (start2, end2)) = Self::parse_ranges(line);
-
-                // Check if the ranges overlap at all.
-                if (start2 <= end1 && start2 >= start1) || (start1 <= end2 && start1 >= start2) {
-                    1
-                } else {
-                    0
-                }
-            })
-            .sum::<u32>();
-        sum.to_string() // Convert the final result to a String
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let sum = Self::parse_pairs(input)?
+            .into_iter()
+            // Check if the ranges overlap at all.
+            .filter(|(first, second)| first.overlaps(second))
+            .count();
+        Ok(sum.into()) // Convert the final result to an Answer
     }
 }
 
@@ -68,13 +66,9 @@ mod test {
     #[test]
     /// Test for Part 1: Validates the function with example input for fully contained ranges.
     fn test_aoc2022_day04_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2022::Day04;
-        let day04 = Day04 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day04 = Day04;
         let input = r#"
 2-4,6-8
 2-3,4-5
@@ -83,19 +77,15 @@ mod test {
 6-6,4-6
 2-6,4-8
         "#;
-        assert_eq!(day04.part_1(input), "2"); // Expect 2 pairs with full containment.
+        assert_eq!(day04.part_1(input).unwrap(), "2"); // Expect 2 pairs with full containment.
     }
 
     #[test]
     /// Test for Part 2: Validates the function with example input for overlapping ranges.
     fn test_aoc2022_day04_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2022::Day04;
-        let day04 = Day04 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day04 = Day04;
         let input = r#"
 2-4,6-8
 2-3,4-5
@@ -104,6 +94,46 @@ mod test {
 6-6,4-6
 2-6,4-8
         "#;
-        assert_eq!(day04.part_2(input), "4"); // Expect 4 pairs with any overlap.
+        assert_eq!(day04.part_2(input).unwrap(), "4"); // Expect 4 pairs with any overlap.
+    }
+
+    #[test]
+    /// Random assignment pairs are counted like a check of every single section.
+    fn test_aoc2022_day04_properties() {
+        use crate::aoc_solution::Puzzle;
+        use crate::solutions::aoc2022::Day04;
+        use proptest::collection::vec;
+        use proptest::strategy::Strategy;
+        use proptest::test_runner::TestRunner;
+        use std::collections::BTreeSet;
+        let day04 = Day04;
+
+        let range = || (1..30i32, 0..10i32).prop_map(|(start, length)| (start, start + length));
+        let mut runner = TestRunner::default();
+        runner
+            .run(&vec((range(), range()), 1..20), |pairs| {
+                let input: String = pairs
+                    .iter()
+                    .map(|((a, b), (c, d))| format!("{}-{},{}-{}\n", a, b, c, d))
+                    .collect();
+                let sections = |(start, end): (i32, i32)| (start..=end).collect::<BTreeSet<_>>();
+                let (mut contained, mut overlapping) = (0, 0);
+                for &(first, second) in &pairs {
+                    let (first, second) = (sections(first), sections(second));
+                    contained += usize::from(first.is_subset(&second) || second.is_subset(&first));
+                    overlapping += usize::from(!first.is_disjoint(&second));
+                }
+
+                assert_eq!(
+                    day04.part_1(&input).unwrap(),
+                    contained.to_string().as_str()
+                );
+                assert_eq!(
+                    day04.part_2(&input).unwrap(),
+                    overlapping.to_string().as_str()
+                );
+                Ok(())
+            })
+            .unwrap();
     }
 }
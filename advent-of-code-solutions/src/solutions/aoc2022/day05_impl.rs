@@ -1,4 +1,12 @@
-use super::day05::Day05;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::parse::{self, Line};
+
+/// Day 5: Supply Stacks.
+#[derive(Default)]
+pub struct Day05;
+
+/// The crate stacks, bottom to top.
+type Stacks = Vec<Vec<char>>;
 
 impl Day05 {
     /// Parses the input string to extract the initial state of stacks and the move instructions.
@@ -8,56 +16,68 @@ impl Day05 {
     ///   - `count`: Number of crates to move.
     ///   - `from`: Source stack index (1-based).
     ///   - `to`: Destination stack index (1-based).
-    fn parse_input(input: &str) -> (Vec<Vec<char>>, Vec<[usize; 3]>) {
-        let mut lines = input.lines();
-
-        // Determine the number of stacks by finding the line with stack numbering (contains digits).
-        let mut stack_count = 0;
-        while let Some(line) = lines.next() {
-            if line.chars().any(|c| c.is_digit(10)) {
-                stack_count = line.split_whitespace().count();
-                break;
-            }
-        }
+    fn parse_input(input: &str) -> Result<(Stacks, Vec<[usize; 3]>), SolveError> {
+        let sections = parse::sections(input);
+        let drawing = sections
+            .first()
+            .ok_or_else(|| SolveError::parse(1, 1, "expected a drawing of the stacks"))?;
+
+        // The last line of the drawing numbers the stacks; the lines above show the crates.
+        let mut rows: Vec<(usize, &str)> =
+            (drawing.first_line..).zip(drawing.text.lines()).collect();
+        let (_, numbering) = rows.pop().expect("sections are never empty");
+        let stack_count = numbering.split_whitespace().count();
 
         // Initialize the stacks as empty vectors with pre-allocated capacity.
         let mut stacks: Vec<Vec<char>> = vec![Vec::with_capacity(100); stack_count];
 
-        // Reset lines iterator to process the stack representation again.
-        lines = input.lines();
-        while let Some(line) = lines.next() {
-            // Stop parsing the stack representation if a line with digits (stack numbering) is reached.
-            if line.chars().any(|c| c.is_digit(10)) {
-                break;
-            }
-
-            // Parse the characters in the line and assign them to the appropriate stacks.
-            let chars: Vec<char> = line.chars().collect();
-            for (i, &c) in chars.iter().enumerate() {
+        // Fill the stacks from the top row down, so insert every crate at the bottom.
+        for (number, row) in rows {
+            for (i, c) in row.chars().enumerate() {
                 if c != ' ' && (i % 4 == 1) {
                     // Use the character position to determine the corresponding stack index.
                     let column_index = i / 4;
-                    stacks[column_index].insert(0, c); // Insert crates at the bottom of the stack.
+                    let stack = stacks.get_mut(column_index).ok_or_else(|| {
+                        SolveError::parse(number, i + 1, "crate outside of the numbered stacks")
+                    })?;
+                    stack.insert(0, c); // Insert crates at the bottom of the stack.
                 }
             }
         }
 
-        let result_stacks = stacks;
-
-        // Parse the move instructions from the remaining lines.
-        let moves: Vec<[usize; 3]> = lines
-            .filter(|line| line.starts_with("move"))
-            .map(|line| {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                [
-                    parts[1].parse::<usize>().unwrap(), // Number of crates to move.
-                    parts[3].parse::<usize>().unwrap(), // Source stack (1-based).
-                    parts[5].parse::<usize>().unwrap(), // Destination stack (1-based).
-                ]
-            })
-            .collect();
-
-        (result_stacks, moves)
+        // Parse the move instructions from the remaining sections.
+        let moves = sections[1..]
+            .iter()
+            .flat_map(|section| section.lines())
+            .map(|line| Self::parse_move(&line, stack_count))
+            .collect::<Result<Vec<[usize; 3]>, SolveError>>()?;
+
+        Ok((stacks, moves))
+    }
+
+    /// Parses a line like `move 1 from 2 to 1` into `[count, from, to]`.
+    fn parse_move(line: &Line, stack_count: usize) -> Result<[usize; 3], SolveError> {
+        let parts: Vec<&str> = line.text.split_whitespace().collect();
+        if !matches!(parts[..], ["move", _, "from", _, "to", _]) {
+            return Err(line.error("expected 'move <count> from <stack> to <stack>'"));
+        }
+
+        let mov = [
+            line.number(parts[1], "a number")?, // Number of crates to move.
+            line.number(parts[3], "a number")?, // Source stack (1-based).
+            line.number(parts[5], "a number")?, // Destination stack (1-based).
+        ];
+
+        // Both stacks must exist and differ from each other.
+        for (stack, part) in [(mov[1], parts[3]), (mov[2], parts[5])] {
+            if stack == 0 || stack > stack_count {
+                return Err(line.error_at(part, "unknown stack"));
+            }
+        }
+        if mov[1] == mov[2] {
+            return Err(line.error_at(parts[5], "source and destination stack are the same"));
+        }
+        Ok(mov)
     }
 
     /// Executes the move instructions to rearrange the stacks.
@@ -65,7 +85,13 @@ impl Day05 {
     /// - `stacks`: Mutable reference to the vector of stacks.
     /// - `moves`: Slice of move instructions.
     /// - `reverse`: If `true`, crates are moved in reverse order; otherwise, they retain their order.
-    fn execute_moves(stacks: &mut Vec<Vec<char>>, moves: &[[usize; 3]], reverse: bool) {
+    ///
+    /// Fails if a move takes more crates than its source stack holds.
+    fn execute_moves(
+        stacks: &mut [Vec<char>],
+        moves: &[[usize; 3]],
+        reverse: bool,
+    ) -> Result<(), SolveError> {
         for mov in moves {
             let count = mov[0]; // Number of crates to move.
             let from = mov[1] - 1; // Convert 1-based index to 0-based for the source stack.
@@ -81,31 +107,52 @@ impl Day05 {
             };
 
             // Move crates directly without creating intermediate Strings or Vecs.
-            let split_index = source.len() - count;
+            let split_index = source.len().checked_sub(count).ok_or_else(|| {
+                SolveError::unsolvable(format!(
+                    "stack {} holds fewer than {} crates",
+                    from + 1,
+                    count
+                ))
+            })?;
             if reverse {
                 target.extend(source.drain(split_index..).rev());
             } else {
                 target.extend(source.drain(split_index..));
             }
         }
+        Ok(())
     }
+}
+
+impl Puzzle for Day05 {
+    const YEAR: u32 = 2022;
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "Supply Stacks";
 
     /// Solves part 1 of the puzzle where crates are moved in reverse order.
-    pub fn part_1(&self, _input: &str) -> String {
-        let (mut stacks, moves) = Self::parse_input(_input);
-        Self::execute_moves(&mut stacks, &moves, true); // Reverse order for CrateMover 9000.
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let (mut stacks, moves) = Self::parse_input(input)?;
+        Self::execute_moves(&mut stacks, &moves, true)?; // Reverse order for CrateMover 9000.
 
         // Collect the top crate from each stack to form the final result.
-        stacks.iter().filter_map(|s| s.last().copied()).collect()
+        Ok(stacks
+            .iter()
+            .filter_map(|s| s.last().copied())
+            .collect::<String>()
+            .into())
     }
 
     /// Solves part 2 of the puzzle where crates retain their order when moved.
-    pub fn part_2(&self, _input: &str) -> String {
-        let (mut stacks, moves) = Self::parse_input(_input);
-        Self::execute_moves(&mut stacks, &moves, false); // Retain order for CrateMover 9001.
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let (mut stacks, moves) = Self::parse_input(input)?;
+        Self::execute_moves(&mut stacks, &moves, false)?; // Retain order for CrateMover 9001.
 
         // Collect the top crate from each stack to form the final result.
-        stacks.iter().filter_map(|s| s.last().copied()).collect()
+        Ok(stacks
+            .iter()
+            .filter_map(|s| s.last().copied())
+            .collect::<String>()
+            .into())
     }
 }
 
@@ -113,13 +160,9 @@ mod test {
     #[test]
     /// Test for part 1 of Day 5. Verifies correct crate rearrangement using CrateMover 9000.
     fn test_aoc2022_day05_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2022::Day05;
-        let day05 = Day05 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day05 = Day05;
         let input = r#"
     [D]
 [N] [C]
@@ -131,19 +174,15 @@ move 3 from 1 to 3
 move 2 from 2 to 1
 move 1 from 1 to 2
 "#;
-        assert_eq!(day05.part_1(input), "CMZ");
+        assert_eq!(day05.part_1(input).unwrap(), "CMZ");
     }
 
     #[test]
     /// Test for part 2 of Day 5. Verifies correct crate rearrangement using CrateMover 9001.
     fn test_aoc2022_day05_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2022::Day05;
-        let day05 = Day05 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day05 = Day05;
         let input = r#"
     [D]
 [N] [C]
@@ -155,6 +194,6 @@ move 3 from 1 to 3
 move 2 from 2 to 1
 move 1 from 1 to 2
 "#;
-        assert_eq!(day05.part_2(input), "MCD");
+        assert_eq!(day05.part_2(input).unwrap(), "MCD");
     }
 }
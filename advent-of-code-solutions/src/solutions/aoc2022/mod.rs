@@ -1,19 +1,12 @@
-pub use day01::Day01;
-pub use day02::Day02;
-pub use day03::Day03;
-pub use day04::Day04;
-pub use day05::Day05;
-pub use day06::Day06;
-
-mod day01;
+pub use day01_impl::Day01;
 mod day01_impl;
-mod day02;
+pub use day02_impl::Day02;
 mod day02_impl;
-mod day03;
+pub use day03_impl::Day03;
 mod day03_impl;
-mod day04;
+pub use day04_impl::Day04;
 mod day04_impl;
-mod day05;
+pub use day05_impl::Day05;
 mod day05_impl;
-mod day06;
+pub use day06_impl::Day06;
 mod day06_impl;
@@ -1,32 +1,42 @@
-use super::day01::Day01;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::parse;
+
+/// Day 1: Trebuchet?!.
+#[derive(Default)]
+pub struct Day01;
 
 impl Day01 {
     // Part 1 of the challenge: Summing up the first and last digits of each line.
-    pub fn part_1(&self, input: &str) -> String {
-        let sum = input
-            .split("\n") // Splitting the input string into lines.
-            .filter(|s| !s.trim().is_empty()) // Filtering out empty lines.
-            .filter_map(|line| {
+
+    // Part 2 of the challenge: Handling spelled-out numbers.
+}
+
+impl Puzzle for Day01 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Trebuchet?!";
+
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let sum = parse::lines(input)
+            .map(|line| {
                 // Processing each line.
                 let digits: Vec<_> = line
+                    .text
                     .chars() // Collecting all digits in the line.
-                    .filter(|c| c.is_digit(10))
+                    .filter_map(|c| c.to_digit(10))
                     .collect();
-                // Creating a string from the first and last digit.
-                let combined_digits = format!("{}{}", digits[0], digits[digits.len() - 1]);
-                // Parsing the string as a number, defaulting to 0 on error.
-                match combined_digits.parse::<u32>() {
-                    Ok(num) => Some(num),
-                    Err(_) => Some(0),
+                // Combining the first and last digit, a line without digits is malformed.
+                match (digits.first(), digits.last()) {
+                    (Some(first), Some(last)) => Ok(first * 10 + last),
+                    _ => Err(line.error("line contains no digit")),
                 }
             })
-            .sum::<u32>() as i64; // Summing all numbers and casting to i64.
+            .sum::<Result<u32, SolveError>>()? as i64; // Summing all numbers and casting to i64.
 
-        sum.to_string() // Convert the final result to a String
+        Ok(sum.into()) // Convert the final result to an Answer
     }
 
-    // Part 2 of the challenge: Handling spelled-out numbers.
-    pub fn part_2(&self, input: &str) -> String {
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
         let replacements = [
             // Mapping of spelled-out numbers to their digit forms.
             ("zero", "z0o"),
@@ -55,33 +65,25 @@ mod test {
     /// Test for part 1 of Day01 of AoC 2023.
     /// Ensures correct calculation of the sum of first and last digits in each line.
     fn test_aoc2023_day01_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2023::Day01;
-        let day01 = Day01 {
-            day: 1,
-            year: 23,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day01 = Day01;
         let input = r#"
 1abc2
 pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet
         "#;
-        assert_eq!(day01.part_1(input), "142"); // Asserts if the function output matches the expected result.
+        assert_eq!(day01.part_1(input).unwrap(), "142"); // Asserts if the function output matches the expected result.
     }
 
     #[test]
     /// Test for part 2 of Day01 of AoC 2023.
     /// Verifies correct handling and sum of lines with spelled-out numbers.
     fn test_aoc2023_day01_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2023::Day01;
-        let day01 = Day01 {
-            day: 1,
-            year: 23,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day01 = Day01;
         let input = r#"
 two1nine
 eightwothree
@@ -91,6 +93,6 @@ xtwone3four
 zoneight234
 7pqrstsixteen
         "#;
-        assert_eq!(day01.part_2(input), "281"); // Asserts if the function output matches the expected result.
+        assert_eq!(day01.part_2(input).unwrap(), "281"); // Asserts if the function output matches the expected result.
     }
 }
@@ -1,7 +1,12 @@
-use super::day02::Day02;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::parse;
 
 use std::collections::HashMap;
 
+/// Day 2: Cube Conundrum.
+#[derive(Default)]
+pub struct Day02;
+
 #[derive(Debug)]
 struct Game {
     id: i64,
@@ -9,35 +14,46 @@ struct Game {
 }
 
 impl Day02 {
-    fn parse_input(input: &str) -> Vec<Game> {
-        input
-            .lines()
-            .filter(|line| !line.trim().is_empty()) // Skip empty lines
+    fn parse_input(input: &str) -> Result<Vec<Game>, SolveError> {
+        parse::lines(input)
             .map(|line| {
-                let parts: Vec<&str> = line.split(": ").collect();
-                let id = parts[0].trim_start_matches("Game ").parse::<i64>().unwrap();
-                let reveals = parts[1]
-                    .split("; ")
+                let (game, reveals) = line.split_once(":", "'Game <id>: <reveals>'")?;
+                let id_text = game.trim_start_matches("Game ");
+                let id = line.number(id_text, "a game id")?;
+                let reveals = reveals
+                    .split(';')
                     .map(|reveal| {
                         reveal
-                            .split(", ")
+                            .split(',')
                             .map(|cube| {
-                                let parts: Vec<&str> = cube.split(' ').collect();
-                                let count = parts[0].parse::<i64>().unwrap();
-                                let color = parts[1].to_string();
-                                (color, count)
+                                // Each cube entry looks like "3 blue".
+                                let (count, color) =
+                                    cube.trim().split_once(' ').ok_or_else(|| {
+                                        line.error_at(cube, "expected '<count> <color>'")
+                                    })?;
+                                let count = line.number(count, "a cube count")?;
+                                Ok((color.trim().to_string(), count))
                             })
                             .collect()
                     })
-                    .collect();
-                Game { id, reveals }
+                    .collect::<Result<_, SolveError>>()?;
+                Ok(Game { id, reveals })
             })
             .collect()
     }
 
     // Solves Part 1 of the puzzle: sum the IDs of games that are possible with fixed cube limits.
-    pub fn part_1(&self, input: &str) -> String {
-        let games = Self::parse_input(input);
+
+    // Solves Part 2 of the puzzle: sum the powers of the minimum required cube sets.
+}
+
+impl Puzzle for Day02 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Cube Conundrum";
+
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let games = Self::parse_input(input)?;
         let max_cubes = (12, 13, 14); // (red, green, blue)
         let (max_red, max_green, max_blue) = max_cubes;
 
@@ -57,36 +73,34 @@ impl Day02 {
                 }
             })
             .sum::<i64>();
-        sum.to_string() // Convert the final result to a String
+        Ok(sum.into()) // Convert the final result to an Answer
     }
 
-    // Solves Part 2 of the puzzle: sum the powers of the minimum required cube sets.
-    pub fn part_2(&self, _input: &str) -> String {
-        let games = Self::parse_input(_input);
-        let sum = games
-            .iter()
-            .map(|game| {
-                // Find the maximum count of each color across all reveals
-                let mut min_cubes = HashMap::new();
-
-                for reveal in &game.reveals {
-                    for (color, &count) in reveal {
-                        *min_cubes.entry(color.clone()).or_insert(0) =
-                            (*min_cubes.get(color.as_str()).unwrap_or(&0)).max(count);
-                    }
-                }
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let games = Self::parse_input(input)?;
+        let sum = games.iter().try_fold(0i64, |sum, game| {
+            // Find the maximum count of each color across all reveals
+            let mut min_cubes = HashMap::new();
 
-                // Calculate power as red * green * blue
-                let red = *min_cubes.get("red").unwrap_or(&0);
-                let green = *min_cubes.get("green").unwrap_or(&0);
-                let blue = *min_cubes.get("blue").unwrap_or(&0);
+            for reveal in &game.reveals {
+                for (color, &count) in reveal {
+                    *min_cubes.entry(color.clone()).or_insert(0) =
+                        (*min_cubes.get(color.as_str()).unwrap_or(&0)).max(count);
+                }
+            }
 
-                let power = red * green * blue;
+            // Calculate power as red * green * blue
+            let red = *min_cubes.get("red").unwrap_or(&0);
+            let green = *min_cubes.get("green").unwrap_or(&0);
+            let blue = *min_cubes.get("blue").unwrap_or(&0);
 
-                power
-            })
-            .sum::<i64>();
-        sum.to_string() // Convert the final result to a String
+            // The power of the minimum cube set.
+            red.checked_mul(green)
+                .and_then(|power| power.checked_mul(blue))
+                .and_then(|power| sum.checked_add(power))
+                .ok_or_else(|| SolveError::unsolvable("the sum of powers overflows an i64"))
+        })?;
+        Ok(sum.into()) // Convert the final result to an Answer
     }
 }
 
@@ -95,13 +109,9 @@ mod test {
     /// Test for part 1 of Day02 for AoC 2023.
     /// Ensures correct calculation of the sum of first and last digits in each line.
     fn test_aoc2023_day02_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2023::Day02;
-        let day02 = Day02 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day02 = Day02;
         let input = r#"
 Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
@@ -109,20 +119,16 @@ Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
         "#;
-        assert_eq!(day02.part_1(input), "8"); // Asserts if the function output matches the expected result.
+        assert_eq!(day02.part_1(input).unwrap(), "8"); // Asserts if the function output matches the expected result.
     }
 
     #[test]
     /// Test for part 2 of Day02 for AoC 2023.
     /// Verifies correct handling and sum of lines with spelled-out numbers.
     fn test_aoc2023_day02_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2023::Day02;
-        let day02 = Day02 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day02 = Day02;
         let input = r#"
 Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
@@ -130,6 +136,6 @@ Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
         "#;
-        assert_eq!(day02.part_2(input), "2286"); // Asserts if the function output matches the expected result.
+        assert_eq!(day02.part_2(input).unwrap(), "2286"); // Asserts if the function output matches the expected result.
     }
 }
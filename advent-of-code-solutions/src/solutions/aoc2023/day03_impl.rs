@@ -1,189 +1,112 @@
-use super::day03::Day03;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::grid::Grid;
+use crate::utils::point::Point;
+
+/// Day 3: Gear Ratios.
+#[derive(Default)]
+pub struct Day03;
+
+/// A number in the schematic: its value and the points of its digits.
+pub struct PartNumber {
+    value: i64,
+    digits: Vec<Point<i32>>,
+}
 
 impl Day03 {
-    // Parse the input into a 2D grid
-    pub fn parse_input(input: &str) -> Vec<Vec<char>> {
-        input
-            .lines()
-            .filter(|line| !line.trim().is_empty())
-            .map(|line| line.chars().collect())
-            .collect()
+    // Parse the input into a grid, all rows must have the same length
+    pub fn parse_input(input: &str) -> Result<Grid<char>, SolveError> {
+        Grid::parse(input, Ok)
     }
 
-    // Extract numbers adjacent to symbols
-    pub fn extract_numbers_adjacent_to_symbols(matrix: &[Vec<char>]) -> i64 {
-        let rows = matrix.len();
-        let cols = matrix[0].len();
-        let mut sum_extracted_numbers = 0i64;
-
-        // Check if a character is a digit
-        let is_digit = |ch: char| ch.is_ascii_digit();
-
-        // Check if a cell is a symbol
-        let is_symbol = |ch: char| !ch.is_ascii_digit() && ch != '.';
-
-        // Check adjacency to symbols
-        let is_adjacent_to_symbol = |r: usize, c: usize| {
-            let directions = [
-                (-1, -1),
-                (-1, 0),
-                (-1, 1),
-                (0, -1),
-                (0, 1),
-                (1, -1),
-                (1, 0),
-                (1, 1),
-            ];
-            directions.iter().any(|&(dr, dc)| {
-                let nr = r as isize + dr;
-                let nc = c as isize + dc;
-                nr >= 0
-                    && nc >= 0
-                    && nr < rows as isize
-                    && nc < cols as isize
-                    && is_symbol(matrix[nr as usize][nc as usize])
-            })
-        };
-
-        // Iterate through the matrix
-        for r in 0..rows {
-            let mut c = 0;
-            while c < cols {
-                if is_digit(matrix[r][c]) {
-                    // Start building a number
-                    let mut number = 0;
-                    let start_c = c;
-
-                    while c < cols && is_digit(matrix[r][c]) {
-                        number = number * 10 + (matrix[r][c] as i64 - '0' as i64);
-                        c += 1;
+    // Collect all numbers, reading each row from left to right
+    pub fn find_numbers(grid: &Grid<char>) -> Result<Vec<PartNumber>, SolveError> {
+        let mut numbers = Vec::new();
+        for y in 0..grid.height() {
+            let mut current: Option<PartNumber> = None;
+            for (p, ch) in grid.ray(Point::new(0, y as i32), Point::new(1, 0)) {
+                match (ch.to_digit(10), current.as_mut()) {
+                    // Continue the number
+                    (Some(digit), Some(number)) => {
+                        number.value = number
+                            .value
+                            .checked_mul(10)
+                            .and_then(|value| value.checked_add(digit as i64))
+                            .ok_or_else(|| {
+                                SolveError::unsupported("a part number does not fit into an i64")
+                            })?;
+                        number.digits.push(p);
                     }
-
-                    // Check if any part of the number is adjacent to a symbol
-                    let number_len = c - start_c;
-                    for offset in 0..number_len {
-                        if is_adjacent_to_symbol(r, start_c + offset) {
-                            sum_extracted_numbers += number;
-                            break;
-                        }
+                    // Start building a number
+                    (Some(digit), None) => {
+                        current = Some(PartNumber {
+                            value: digit as i64,
+                            digits: vec![p],
+                        })
                     }
-                } else {
-                    c += 1;
+                    (None, _) => numbers.extend(current.take()),
                 }
             }
+            numbers.extend(current); // A number can end the row
         }
-
-        sum_extracted_numbers
+        Ok(numbers)
     }
 
-    // Part 1: Solve the puzzle
-    pub fn part_1(&self, input: &str) -> String {
-        let matrix = Self::parse_input(input);
-        let sum = Self::extract_numbers_adjacent_to_symbols(&matrix);
-        sum.to_string() // Convert the final result to a String
+    // Extract numbers adjacent to symbols
+    pub fn extract_numbers_adjacent_to_symbols(grid: &Grid<char>) -> Result<i64, SolveError> {
+        // Check if a cell is a symbol
+        let is_symbol = |ch: char| !ch.is_ascii_digit() && ch != '.';
+
+        Self::find_numbers(grid)?
+            .iter()
+            .filter(|number| {
+                // Check if any part of the number is adjacent to a symbol
+                number
+                    .digits
+                    .iter()
+                    .any(|&digit| grid.neighbors8(digit).any(|n| is_symbol(grid[n])))
+            })
+            .try_fold(0i64, |sum, number| sum.checked_add(number.value))
+            .ok_or_else(|| SolveError::unsolvable("the sum of part numbers overflows an i64"))
     }
 
     // Extract gear ratios and sum them
-    pub fn find_gear_ratios(matrix: &[Vec<char>]) -> i64 {
-        let rows = matrix.len();
-        let cols = matrix[0].len();
-
-        // Helper to parse a number starting at a position and direction
-        let parse_number_at = |matrix: &[Vec<char>], row: usize, col: usize| -> Option<i64> {
-            let rows = matrix.len();
-            let cols = matrix[0].len();
-            let mut number = String::new();
-
-            // Check left
-            if col > 0 && matrix[row][col - 1].is_ascii_digit() {
-                let mut i = col as isize - 1;
-                while i >= 0 && matrix[row][i as usize].is_ascii_digit() {
-                    number.insert(0, matrix[row][i as usize]); // Prepend to the number
-                    i -= 1;
-                }
-            }
-
-            // Check up
-            if row > 0 && matrix[row - 1][col].is_ascii_digit() {
-                let mut i = row as isize - 1;
-                while i >= 0 && matrix[i as usize][col].is_ascii_digit() {
-                    number.insert(0, matrix[i as usize][col]); // Prepend to the number
-                    i -= 1;
-                }
-            }
-
-            // Include current digit
-            if matrix[row][col].is_ascii_digit() {
-                number.push(matrix[row][col]);
-            } else {
-                return None; // If the current position is not a digit, return None
-            }
-
-            // Check right
-            let mut i = col + 1;
-            while i < cols && matrix[row][i].is_ascii_digit() {
-                number.push(matrix[row][i]);
-                i += 1;
-            }
-
-            // Check down
-            let mut i = row + 1;
-            while i < rows && matrix[i][col].is_ascii_digit() {
-                number.push(matrix[i][col]);
-                i += 1;
-            }
-
-            number.parse::<i64>().ok()
-        };
-
-        // Directions for adjacency
-        let directions = [
-            (-1, -1),
-            (-1, 0),
-            (-1, 1), // Top-left, Top, Top-right
-            (0, -1),
-            (0, 1), // Left, Right
-            (1, -1),
-            (1, 0),
-            (1, 1), // Bottom-left, Bottom, Bottom-right
-        ];
-
-        let mut sum_gear_ratios = 0i64;
+    pub fn find_gear_ratios(grid: &Grid<char>) -> Result<i64, SolveError> {
+        let numbers = Self::find_numbers(grid)?;
 
         // Process each asterisk
-        for r in 0..rows {
-            for c in 0..cols {
-                if matrix[r][c] == '*' {
-                    let mut adjacent_numbers = vec![];
-
-                    for &(dr, dc) in &directions {
-                        let adj_row = (r as isize + dr) as usize;
-                        let adj_col = (c as isize + dc) as usize;
-
-                        if adj_row < rows && adj_col < cols {
-                            if let Some(number) = parse_number_at(&matrix, adj_row, adj_col) {
-                                if !adjacent_numbers.contains(&number) {
-                                    // Check for duplicates
-                                    adjacent_numbers.push(number);
-                                }
-                            }
-                        }
-                    }
-
-                    if adjacent_numbers.len() == 2 {
-                        sum_gear_ratios += adjacent_numbers[0] * adjacent_numbers[1]
-                    }
+        grid.find_all(|&ch| ch == '*')
+            .filter_map(|gear| {
+                let adjacent_numbers: Vec<i64> = numbers
+                    .iter()
+                    .filter(|number| grid.neighbors8(gear).any(|n| number.digits.contains(&n)))
+                    .map(|number| number.value)
+                    .collect();
+
+                match adjacent_numbers[..] {
+                    [a, b] => Some(a.checked_mul(b)), // A gear touches exactly two numbers
+                    _ => None,
                 }
-            }
-        }
+            })
+            .try_fold(0i64, |sum, ratio| sum.checked_add(ratio?))
+            .ok_or_else(|| SolveError::unsolvable("the sum of gear ratios overflows an i64"))
+    }
+}
+
+impl Puzzle for Day03 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "Gear Ratios";
 
-        sum_gear_ratios
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let grid = Self::parse_input(input)?;
+        let sum = Self::extract_numbers_adjacent_to_symbols(&grid)?;
+        Ok(sum.into()) // Convert the final result to an Answer
     }
 
-    pub fn part_2(&self, _input: &str) -> String {
-        let matrix = Self::parse_input(_input);
-        let sum = Self::find_gear_ratios(&matrix);
-        sum.to_string()
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let grid = Self::parse_input(input)?;
+        let sum = Self::find_gear_ratios(&grid)?;
+        Ok(sum.into())
     }
 }
 
@@ -192,13 +115,9 @@ mod test {
     /// Test for part 1 of Day03 for AoC 2023.
     /// Ensures correct calculation of the sum of first and last digits in each line.
     fn test_aoc2023_day03_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2023::Day03;
-        let day03 = Day03 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day03 = Day03;
         let input = r#"
 467..114..
 ...*......
@@ -211,20 +130,16 @@ mod test {
 ...$.*....
 .664.598..
         "#;
-        assert_eq!(day03.part_1(input), "4361"); // Asserts if the function output matches the expected result.
+        assert_eq!(day03.part_1(input).unwrap(), "4361"); // Asserts if the function output matches the expected result.
     }
 
     #[test]
     /// Test for part 2 of Day03 for AoC 2023.
     /// Verifies correct handling and sum of lines with spelled-out numbers.
     fn test_aoc2023_day03_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2023::Day03;
-        let day03 = Day03 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day03 = Day03;
         let input = r#"
 467..114..
 ...*......
@@ -237,6 +152,6 @@ mod test {
 ...$.*....
 .664.598..
         "#;
-        assert_eq!(day03.part_2(input), "467835"); // Asserts if the function output matches the expected result.
+        assert_eq!(day03.part_2(input).unwrap(), "467835"); // Asserts if the function output matches the expected result.
     }
 }
@@ -1,194 +1,108 @@
-use super::day05::Day05;
-
-#[derive(Debug)]
-/// Represents a mapping entry between source and target ranges.
-struct MapEntry {
-    source_begin: u64, // Start of the source range
-    source_end: u64,   // End of the source range
-    target_begin: u64, // Start of the target range
-}
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::interval::{Interval, IntervalSet};
+use crate::utils::parse::{self, Line};
+
+/// Day 5: If You Give A Seed A Fertilizer.
+#[derive(Default)]
+pub struct Day05;
+
+/// A map as pairs of a source range and the start of the target range it moves to.
+type Map = Vec<(Interval<u64>, u64)>;
 
 impl Day05 {
     /// Parses the puzzle input into:
     /// 1. A vector of seed numbers (or ranges for part 2).
-    /// 2. A vector of maps, where each map is a vector of `MapEntry`.
-    fn parse_input(input: &str) -> (Vec<u64>, Vec<Vec<MapEntry>>) {
-        let lines = input.lines();
-        let mut maps = Vec::with_capacity(10); // Allocate space for up to 10 maps
-        let mut current_entries = Vec::with_capacity(50); // Allocate space for up to 50 entries per map
+    /// 2. A vector of maps, each a list of source ranges and their target starts.
+    fn parse_input(input: &str) -> Result<(Vec<u64>, Vec<Map>), SolveError> {
+        let sections = parse::sections(input);
+        let (almanac, maps) = sections
+            .split_first()
+            .ok_or_else(|| SolveError::parse(1, 1, "expected a line starting with 'seeds:'"))?;
 
         // Parse the seed numbers from the `seeds:` line.
-        let seeds = input
-            .lines()
-            .filter(|line| !line.trim().is_empty()) // Skip empty lines
-            .filter_map(|line| {
-                if line.starts_with("seeds:") {
-                    Some(
-                        line["seeds:".len()..] // Extract everything after "seeds:"
-                            .split_whitespace()
-                            .filter_map(|s| s.parse::<u64>().ok()) // Parse as `u64`
-                            .collect::<Vec<u64>>(),
-                    )
-                } else {
-                    None
-                }
-            })
-            .flatten()
-            .collect();
-
-        // Parse the maps from the input.
-        for line in lines {
-            if line.contains("map:") {
-                // A new map starts; save the current map (if any).
-                if !current_entries.is_empty() {
-                    maps.push(current_entries);
-                    current_entries = Vec::with_capacity(50); // Reinitialize for the next map
-                }
-            } else if !line.trim().is_empty() {
-                // Parse individual map entries
-                let parts: Vec<u64> = line
-                    .split_whitespace()
-                    .filter_map(|s| s.parse::<u64>().ok())
-                    .collect();
-
-                if parts.len() == 3 {
-                    let source_begin = parts[1];
-                    let source_end = parts[1] + parts[2]; // Compute source range end
-                    let target_begin = parts[0];
-
-                    let entry = MapEntry {
-                        source_begin,
-                        source_end,
-                        target_begin,
-                    };
-
-                    current_entries.push(entry);
-                }
-            }
+        let mut lines = almanac.lines();
+        let line = lines.next().expect("sections are never empty");
+        let seeds = line.list(line.value_of("seeds:")?, "a number")?;
+        if let Some(line) = lines.next() {
+            return Err(line.error("expected a blank line after the seeds"));
         }
 
-        // Add the last map (if any).
-        if !current_entries.is_empty() {
-            maps.push(current_entries);
-        }
+        // Every map starts with a header like `seed-to-soil map:`, followed by its entries
+        let maps = maps
+            .iter()
+            .map(|map| {
+                let mut lines = map.lines();
+                let header = lines.next().expect("sections are never empty");
+                if !header.text.ends_with("map:") {
+                    return Err(header.error("expected a header like 'seed-to-soil map:'"));
+                }
+                lines
+                    .filter_map(|line| Self::parse_entry(&line).transpose())
+                    .collect()
+            })
+            .collect::<Result<Vec<Map>, SolveError>>()?;
 
-        (seeds, maps)
+        Ok((seeds, maps))
     }
 
-    /// Maps a source range to a target range based on the given map entries.
-    /// Returns a vector of resulting ranges.
-    fn map_source_to_target(range: (u64, u64), mapping: &[MapEntry]) -> Vec<(u64, u64)> {
-        let (start, end) = range;
-        let mut result = Vec::new();
-
-        for entry in mapping {
-            let source_end = entry.source_begin + (entry.source_end - entry.source_begin) - 1;
-            // Check if the source range overlaps with the current entry's range.
-            if entry.source_begin <= end && start <= source_end {
-                let mapped_start = start.max(entry.source_begin);
-                let mapped_end = end.min(source_end);
-                let offset = mapped_start - entry.source_begin;
-
-                result.push((
-                    entry.target_begin + offset,
-                    entry.target_begin + offset + (mapped_end - mapped_start),
-                ));
-            }
-        }
-
-        // If no overlap was found, the range maps to itself.
-        if result.is_empty() {
-            result.push((start, end));
-        }
+    /// Parses a map entry into its source range and target start. Entries of length zero
+    /// map nothing and yield `None`.
+    fn parse_entry(line: &Line) -> Result<Option<(Interval<u64>, u64)>, SolveError> {
+        let parts: Vec<u64> = line.list(line.text, "a number")?;
+        let [target, source, length] = parts[..] else {
+            return Err(line.error("expected '<target start> <source start> <length>'"));
+        };
 
-        result
+        let source_end = source
+            .checked_add(length)
+            .ok_or_else(|| line.error("range exceeds the number space"))?; // Compute source range end
+        let source = Interval::new(source, source_end);
+        Ok((!source.is_empty()).then_some((source, target)))
     }
 
-    /// Merges overlapping or contiguous ranges into a single range.
-    fn merge_ranges(mut ranges: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
-        if ranges.is_empty() {
-            return vec![];
-        }
-
-        // Sort ranges by their starting value.
-        ranges.sort_by(|a, b| a.0.cmp(&b.0));
-
-        let mut merged = Vec::new();
-        merged.push(ranges[0]);
-
-        for range in ranges.iter().skip(1) {
-            let last = merged.last_mut().unwrap();
-            if range.0 <= last.1 + 1 {
-                // Extend the last range if they overlap or are contiguous.
-                last.1 = last.1.max(range.1);
-            } else {
-                merged.push(*range);
-            }
-        }
-
-        merged
+    /// Sends the seeds through every map and returns the smallest resulting location.
+    fn lowest_location(seeds: IntervalSet<u64>, maps: &[Map]) -> Result<Answer, SolveError> {
+        maps.iter()
+            .try_fold(seeds, |ranges, map| ranges.map_piecewise(map))
+            .ok_or_else(|| SolveError::unsupported("a map moves numbers out of range"))?
+            .min()
+            .ok_or_else(|| SolveError::unsolvable("no seeds to locate"))
+            .map(Answer::from)
     }
+}
 
-    /// Solves part 1 of the puzzle: finds the smallest location for individual seeds.
-    pub fn part_1(&self, _input: &str) -> String {
-        let (seeds, maps) = Self::parse_input(_input);
-
-        // Convert seeds into ranges where each range is a single number.
-        let mut current_ranges: Vec<(u64, u64)> =
-            seeds.iter().map(|&start| (start, start)).collect();
-
-        for map in &maps {
-            let mut next_ranges = Vec::new();
-
-            // Map each range through the current map.
-            for range in &current_ranges {
-                let mapped_ranges = Self::map_source_to_target(*range, map);
-                next_ranges.extend(mapped_ranges);
-            }
+impl Puzzle for Day05 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
 
-            // Merge overlapping ranges.
-            current_ranges = Self::merge_ranges(next_ranges);
-        }
+    /// Solves part 1 of the puzzle: finds the smallest location for individual seeds.
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let (seeds, maps) = Self::parse_input(input)?;
 
-        // Find the smallest starting value in the resulting ranges.
-        current_ranges
+        // Every seed is a range of a single number.
+        let seeds = seeds
             .iter()
-            .min_by_key(|&&(start, _)| start)
-            .map(|&(start, _)| start)
-            .unwrap_or_default()
-            .to_string()
+            .map(|&seed| Interval::with_length(seed, 1))
+            .collect();
+        Self::lowest_location(seeds, &maps)
     }
 
     /// Solves part 2 of the puzzle: handles ranges of seeds.
-    pub fn part_2(&self, _input: &str) -> String {
-        let (seeds, maps) = Self::parse_input(_input);
-
-        // Parse seeds as ranges (start, end).
-        let mut current_ranges: Vec<(u64, u64)> = seeds
-            .chunks(2) // Each range is defined by two values: start and length.
-            .map(|chunk| (chunk[0], chunk[0] + chunk[1] - 2)) // Compute end from start + length.
-            .collect();
-
-        for map in &maps {
-            let mut next_ranges = Vec::new();
-
-            // Map each range through the current map.
-            for range in &current_ranges {
-                let mapped_ranges = Self::map_source_to_target(*range, map);
-                next_ranges.extend(mapped_ranges);
-            }
-
-            // Merge overlapping ranges.
-            current_ranges = Self::merge_ranges(next_ranges);
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let (seeds, maps) = Self::parse_input(input)?;
+        if seeds.len() % 2 != 0 {
+            return Err(SolveError::unsupported(
+                "seed ranges need a start and a length",
+            ));
         }
 
-        // Find the smallest starting value in the resulting ranges.
-        current_ranges
-            .iter()
-            .min_by_key(|&&(start, _)| start)
-            .map(|&(start, _)| start)
-            .unwrap_or_default()
-            .to_string()
+        // Each seed range is defined by two values: start and length.
+        let seeds = seeds
+            .chunks(2)
+            .map(|chunk| Interval::with_length(chunk[0], chunk[1]))
+            .collect();
+        Self::lowest_location(seeds, &maps)
     }
 }
 
@@ -197,13 +111,9 @@ mod test {
     /// Test for part 1 of Day05 for AoC 2023.
     /// Ensures correct calculation of the sum of first and last digits in each line.
     fn test_aoc2023_day05_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2023::Day05;
-        let day05 = Day05 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day05 = Day05;
         let input = r#"
 seeds: 79 14 55 13
 
@@ -239,20 +149,16 @@ humidity-to-location map:
 60 56 37
 56 93 4
         "#;
-        assert_eq!(day05.part_1(input), "35"); // Asserts if the function output matches the expected result.
+        assert_eq!(day05.part_1(input).unwrap(), "35"); // Asserts if the function output matches the expected result.
     }
 
     #[test]
     /// Test for part 2 of Day05 for AoC 2023.
     /// Verifies correct handling and sum of lines with spelled-out numbers.
     fn test_aoc2023_day05_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2023::Day05;
-        let day05 = Day05 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day05 = Day05;
         let input = r#"
 seeds: 79 14 55 13
 
@@ -288,6 +194,59 @@ humidity-to-location map:
 60 56 37
 56 93 4
         "#;
-        assert_eq!(day05.part_2(input), "46"); // Asserts if the function output matches the expected result.
+        assert_eq!(day05.part_2(input).unwrap(), "46"); // Asserts if the function output matches the expected result.
+    }
+
+    #[test]
+    /// Random seed ranges and maps agree with mapping every seed on its own.
+    fn test_aoc2023_day05_properties() {
+        use crate::aoc_solution::Puzzle;
+        use crate::solutions::aoc2023::Day05;
+        use proptest::collection::vec;
+        use proptest::test_runner::TestRunner;
+        let day05 = Day05;
+
+        let entry = || (0..100u64, 0..100u64, 1..20u64);
+        let mut runner = TestRunner::default();
+        runner
+            .run(
+                &(
+                    vec((0..100u64, 1..20u64), 1..4),
+                    vec(vec(entry(), 1..4), 1..4),
+                ),
+                |(seeds, maps)| {
+                    let mut input = String::from("seeds:");
+                    for (start, length) in &seeds {
+                        input += &format!(" {} {}", start, length);
+                    }
+                    for (index, map) in maps.iter().enumerate() {
+                        input += &format!("\n\nmap-{} map:\n", index);
+                        for (target, source, length) in map {
+                            input += &format!("{} {} {}\n", target, source, length);
+                        }
+                    }
+
+                    // The first entry whose source range holds the number moves it.
+                    let locate = |seed: u64| {
+                        maps.iter().fold(seed, |number, map| {
+                            map.iter()
+                                .find(|&&(_, source, length)| {
+                                    (source..source + length).contains(&number)
+                                })
+                                .map_or(number, |&(target, source, _)| number - source + target)
+                        })
+                    };
+                    let lowest = seeds
+                        .iter()
+                        .flat_map(|&(start, length)| start..start + length)
+                        .map(locate)
+                        .min()
+                        .unwrap();
+
+                    assert_eq!(day05.part_2(&input).unwrap(), lowest.to_string().as_str());
+                    Ok(())
+                },
+            )
+            .unwrap();
     }
 }
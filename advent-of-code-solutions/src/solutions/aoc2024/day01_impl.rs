@@ -1,62 +1,75 @@
-use super::day01::Day01;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::parse;
 use std::collections::HashMap;
 
+/// Day 1: Historian Hysteria.
+#[derive(Default)]
+pub struct Day01;
+
 // Implementation of methods for the Day01 struct
 impl Day01 {
     /// Parses the input string into two vectors of integers.
     /// Each line in the input is expected to contain two numbers separated by whitespace.
-    fn parse_to_vectors(input: &str) -> (Vec<i32>, Vec<i32>) {
-        let pairs: Vec<(i32, i32)> = input
-            .lines()
-            .filter_map(|line| {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() == 2 {
-                    Some((parts[0].parse::<i32>().ok()?, parts[1].parse::<i32>().ok()?))
-                } else {
-                    None
-                }
-            })
-            .collect();
+    fn parse_to_vectors(input: &str) -> Result<(Vec<i32>, Vec<i32>), SolveError> {
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+
+        for line in parse::lines(input) {
+            // Parse both location IDs, pointing at the offending one on failure.
+            let ids: Vec<i32> = line.list(line.text, "a location ID")?;
+            let [first, second] = ids[..] else {
+                return Err(line.error("expected two location IDs separated by whitespace"));
+            };
+            left.push(first);
+            right.push(second);
+        }
 
-        // Separate and sort both lists.
-        let (mut left, mut right): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+        // Sort both lists.
         left.sort_unstable();
         right.sort_unstable();
 
-        (left, right)
+        Ok((left, right))
     }
+}
+
+impl Puzzle for Day01 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Historian Hysteria";
 
     /// Solves Part 1 of the puzzle.
     /// Calculates the total distance between paired values of sorted left and right lists.
-    pub fn part_1(&self, _input: &str) -> String {
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
         // Parse the input into two vectors.
-        let (left, right) = Self::parse_to_vectors(_input);
+        let (left, right) = Self::parse_to_vectors(input)?;
 
         // Calculate the total distance by summing the absolute differences of paired elements.
-        left.iter()
+        let distance = left
+            .iter()
             .zip(right.iter()) // Pair elements from left and right vectors.
-            .map(|(l, r)| (l - r).abs() as i32) // Calculate absolute difference for each pair.
-            .sum::<i32>()
-            .to_string()
+            .map(|(l, r)| (*l as i64 - *r as i64).abs()) // Calculate absolute difference for each pair.
+            .sum::<i64>();
+        Ok(distance.into())
     }
 
     /// Solves Part 2 of the puzzle.
     /// Calculates the similarity score based on the frequency of elements in the right list.
-    pub fn part_2(&self, _input: &str) -> String {
-        let (left, right) = Self::parse_to_vectors(_input);
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let (left, right) = Self::parse_to_vectors(input)?;
 
         // Pre-calc occurrences in the right vector (this is for runtime optimization)
-        let right_counts: HashMap<i32, i32> =
+        let right_counts: HashMap<i32, i64> =
             right.into_iter().fold(HashMap::new(), |mut map, num| {
                 *map.entry(num).or_insert(0) += 1;
                 map
             });
 
         // Calculate the similarity score.
-        left.iter()
-            .map(|num| *num * right_counts.get(num).copied().unwrap_or(0))
-            .sum::<i32>()
-            .to_string()
+        let score = left
+            .iter()
+            .map(|num| *num as i64 * right_counts.get(num).copied().unwrap_or(0))
+            .sum::<i64>();
+        Ok(score.into())
     }
 }
 
@@ -66,13 +79,9 @@ mod test {
     /// Test for part 1 of Day01 for AoC 2024.
     /// Ensures correct calculation of the total distance between paired values.
     fn test_aoc2024_day01_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day01;
-        let day01 = Day01 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day01 = Day01;
         let input = r#"
 3   4
 4   3
@@ -81,20 +90,16 @@ mod test {
 3   9
 3   3
         "#;
-        assert_eq!(day01.part_1(input), "11"); // Assert correct output for example input.
+        assert_eq!(day01.part_1(input).unwrap(), "11"); // Assert correct output for example input.
     }
 
     #[test]
     /// Test for part 2 of Day01 for AoC 2024.
     /// Verifies correct calculation of the similarity score.
     fn test_aoc2024_day01_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day01;
-        let day01 = Day01 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day01 = Day01;
         let input = r#"
 3   4
 4   3
@@ -103,6 +108,6 @@ mod test {
 3   9
 3   3
         "#;
-        assert_eq!(day01.part_2(input), "31"); // Assert correct output for example input.
+        assert_eq!(day01.part_2(input).unwrap(), "31"); // Assert correct output for example input.
     }
 }
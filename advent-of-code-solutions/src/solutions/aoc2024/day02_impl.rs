@@ -1,17 +1,16 @@
-use super::day02::Day02;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::parse;
+
+/// Day 2: Red-Nosed Reports.
+#[derive(Default)]
+pub struct Day02;
 
 impl Day02 {
     /// Parses a string into a Vec<Vec<i32>> by splitting it line by line
     /// and converting space-separated numbers into integers.
-    fn parse_to_vectors(input: &str) -> Vec<Vec<i32>> {
-        input
-            .lines() // Split the input into lines.
-            .filter(|s| !s.trim().is_empty()) // Remove any empty lines.
-            .map(|line| {
-                line.split_whitespace() // Split the line into words based on spaces.
-                    .filter_map(|num| num.parse::<i32>().ok()) // Attempt to parse each word as an i32.
-                    .collect() // Collect the parsed integers into a Vec<i32>.
-            })
+    fn parse_to_vectors(input: &str) -> Result<Vec<Vec<i32>>, SolveError> {
+        parse::lines(input)
+            .map(|line| line.list(line.text, "a level")) // Parse the space-separated levels.
             .collect() // Collect all the parsed Vec<i32> into a Vec<Vec<i32>>.
     }
 
@@ -24,28 +23,34 @@ impl Day02 {
             // Check if all adjacent differences are between 1 and 3 and are consistently decreasing.
             || report.windows(2).all(|w| (1..=3).contains(&(w[0] - w[1])))
     }
+}
+
+impl Puzzle for Day02 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Red-Nosed Reports";
 
     /// Solves Part 1 of the puzzle.
     /// Counts the number of reports that are "safe" without any modifications.
-    pub fn part_1(&self, _input: &str) -> String {
-        let level_vectors = Self::parse_to_vectors(_input); // Parse the input into a Vec<Vec<i32>>.
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let level_vectors = Self::parse_to_vectors(input)?; // Parse the input into a Vec<Vec<i32>>.
 
-        // Count the number of safe reports and convert the count to a String.
-        level_vectors
+        // Count the number of safe reports.
+        let count = level_vectors
             .iter() // Iterate over each report.
             .filter(|report| Self::is_safe_report(report)) // Keep only the reports that are safe.
-            .count() // Count the number of safe reports.
-            .to_string() // Convert the count to a String for the final output.
+            .count(); // Count the number of safe reports.
+        Ok(count.into())
     }
 
     /// Solves Part 2 of the puzzle.
     /// Counts the number of reports that are "safe" with the Problem Dampener:
     /// - Allows removing one "bad" level to make the report safe.
-    pub fn part_2(&self, _input: &str) -> String {
-        let level_vectors = Self::parse_to_vectors(_input); // Parse the input into a Vec<Vec<i32>>.
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let level_vectors = Self::parse_to_vectors(input)?; // Parse the input into a Vec<Vec<i32>>.
 
         // Calculate the total number of safe reports, considering the Problem Dampener.
-        level_vectors
+        let count = level_vectors
             .iter() // Iterate over each report.
             .map(|report| {
                 if Self::is_safe_report(report) {
@@ -65,8 +70,8 @@ impl Day02 {
                     }) as u32 // Convert the boolean to 1 or 0.
                 }
             })
-            .sum::<u32>() // Sum up the counts of all safe reports.
-            .to_string() // Convert the sum to a String for the final output.
+            .sum::<u32>(); // Sum up the counts of all safe reports.
+        Ok(count.into())
     }
 }
 
@@ -75,13 +80,9 @@ mod test {
     /// Test for Part 1 of Day02.
     /// Ensures correct calculation of the number of safe reports without modifications.
     fn test_aoc2024_day02_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day02;
-        let day02 = Day02 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day02 = Day02;
         let input = r#"
 7 6 4 2 1
 1 2 7 8 9
@@ -92,20 +93,16 @@ mod test {
         "#;
 
         // Expected result: 2 safe reports in this input.
-        assert_eq!(day02.part_1(input), "2");
+        assert_eq!(day02.part_1(input).unwrap(), "2");
     }
 
     #[test]
     /// Test for Part 2 of Day02.
     /// Verifies correct calculation of the number of safe reports with the Problem Dampener.
     fn test_aoc2024_day02_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day02;
-        let day02 = Day02 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day02 = Day02;
         let input = r#"
 7 6 4 2 1
 1 2 7 8 9
@@ -116,6 +113,6 @@ mod test {
         "#;
 
         // Expected result: 4 safe reports with the Problem Dampener.
-        assert_eq!(day02.part_2(input), "4");
+        assert_eq!(day02.part_2(input).unwrap(), "4");
     }
 }
@@ -1,74 +1,99 @@
-use super::day03::Day03;
-use lazy_static::lazy_static;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::parse;
 
-use regex::Regex;
-
-// Use compile-time regex to reduce memory usage
-lazy_static! {
-    static ref AOC_2024_03_OUTER_REGEX: Regex = Regex::new(r"(mul|do|don't)\(([^()]*?)\)").unwrap();
-    static ref AOC_2024_03_INNER_REGEX: Regex = Regex::new(r"^(\d+),\s*(\d+)$").unwrap();
-}
+/// Day 3: Mull It Over.
+#[derive(Default)]
+pub struct Day03;
 
 impl Day03 {
     /// Parses the input string to extract valid instructions and their arguments.
     /// Returns a vector of tuples where the first element is the instruction name
     /// (e.g., "mul", "do", "don't"), and the second element is a vector of arguments.
-    fn parse_instructions(input: &str) -> Vec<(String, Vec<u32>)> {
+    /// Corrupted memory is expected, so anything that is not a valid instruction is skipped.
+    fn parse_instructions(input: &str) -> Vec<(String, Vec<u64>)> {
         let mut results = Vec::new();
 
-        // Iterate over all matches found by the main regex
-        for caps in AOC_2024_03_OUTER_REGEX.captures_iter(input) {
-            let func_name = &caps[1]; // Capture the function name (e.g., "mul")
-            let args = &caps[2]; // Capture the arguments inside the parentheses
-
-            match func_name {
-                "mul" => {
-                    // Use a secondary regex to parse arguments for "mul"
-                    if let Some(arg_caps) = AOC_2024_03_INNER_REGEX.captures(args) {
-                        let arg1: u32 = arg_caps[1].parse().unwrap(); // Parse the first argument
-                        let arg2: u32 = arg_caps[2].parse().unwrap(); // Parse the second argument
-                        results.push((func_name.to_string(), vec![arg1, arg2]));
-                        // Store the parsed instruction
+        for line in parse::lines(input) {
+            let mut rest = line.text;
+            while !rest.is_empty() {
+                // Try every instruction at the current position, longest names first
+                let call = ["mul", "don't", "do"].into_iter().find_map(|name| {
+                    let args = rest.strip_prefix(name)?.strip_prefix('(')?;
+                    // The arguments end at the first ')' and contain no other parenthesis
+                    let end = args.find(['(', ')'])?;
+                    (args[end..].starts_with(')')).then(|| (name, &args[..end]))
+                });
+
+                match call {
+                    Some(("mul", args)) => {
+                        // Arguments too large for a u64 are corrupted as well
+                        if let Some(args) = Self::parse_mul_arguments(args) {
+                            results.push(("mul".to_string(), args)); // Store the parsed instruction
+                        }
                     }
+                    Some((name, _)) => {
+                        // For "do" and "don't", simply add the instruction with no arguments
+                        results.push((name.to_string(), vec![]));
+                    }
+                    None => {}
                 }
-                "do" | "don't" => {
-                    // For "do" and "don't", simply add the instruction with no arguments
-                    results.push((func_name.to_string(), vec![]));
-                }
-                _ => {}
+
+                // Move on by one character; instructions may start inside corrupted ones
+                let next = rest.chars().next().map_or(1, char::len_utf8);
+                rest = &rest[next..];
             }
         }
 
         results
     }
 
+    /// Parses the arguments of `mul`: two numbers separated by a comma, optionally
+    /// followed by whitespace.
+    fn parse_mul_arguments(args: &str) -> Option<Vec<u64>> {
+        let (first, second) = args.split_once(',')?;
+        let second = second.trim_start();
+        let is_number = |text: &str| !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit());
+        if !is_number(first) || !is_number(second) {
+            return None;
+        }
+        Some(vec![first.parse().ok()?, second.parse().ok()?])
+    }
+
+    /// The error returned when the sum of all products does not fit into a u64.
+    fn overflow() -> SolveError {
+        SolveError::unsolvable("the sum of all products overflows a u64")
+    }
+}
+
+impl Puzzle for Day03 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "Mull It Over";
+
     /// Part 1: Calculates the sum of all valid "mul(X,Y)" results in the input.
-    pub fn part_1(&self, input: &str) -> String {
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
         // Parse the input to extract instructions
         let pairs = Self::parse_instructions(input);
 
         // Calculate the sum of all "mul" results
-        let sum: u32 = pairs
+        let sum = pairs
             .iter()
-            .filter_map(|(inst, args)| {
-                if inst == "mul" {
-                    Some(args[0] * args[1])
-                } else {
-                    None
-                }
+            .filter(|(inst, _)| inst == "mul")
+            .try_fold(0u64, |sum, (_, args)| {
+                sum.checked_add(args[0].checked_mul(args[1])?)
             })
-            .sum();
+            .ok_or_else(Self::overflow)?;
 
-        sum.to_string() // Return the result as a string
+        Ok(sum.into()) // Return the result as an Answer
     }
 
     /// Part 2: Handles enabling/disabling of "mul" based on "do()" and "don't()".
-    pub fn part_2(&self, input: &str) -> String {
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
         // Parse the input to extract instructions
         let pairs = Self::parse_instructions(input);
 
         let mut mul_enabled = true; // "mul" is initially enabled
-        let mut result = 0; // Initialize the result sum
+        let mut result: u64 = 0; // Initialize the result sum
 
         for (inst, args) in pairs {
             match inst.as_str() {
@@ -76,13 +101,16 @@ impl Day03 {
                 "don't" => mul_enabled = false, // Disable "mul" instructions
                 "mul" if mul_enabled => {
                     // Process "mul" only if enabled
-                    result += args[0] * args[1];
+                    result = args[0]
+                        .checked_mul(args[1])
+                        .and_then(|product| result.checked_add(product))
+                        .ok_or_else(Self::overflow)?;
                 }
                 _ => {}
             }
         }
 
-        result.to_string() // Return the final result as a string
+        Ok(result.into()) // Return the final result as an Answer
     }
 }
 
@@ -91,28 +119,20 @@ mod test {
     #[test]
     /// Test for Part 1 of Day03 to ensure correct handling of all valid "mul" instructions.
     fn test_aoc2024_day03_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day03; // Import the Day03 struct
-        let day03 = Day03 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day03 = Day03;
         let input = r#"xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))"#;
-        assert_eq!(day03.part_1(input), "161"); // Expected result for Part 1
+        assert_eq!(day03.part_1(input).unwrap(), "161"); // Expected result for Part 1
     }
 
     #[test]
     /// Test for Part 2 of Day03 to verify correct handling of "do()" and "don't()" instructions.
     fn test_aoc2024_day03_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day03; // Import the Day03 struct
-        let day03 = Day03 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day03 = Day03;
         let input = r#"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))"#;
-        assert_eq!(day03.part_2(input), "48"); // Expected result for Part 2
+        assert_eq!(day03.part_2(input).unwrap(), "48"); // Expected result for Part 2
     }
 }
@@ -1,130 +1,73 @@
-use super::day04::Day04;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::grid::Grid;
+use crate::utils::point::{Direction, Point};
 
-impl Day04 {
-    /// Parses the input string into a 2D matrix of characters.
-    /// Each line in the input becomes a row in the matrix, ignoring empty lines.
-    pub fn parse_input(input: &str) -> Vec<Vec<char>> {
-        input
-            .lines()
-            .filter(|line| !line.trim().is_empty()) // Ignore empty lines
-            .map(|line| line.chars().collect()) // Convert each line into a vector of characters
-            .collect() // Collect all lines into a 2D vector
-    }
-
-    /// Counts all occurrences of the word "XMAS" in the matrix, in any direction.
-    pub fn count_xmas_words(matrix: &[Vec<char>]) -> usize {
-        let rows = matrix.len();
-        let cols = matrix[0].len();
-        let mut sum_extracted_xmas = 0;
-
-        // "XMAS" as a constant sequence of characters
-        const XMAS: [char; 4] = ['X', 'M', 'A', 'S'];
-
-        /// Checks if "XMAS" exists in the specified direction (dr, dc) starting from (row, col).
-        fn check_direction(
-            matrix: &[Vec<char>],
-            rows: usize,
-            cols: usize,
-            row: usize,
-            col: usize,
-            dr: isize,
-            dc: isize,
-        ) -> bool {
-            let mut buffer = ['\0'; 4]; // Temporary buffer for the characters being checked
-            for i in 0..4 {
-                let nr = row as isize + i * dr; // Calculate the row index for the current character
-                let nc = col as isize + i * dc; // Calculate the column index for the current character
-
-                // Ensure indices are within bounds
-                if nr < 0 || nc < 0 || nr >= rows as isize || nc >= cols as isize {
-                    return false;
-                }
-
-                // Collect the character from the matrix into the buffer
-                buffer[i as usize] = matrix[nr as usize][nc as usize];
-            }
-
-            buffer == XMAS // Check if the buffer matches "XMAS"
-        }
-
-        // Traverse the entire matrix
-        for r in 0..rows {
-            for c in 0..cols {
-                // All possible directions for "XMAS"
-                let directions = [
-                    (-1, -1), // Up-left
-                    (-1, 0),  // Up
-                    (-1, 1),  // Up-right
-                    (0, -1),  // Left
-                    (0, 1),   // Right
-                    (1, -1),  // Down-left
-                    (1, 0),   // Down
-                    (1, 1),   // Down-right
-                ];
+/// Day 4: Ceres Search.
+#[derive(Default)]
+pub struct Day04;
 
-                // Check each direction for "XMAS"
-                for &(dr, dc) in &directions {
-                    if check_direction(matrix, rows, cols, r, c, dr, dc) {
-                        sum_extracted_xmas += 1; // Increment count if "XMAS" is found
-                    }
-                }
-            }
-        }
-
-        sum_extracted_xmas // Return the total count
+impl Day04 {
+    /// Parses the input into a grid of letters. All rows must have the same length.
+    pub fn parse_input(input: &str) -> Result<Grid<char>, SolveError> {
+        Grid::parse(input, Ok)
     }
 
-    /// Solves part 1 of the puzzle: counts all occurrences of "XMAS" in the input.
-    pub fn part_1(&self, input: &str) -> String {
-        let matrix = Self::parse_input(input); // Parse the input into a matrix
-        let sum = Self::count_xmas_words(&matrix); // Count all "XMAS" occurrences
-        sum.to_string() // Convert the result to a string
+    /// Counts all occurrences of the word "XMAS" in the grid, in any direction.
+    pub fn count_xmas_words(grid: &Grid<char>) -> usize {
+        grid.find_all(|&c| c == 'X')
+            .map(|start| {
+                // Read four letters in every direction and compare them with "XMAS"
+                Direction::ALL
+                    .iter()
+                    .filter(|&&direction| {
+                        grid.ray(start, direction.into())
+                            .map(|(_, &c)| c)
+                            .take(4)
+                            .eq("XMAS".chars())
+                    })
+                    .count()
+            })
+            .sum()
     }
 
-    /// Counts occurrences of the "X-MAS" pattern in the matrix.
-    pub fn find_x_mas(matrix: &[Vec<char>]) -> usize {
-        let rows = matrix.len();
-        let cols = matrix[0].len();
+    /// Counts occurrences of the "X-MAS" pattern in the grid.
+    pub fn find_x_mas(grid: &Grid<char>) -> usize {
+        // Both diagonals through an 'A' must read "MAS" or "SAM"
+        let is_mas = |a: Option<&char>, b: Option<&char>| {
+            matches!((a, b), (Some('M'), Some('S')) | (Some('S'), Some('M')))
+        };
 
-        let mut sum_mas = 0;
+        grid.find_all(|&c| c == 'A')
+            .filter(|&p| {
+                is_mas(
+                    grid.get(p + Point::new(-1, -1)),
+                    grid.get(p + Point::new(1, 1)),
+                ) && is_mas(
+                    grid.get(p + Point::new(1, -1)),
+                    grid.get(p + Point::new(-1, 1)),
+                )
+            })
+            .count()
+    }
+}
 
-        // Traverse the matrix, excluding edges (since X-MAS requires neighbors)
-        for r in 1..rows - 1 {
-            for c in 1..cols - 1 {
-                // Check if the current cell is 'A', the center of the X-MAS pattern
-                if matrix[r][c] == 'A' {
-                    // Check for all valid X-MAS configurations
-                    if (matrix[r - 1][c - 1] == 'M' // Top-left
-                        && matrix[r + 1][c + 1] == 'S' // Bottom-right
-                        && matrix[r + 1][c - 1] == 'M' // Bottom-left
-                        && matrix[r - 1][c + 1] == 'S') // Top-right
-                        || (matrix[r - 1][c - 1] == 'S'
-                        && matrix[r + 1][c + 1] == 'M'
-                        && matrix[r + 1][c - 1] == 'S'
-                        && matrix[r - 1][c + 1] == 'M')
-                        || (matrix[r - 1][c - 1] == 'M'
-                        && matrix[r + 1][c + 1] == 'S'
-                        && matrix[r + 1][c - 1] == 'S'
-                        && matrix[r - 1][c + 1] == 'M')
-                        || (matrix[r - 1][c - 1] == 'S'
-                        && matrix[r + 1][c + 1] == 'M'
-                        && matrix[r + 1][c - 1] == 'M'
-                        && matrix[r - 1][c + 1] == 'S')
-                    {
-                        sum_mas += 1; // Increment count if a valid X-MAS pattern is found
-                    }
-                }
-            }
-        }
+impl Puzzle for Day04 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 4;
+    const TITLE: &'static str = "Ceres Search";
 
-        sum_mas // Return the total count
+    /// Solves part 1 of the puzzle: counts all occurrences of "XMAS" in the input.
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let grid = Self::parse_input(input)?; // Parse the input into a grid
+        let sum = Self::count_xmas_words(&grid); // Count all "XMAS" occurrences
+        Ok(sum.into()) // Convert the result to an Answer
     }
 
     /// Solves part 2 of the puzzle: counts all occurrences of the "X-MAS" pattern.
-    pub fn part_2(&self, input: &str) -> String {
-        let matrix = Self::parse_input(input); // Parse the input into a matrix
-        let sum = Self::find_x_mas(&matrix); // Count all "X-MAS" occurrences
-        sum.to_string() // Convert the result to a string
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let grid = Self::parse_input(input)?; // Parse the input into a grid
+        let sum = Self::find_x_mas(&grid); // Count all "X-MAS" occurrences
+        Ok(sum.into()) // Convert the result to an Answer
     }
 }
 
@@ -132,13 +75,9 @@ mod test {
     #[test]
     /// Test for part 1: verifies correct "XMAS" counting in the example input.
     fn test_aoc2024_day04_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day04;
-        let day04 = Day04 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day04 = Day04;
         let input = r#"
 MMMSXXMASM
 MSAMXMSMSA
@@ -151,19 +90,15 @@ SAXAMASAAA
 MAMMMXMMMM
 MXMXAXMASX
         "#;
-        assert_eq!(day04.part_1(input), "18");
+        assert_eq!(day04.part_1(input).unwrap(), "18");
     }
 
     #[test]
     /// Test for part 2: verifies correct "X-MAS" pattern counting in the example input.
     fn test_aoc2024_day04_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day04;
-        let day04 = Day04 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day04 = Day04;
         let input = r#"
 MMMSXXMASM
 MSAMXMSMSA
@@ -176,6 +111,6 @@ SAXAMASAAA
 MAMMMXMMMM
 MXMXAXMASX
         "#;
-        assert_eq!(day04.part_2(input), "9");
+        assert_eq!(day04.part_2(input).unwrap(), "9");
     }
 }
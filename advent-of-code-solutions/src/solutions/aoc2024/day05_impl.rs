@@ -1,104 +1,73 @@
-use super::day05::Day05;
-use std::collections::{HashMap, HashSet, VecDeque};
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::explain::Explain;
+use crate::utils::parse;
+use crate::utils::search;
+use hashbrown::{HashMap, HashSet};
+use std::fmt;
 
-impl Day05 {
-    /// Parses the input into pairs (ordering rules) and lists (updates).
-    fn parse_input(input: &str) -> (Vec<(u8, u8)>, Vec<Vec<u8>>) {
-        let lines = input.lines();
-
-        let mut pairs: Vec<(u8, u8)> = Vec::new(); // Stores the ordering rules
-        let mut lists = Vec::new(); // Stores the page update lists
-        let mut parsing_pairs = true; // Tracks whether we're reading rules or updates
-
-        for line in lines {
-            if line.trim().is_empty() {
-                continue; // Skip empty lines
-            }
-
-            if parsing_pairs {
-                // Parse ordering rules (lines containing '|')
-                if line.contains('|') {
-                    let parts: Vec<&str> = line.split('|').map(|part| part.trim()).collect();
-                    if let [src, dst] = &parts[..] {
-                        let src = src.parse::<u8>().unwrap();
-                        let dst = dst.parse::<u8>().unwrap();
-                        pairs.push((src, dst));
-                    }
-                } else {
-                    parsing_pairs = false; // Switch to parsing update lists
-                }
-            }
-
-            if !parsing_pairs {
-                // Parse update lists (lines containing ',')
-                let list: Vec<u8> = line
-                    .split(',')
-                    .map(|part| part.trim().parse::<u8>().unwrap())
-                    .collect();
-                lists.push(list);
-            }
-        }
+/// Day 5: Print Queue.
+#[derive(Default)]
+pub struct Day05;
 
-        (pairs, lists)
-    }
-
-    /// Performs a topological sort on the given pairs (graph edges).
-    /// Returns a sorted order of nodes.
-    fn topological_sort(pairs: &Vec<(u8, u8)>) -> Vec<u8> {
-        // Build the adjacency list and indegree map
-        let mut graph: HashMap<u8, Vec<u8>> = HashMap::new();
-        let mut indegree: HashMap<u8, u8> = HashMap::new();
-
-        for &(src, dst) in pairs {
-            graph.entry(src).or_insert(Vec::new()).push(dst);
-            *indegree.entry(dst).or_insert(0) += 1;
-            indegree.entry(src).or_insert(0); // Ensure src is included in indegree map
-        }
+/// An ordering rule: the first page must be printed before the second.
+type Rule = (u8, u8);
 
-        // Initialize a queue for nodes with zero incoming edges
-        let mut queue: VecDeque<u8> = indegree
+impl Day05 {
+    /// Parses the input into pairs (ordering rules) and lists (updates).
+    fn parse_input(input: &str) -> Result<(Vec<Rule>, Vec<Vec<u8>>), SolveError> {
+        let sections = parse::sections(input);
+        let (rules, updates) = sections
+            .split_first()
+            .ok_or_else(|| SolveError::parse(1, 1, "expected ordering rules like 47|53"))?;
+
+        // Parse the ordering rules, one `before|after` pair per line
+        let pairs = rules
+            .lines()
+            .map(|line| {
+                let (src, dst) = line.split_once("|", "an ordering rule like 47|53")?;
+                Ok((
+                    line.number(src, "a page number")?,
+                    line.number(dst, "a page number")?,
+                ))
+            })
+            .collect::<Result<Vec<Rule>, SolveError>>()?;
+
+        // Parse the update lists, comma-separated page numbers
+        let lists = updates
             .iter()
-            .filter_map(|(&node, &deg)| if deg == 0 { Some(node) } else { None })
-            .collect();
-
-        let mut sorted: Vec<u8> = Vec::new(); // Stores the final sorted order
-
-        while let Some(node) = queue.pop_front() {
-            sorted.push(node);
-
-            if let Some(neighbors) = graph.get(&node) {
-                for &neighbor in neighbors {
-                    if let Some(deg) = indegree.get_mut(&neighbor) {
-                        *deg -= 1; // Decrement indegree
-                        if *deg == 0 {
-                            queue.push_back(neighbor); // Add to queue when indegree is zero
-                        }
-                    }
-                }
-            }
-        }
+            .flat_map(|section| section.lines())
+            .map(|line| line.list(line.text, "a page number"))
+            .collect::<Result<Vec<Vec<u8>>, _>>()?;
 
-        sorted
+        Ok((pairs, lists))
     }
 
     /// Generalized function to calculate the sum of middle elements.
     /// If `only_valid` is true, it processes valid updates only;
     /// otherwise, it processes invalid updates after reordering them.
-    fn calculate_middle_sum(&self, input: &str, only_valid: bool) -> u32 {
-        let (pairs, lists) = Self::parse_input(input);
+    /// Records for every update whether it was already ordered in `explain`.
+    fn calculate_middle_sum(
+        &self,
+        input: &str,
+        only_valid: bool,
+        explain: &mut Explain,
+    ) -> Result<u32, SolveError> {
+        let (pairs, lists) = Self::parse_input(input)?;
         let mut sum_of_middles: u32 = 0;
 
-        for vector in lists {
+        for (number, vector) in (1..).zip(lists) {
+            // Only the rules between pages of this update take part in its ordering
             let relevant_nodes: HashSet<u8> = vector.iter().cloned().collect();
-            let filtered_pairs: Vec<(u8, u8)> = pairs
-                .iter()
-                .filter(|&&(src, dst)| {
-                    relevant_nodes.contains(&src) && relevant_nodes.contains(&dst)
-                })
-                .cloned()
-                .collect();
+            let mut graph: HashMap<u8, HashSet<u8>> = HashMap::new();
+            for &(src, dst) in &pairs {
+                if relevant_nodes.contains(&src) && relevant_nodes.contains(&dst) {
+                    graph.entry(src).or_default().insert(dst);
+                }
+            }
 
-            let order = Self::topological_sort(&filtered_pairs);
+            let order = search::topological_sort(&graph, &vector).map_err(|_| {
+                SolveError::unsolvable("the ordering rules for an update contain a cycle")
+            })?;
 
             let order_map: HashMap<u8, usize> =
                 order.iter().enumerate().map(|(i, &n)| (n, i)).collect();
@@ -108,25 +77,74 @@ impl Day05 {
 
             let is_valid = sorted_vec == vector;
 
-            if is_valid == only_valid {
-                if !sorted_vec.is_empty() {
-                    let middle_index = sorted_vec.len() / 2;
-                    sum_of_middles += sorted_vec[middle_index] as u32;
-                }
+            if is_valid == only_valid && !sorted_vec.is_empty() {
+                let middle_index = sorted_vec.len() / 2;
+                sum_of_middles += sorted_vec[middle_index] as u32;
+            }
+
+            let middle = sorted_vec.get(sorted_vec.len() / 2).copied().unwrap_or(0);
+            let update = format_args!("update {} ({})", number, Pages(&vector));
+            if is_valid {
+                explain.fact(
+                    update,
+                    format_args!("already ordered, middle page {}", middle),
+                );
+            } else {
+                explain.fact(
+                    update,
+                    format_args!(
+                        "reordered to {}, middle page {}",
+                        Pages(&sorted_vec),
+                        middle
+                    ),
+                );
             }
         }
 
-        sum_of_middles
+        Ok(sum_of_middles)
+    }
+}
+
+/// Pages of an update written like the input, e.g. `75,47,61`.
+struct Pages<'a>(&'a [u8]);
+
+impl fmt::Display for Pages<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, page) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", page)?;
+        }
+        Ok(())
     }
+}
+
+impl Puzzle for Day05 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "Print Queue";
 
     /// Solves Part 1: Computes the sum of middle elements for correctly ordered updates.
-    pub fn part_1(&self, input: &str) -> String {
-        self.calculate_middle_sum(input, true).to_string()
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        self.explain_part_1(input, &mut Explain::disabled())
+    }
+
+    /// Explains Part 1 by listing which updates were already ordered.
+    fn explain_part_1(&self, input: &str, explain: &mut Explain) -> Result<Answer, SolveError> {
+        self.calculate_middle_sum(input, true, explain)
+            .map(Answer::from)
     }
 
     /// Solves Part 2: Computes the sum of middle elements for reordered updates.
-    pub fn part_2(&self, input: &str) -> String {
-        self.calculate_middle_sum(input, false).to_string()
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        self.explain_part_2(input, &mut Explain::disabled())
+    }
+
+    /// Explains Part 2 by listing how each update was reordered.
+    fn explain_part_2(&self, input: &str, explain: &mut Explain) -> Result<Answer, SolveError> {
+        self.calculate_middle_sum(input, false, explain)
+            .map(Answer::from)
     }
 }
 
@@ -135,13 +153,9 @@ mod test {
     /// Test for part 1 of Day05 for AoC 2024.
     /// Ensures correct calculation of the sum of first and last digits in each line.
     fn test_aoc2024_day05_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day05;
-        let day05 = Day05 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day05 = Day05;
         let input = r#"
 47|53
 97|13
@@ -172,20 +186,16 @@ mod test {
 61,13,29
 97,13,75,29,47
         "#;
-        assert_eq!(day05.part_1(input), "143"); // Asserts if the function output matches the expected result.
+        assert_eq!(day05.part_1(input).unwrap(), "143"); // Asserts if the function output matches the expected result.
     }
 
     #[test]
     /// Test for part 2 of Day05 for AoC 2024.
     /// Verifies correct handling and sum of lines with spelled-out numbers.
     fn test_aoc2024_day05_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day05;
-        let day05 = Day05 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day05 = Day05;
         let input = r#"
 47|53
 97|13
@@ -216,6 +226,6 @@ mod test {
 61,13,29
 97,13,75,29,47
         "#;
-        assert_eq!(day05.part_2(input), "123"); // Asserts if the function output matches the expected result.
+        assert_eq!(day05.part_2(input).unwrap(), "123"); // Asserts if the function output matches the expected result.
     }
 }
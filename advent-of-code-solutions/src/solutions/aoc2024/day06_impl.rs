@@ -1,181 +1,219 @@
-use super::day06::Day06;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::trace::Trace;
+use crate::utils::cycle;
+use crate::utils::grid::Grid;
+use crate::utils::point::{Direction, Point};
 use std::collections::HashSet;
 
-/// Struct to store the map details, including the guard's starting position,
-/// map size, and the locations of obstacles (`#`).
+/// Day 6: Guard Gallivant.
+#[derive(Default)]
+pub struct Day06;
+
+/// The map as a grid of obstacles, plus the guard's starting position and direction.
 #[derive(Debug)]
 struct MapData {
-    start_position: ((u8, u8), char), // ((x, y), direction)
-    size: (u8, u8),                   // Dimensions of the map (width, height)
-    obstacles: HashSet<(u8, u8)>,     // Set of obstacle coordinates
+    start_position: (Point<i32>, Direction), // (position, direction)
+    obstacles: Grid<bool>,                   // Whether a tile holds an obstacle (`#`)
 }
 
 impl Day06 {
     /// Parses the input map into a `MapData` structure.
-    /// Extracts the guard's starting position, map size, and obstacle positions.
-    fn parse_map(input: &str) -> MapData {
-        let mut start_position = None; // Placeholder for guard's start position
-        let mut obstacles = HashSet::new();
-
-        // Remove empty lines and prepare the input for parsing
-        let filtered_input: Vec<&str> = input
-            .lines()
-            .map(|line| line.trim()) // Trim whitespace from each line
-            .filter(|line| !line.is_empty()) // Skip empty lines
-            .collect();
-        let y_size = filtered_input.len() as u8; // Number of rows
-        let x_size = filtered_input.get(0).map_or(0, |row| row.len()) as u8; // Number of columns
-
-        // Iterate through the map and populate start position and obstacle set
-        for (y, row) in filtered_input.iter().enumerate() {
-            for (x, ch) in row.chars().enumerate() {
-                match ch {
-                    '^' | '>' | '<' | 'v' => {
-                        start_position = Some(((x as u8, y as u8), ch)) // Guard's starting point
-                    }
-                    '#' => {
-                        obstacles.insert((x as u8, y as u8)); // Add obstacle position
-                    }
-                    _ => {}
-                }
-            }
-        }
+    /// Extracts the guard's starting position and the obstacle positions.
+    fn parse_map(input: &str) -> Result<MapData, SolveError> {
+        let map = Grid::parse(input, Ok)?;
 
         // Ensure the guard's starting position was found in the input
-        MapData {
-            start_position: start_position.expect("No start position found in the map!"),
-            size: (x_size, y_size),
-            obstacles,
-        }
-    }
+        let start = map
+            .find(|&ch| Direction::from_arrow(ch).is_some())
+            .ok_or_else(|| SolveError::unsolvable("no guard position found in the map"))?;
+        let direction = Direction::from_arrow(map[start]).expect("the guard faces an arrow");
 
-    /// Rotates the guard's direction 90 degrees to the right.
-    fn rotate_right(direction: char) -> char {
-        match direction {
-            '^' => '>',
-            '>' => 'v',
-            'v' => '<',
-            '<' => '^',
-            _ => panic!("Invalid direction!"), // Should never occur
+        let mut obstacles = Grid::new(map.width(), map.height(), false);
+        for p in map.find_all(|&ch| ch == '#') {
+            obstacles[p] = true;
         }
+
+        Ok(MapData {
+            start_position: (start, direction),
+            obstacles,
+        })
     }
 
     /// Simulates the guard's movement across the map.
     ///
     /// - `start_position`: The initial position and direction of the guard.
-    /// - `obstacles`: Set of obstacle positions.
-    /// - `size`: Dimensions of the map (width, height).
+    /// - `obstacles`: Grid of obstacle positions.
     /// - `distinct_positions`: Tracks all visited positions (mutated in place).
-    /// - `detect_loop`: If true, detects loops instead of recording distinct positions.
+    /// - `trace`: Receives a frame after every move or turn of the guard.
     ///
     /// Returns `true` if a loop is detected, otherwise `false`.
+    /// Loops are detected, so the simulation always terminates.
     fn simulate_steps(
-        start_position: &((u8, u8), char),
-        obstacles: &HashSet<(u8, u8)>,
-        size: &(u8, u8),
-        distinct_positions: &mut HashSet<(u8, u8)>,
-        detect_loop: bool,
+        start_position: &(Point<i32>, Direction),
+        obstacles: &Grid<bool>,
+        distinct_positions: &mut HashSet<Point<i32>>,
+        trace: &mut Trace,
     ) -> bool {
-        let mut position = start_position.0; // Current position (x, y)
-        let mut direction = start_position.1; // Current direction ('^', '>', '<', 'v')
-        let x_size = size.0; // Map width
-        let y_size = size.1; // Map height
+        let (mut position, mut direction) = *start_position; // Current position and direction
         let mut visited = HashSet::new(); // Tracks visited states for loop detection
-
-        // Helper to calculate the next position based on direction
-        let next_position = |pos: (u8, u8), dir: char| -> (i16, i16) {
-            match dir {
-                '^' => (pos.0 as i16, pos.1 as i16 - 1), // Move up
-                '>' => (pos.0 as i16 + 1, pos.1 as i16), // Move right
-                'v' => (pos.0 as i16, pos.1 as i16 + 1), // Move down
-                '<' => (pos.0 as i16 - 1, pos.1 as i16), // Move left
-                _ => (pos.0 as i16, pos.1 as i16),       // No movement
-            }
-        };
+        trace.frame(0, || {
+            Self::render(obstacles, distinct_positions, (position, direction))
+        });
 
         // Main loop to simulate the guard's patrol
-        loop {
-            let new_position = next_position(position, direction);
-            // Check if the guard leaves the map boundaries
-            if new_position.0 < 0
-                || new_position.1 < 0
-                || new_position.0 >= x_size as i16
-                || new_position.1 >= y_size as i16
-            {
-                return false; // Guard has exited the map
-            }
-            let new_position = (new_position.0 as u8, new_position.1 as u8);
-
-            // Check if the next position is an obstacle
-            if obstacles.contains(&new_position) {
-                if detect_loop && visited.contains(&(new_position, direction)) {
-                    return true; // Loop detected
+        for step in 1.. {
+            let new_position = position + direction.into();
+            match obstacles.get(new_position) {
+                None => {
+                    trace.event(format_args!(
+                        "left the map at {},{}",
+                        position.x, position.y
+                    ));
+                    trace.frame(step, || {
+                        Self::render(obstacles, distinct_positions, (position, direction))
+                    });
+                    return false; // Guard has exited the map
+                }
+                Some(true) => {
+                    // The next position is an obstacle
+                    if !visited.insert((new_position, direction)) {
+                        trace.event(format_args!(
+                            "hit the obstacle at {},{} facing {:?} before: loop",
+                            new_position.x, new_position.y, direction
+                        ));
+                        return true; // Loop detected
+                    }
+                    direction = direction.turn_right(); // Turn right
+                    trace.event(format_args!(
+                        "turned {:?} at {},{}",
+                        direction, position.x, position.y
+                    ));
                 }
-                visited.insert((new_position, direction)); // Mark state as visited
-                direction = Self::rotate_right(direction); // Turn right
-            } else {
-                position = new_position; // Move forward
-                if !detect_loop {
+                Some(false) => {
+                    position = new_position; // Move forward
                     distinct_positions.insert(position); // Record visited position
                 }
             }
+            trace.frame(step, || {
+                Self::render(obstacles, distinct_positions, (position, direction))
+            });
+        }
+        unreachable!("the patrol ends by leaving the map or in a loop")
+    }
+
+    /// The guard's next position and direction, or `None` once the guard has left the map.
+    /// Leaving the map is final, so `None` is followed by `None` again.
+    fn next_state(
+        obstacles: &Grid<bool>,
+        state: &Option<(Point<i32>, Direction)>,
+    ) -> Option<(Point<i32>, Direction)> {
+        let (position, direction) = (*state)?;
+        let new_position = position + direction.into();
+        match obstacles.get(new_position)? {
+            true => Some((position, direction.turn_right())),
+            false => Some((new_position, direction)),
         }
     }
 
+    /// Whether the guard patrols in a loop. The states repeat either way, as leaving the
+    /// map repeats forever; a loop is a cycle of more than that one state. Brent's algorithm
+    /// finds it without remembering the states, which keeps the many probes of part 2 small.
+    fn is_loop(start_position: &(Point<i32>, Direction), obstacles: &Grid<bool>) -> bool {
+        let cycle = cycle::brent(Some(*start_position), |state| {
+            Self::next_state(obstacles, state)
+        });
+        cycle.length > 1
+    }
+
+    /// Draws the map with obstacles as `#`, visited positions as `X` and the guard as an
+    /// arrow, like the puzzle description does.
+    fn render(
+        obstacles: &Grid<bool>,
+        distinct_positions: &HashSet<Point<i32>>,
+        (guard, direction): (Point<i32>, Direction),
+    ) -> String {
+        let mut text = String::new();
+        for y in 0..obstacles.height() as i32 {
+            for x in 0..obstacles.width() as i32 {
+                let p = Point::new(x, y);
+                text.push(if p == guard {
+                    direction.arrow().unwrap_or('@')
+                } else if obstacles[p] {
+                    '#'
+                } else if distinct_positions.contains(&p) {
+                    'X'
+                } else {
+                    '.'
+                });
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl Puzzle for Day06 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 6;
+    const TITLE: &'static str = "Guard Gallivant";
+
     /// Solves Part 1 of the problem: calculate the number of distinct positions
     /// visited by the guard before leaving the map.
-    pub fn part_1(&self, input: &str) -> String {
-        let map_data = Self::parse_map(input); // Parse input map
-        let mut distinct_positions: HashSet<(u8, u8)> = HashSet::new();
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        self.trace_part_1(input, &mut Trace::disabled())
+    }
+
+    /// Records the guard's patrol of part 1, one frame per move or turn.
+    fn trace_part_1(&self, input: &str, trace: &mut Trace) -> Result<Answer, SolveError> {
+        let map_data = Self::parse_map(input)?; // Parse input map
+        let mut distinct_positions: HashSet<Point<i32>> = HashSet::new();
 
         // Initialize simulation with the guard's starting position
         distinct_positions.insert(map_data.start_position.0);
-        Self::simulate_steps(
+        let loop_detected = Self::simulate_steps(
             &map_data.start_position,
             &map_data.obstacles,
-            &map_data.size,
             &mut distinct_positions,
-            false, // Not detecting loops
+            trace,
         );
-        distinct_positions.len().to_string()
+        if loop_detected {
+            return Err(SolveError::unsolvable("the guard never leaves the map"));
+        }
+        Ok(distinct_positions.len().into())
     }
 
     /// Solves Part 2 of the problem: find the number of positions where adding
     /// an obstacle would trap the guard in a loop.
-    pub fn part_2(&self, input: &str) -> String {
-        let mut map_data = Self::parse_map(input); // Parse input map
-        let mut distinct_positions: HashSet<(u8, u8)> = HashSet::new();
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let mut map_data = Self::parse_map(input)?; // Parse input map
+        let mut distinct_positions: HashSet<Point<i32>> = HashSet::new();
 
-        // Record positions visited without detecting loops
+        // Record positions visited on the original patrol
         distinct_positions.insert(map_data.start_position.0);
-        Self::simulate_steps(
+        let loop_detected = Self::simulate_steps(
             &map_data.start_position,
             &map_data.obstacles,
-            &map_data.size,
             &mut distinct_positions,
-            false,
+            &mut Trace::disabled(),
         );
+        if loop_detected {
+            return Err(SolveError::unsolvable("the guard never leaves the map"));
+        }
 
         // Check each distinct position as a potential obstruction
         let result: usize = distinct_positions
             .iter()
             .filter(|&cor| {
-                let added = map_data.obstacles.insert(*cor); // Temporarily add obstacle
-                let loop_detected = Self::simulate_steps(
-                    &map_data.start_position,
-                    &map_data.obstacles,
-                    &map_data.size,
-                    &mut HashSet::new(),
-                    true, // Detect loops
-                );
+                let added = !std::mem::replace(&mut map_data.obstacles[*cor], true); // Temporarily add obstacle
+                let loop_detected = Self::is_loop(&map_data.start_position, &map_data.obstacles);
                 if added {
-                    map_data.obstacles.remove(cor); // Remove temporary obstacle
+                    map_data.obstacles[*cor] = false; // Remove temporary obstacle
                 }
                 loop_detected
             })
             .count();
 
-        result.to_string()
+        Ok(result.into())
     }
 }
 
@@ -184,13 +222,9 @@ mod test {
     /// Test for part 1 of Day06 for AoC 2024.
     /// Ensures correct calculation of the sum of first and last digits in each line.
     fn test_aoc2024_day06_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day06;
-        let day06 = Day06 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day06 = Day06;
         let input = r#"
 ....#.....
 .........#
@@ -203,20 +237,38 @@ mod test {
 #.........
 ......#...
         "#;
-        assert_eq!(day06.part_1(input), "41"); // Asserts if the function output matches the expected result.
+        assert_eq!(day06.part_1(input).unwrap(), "41"); // Asserts if the function output matches the expected result.
     }
 
     #[test]
     /// Test for part 2 of Day06 for AoC 2024.
     /// Verifies correct handling and sum of lines with spelled-out numbers.
     fn test_aoc2024_day06_part_2() {
+        use crate::aoc_solution::Puzzle;
+        use crate::solutions::aoc2024::Day06;
+        let day06 = Day06;
+        let input = r#"
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+        "#;
+        assert_eq!(day06.part_2(input).unwrap(), "6"); // Asserts if the function output matches the expected result.
+    }
+
+    #[test]
+    /// The trace of part 1 follows the guard from the start until it leaves the map.
+    fn test_aoc2024_day06_trace() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day06;
-        let day06 = Day06 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        use crate::trace::Trace;
+        let day06 = Day06;
         let input = r#"
 ....#.....
 .........#
@@ -229,6 +281,15 @@ mod test {
 #.........
 ......#...
         "#;
-        assert_eq!(day06.part_2(input), "6"); // Asserts if the function output matches the expected result.
+        let mut trace = Trace::new(usize::MAX);
+        assert_eq!(day06.trace_part_1(input, &mut trace).unwrap(), "41");
+
+        let frames = trace.frames();
+        assert_eq!(frames[0].grid.join("\n"), input.trim());
+        assert_eq!(frames[1].grid[5], "....^.....");
+        assert_eq!(frames[6].events, ["turned E at 4,1"]);
+        let last = frames.last().unwrap();
+        assert_eq!(last.events, ["left the map at 7,9"]);
+        assert_eq!(last.grid.concat().matches(['X', 'v']).count(), 41);
     }
 }
@@ -1,85 +1,152 @@
-use super::day07::Day07;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::explain::Explain;
+use crate::utils::parse;
+use std::fmt;
+
+/// Day 7: Bridge Repair.
+#[derive(Default)]
+pub struct Day07;
 
 impl Day07 {
     /// Parses the input into a vector of tuples containing the target value (u64) and a vector of numbers (u16).
-    fn parse_test_values(input: &str) -> Result<Vec<(u64, Vec<u16>)>, String> {
-        input
-            .lines()
-            .map(str::trim) // Remove leading and trailing whitespace from each line.
-            .filter(|line| !line.is_empty()) // Skip empty lines.
+    fn parse_test_values(input: &str) -> Result<Vec<(u64, Vec<u16>)>, SolveError> {
+        parse::lines(input)
             .map(|line| {
-                let mut parts = line.split(':'); // Split each line into the target and number list.
-
-                // Parse the target value (to the left of the colon).
-                let key = parts
-                    .next()?
-                    .trim()
-                    .parse::<u64>() // Parse as u64 since target values can be large.
-                    .ok()?;
-
-                // Parse the list of numbers (to the right of the colon).
-                let values = parts
-                    .next()?
-                    .trim()
-                    .split_whitespace()
-                    .map(|v| v.parse::<u16>().ok()) // Parse as u16; these values are smaller.
-                    .collect::<Option<Vec<u16>>>()?;
-
-                Some((key, values)) // Return the tuple if successful.
+                // Split each line into the target and number list.
+                let (key, values) = line.split_once(":", "a target value followed by ':'")?;
+                // Parse the target value as u64 since target values can be large.
+                let key = line.number::<u64>(key, "a target value")?;
+                // Parse the list of numbers as u16; these values are smaller.
+                let values = line.list::<u16>(values, "a number")?;
+                if values.is_empty() {
+                    return Err(SolveError::parse(
+                        line.number,
+                        line.text.len() + 1,
+                        "expected at least one number after ':'",
+                    ));
+                }
+                Ok((key, values)) // Return the tuple if successful.
             })
-            .collect::<Option<Vec<(u64, Vec<u16>)>>>() // Collect all parsed lines into a vector.
-            .ok_or_else(|| "Failed to parse test values".to_string()) // Return an error if parsing fails.
+            .collect() // Collect all parsed lines into a vector.
     }
 
     /// Calculates the total calibration result by evaluating all possible operator combinations.
+    /// Records in `explain` which equations are solvable and with which operators.
     fn calculate_total_calibration(
         test_values: Vec<(u64, Vec<u16>)>, // Parsed test values as input.
         base: u32, // The number of operators (e.g., 2 for Part 1, 3 for Part 2).
-    ) -> String {
-        let mut total_calibration_result = 0;
+        explain: &mut Explain,
+    ) -> Result<Answer, SolveError> {
+        let mut total_calibration_result: u64 = 0;
 
         // Iterate over each (target value, numbers list) tuple.
         for (result, numbers) in test_values {
             // Calculate the number of operator combinations (base^(n-1)).
-            let num_combinations = base.pow(u32::try_from(numbers.len() - 1).unwrap());
+            let num_combinations = u32::try_from(numbers.len() - 1)
+                .ok()
+                .and_then(|exponent| base.checked_pow(exponent))
+                .ok_or_else(|| {
+                    SolveError::unsupported(format!(
+                        "too many numbers ({}) in one equation",
+                        numbers.len()
+                    ))
+                })?;
+            let mut solution = None;
             for mut i in 0..num_combinations {
-                let mut numbers_iter = numbers.iter(); // Create an iterator over the numbers.
-                let mut test_result = *numbers_iter.next().unwrap() as u64; // Start with the first number.
-
-                // Apply operators between numbers.
-                for n in numbers_iter {
-                    match i % base {
-                        0 => test_result += *n as u64,
-                        1 => test_result *= *n as u64,
-                        3 => {
-                            test_result *= 10_u64.pow(n.ilog10() + 1);
-                            test_result += *n as u64;
+                let combination = i;
+                let (first, rest) = numbers.split_first().expect("parser rejects empty lists");
+                let mut test_result = Some(*first as u64); // Start with the first number.
+
+                // Apply operators between numbers. A result that overflows can never match.
+                for n in rest {
+                    let n64 = *n as u64;
+                    test_result = test_result.and_then(|value| match i % base {
+                        0 => value.checked_add(n64),
+                        1 => value.checked_mul(n64),
+                        _ => {
+                            let digits = n.checked_ilog10().unwrap_or(0) + 1;
+                            value.checked_mul(10_u64.pow(digits))?.checked_add(n64)
                         }
-                        _ => {}
-                    }
+                    });
                     i /= base; // Move to the next operator combination.
                 }
 
                 // Check if the calculated result matches the target.
-                if test_result == result {
-                    total_calibration_result += result; // Add the target value to the total if it matches.
+                if test_result == Some(result) {
+                    // Add the target value to the total if it matches.
+                    total_calibration_result = total_calibration_result
+                        .checked_add(result)
+                        .ok_or_else(|| {
+                            SolveError::unsolvable("the calibration result overflows a u64")
+                        })?;
+                    solution = Some(combination);
                     break; // No need to try further combinations for this target.
                 }
             }
+
+            match solution {
+                Some(operators) => explain.fact(
+                    result,
+                    Equation {
+                        numbers: &numbers,
+                        operators,
+                        base,
+                    },
+                ),
+                None => explain.fact(result, "not solvable"),
+            }
         }
-        total_calibration_result.to_string() // Return the total as a string.
+        Ok(total_calibration_result.into()) // Return the total as an Answer.
     }
+}
+
+/// The numbers of an equation with the operators of a combination between them,
+/// written like `81 + 40 * 27`.
+struct Equation<'a> {
+    numbers: &'a [u16],
+    /// The combination index; each digit in `base` selects one operator.
+    operators: u32,
+    base: u32,
+}
+
+impl fmt::Display for Equation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut operators = self.operators;
+        write!(f, "{}", self.numbers[0])?;
+        for n in &self.numbers[1..] {
+            let operator = ["+", "*", "||"][(operators % self.base) as usize];
+            write!(f, " {} {}", operator, n)?;
+            operators /= self.base;
+        }
+        Ok(())
+    }
+}
+
+impl Puzzle for Day07 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 7;
+    const TITLE: &'static str = "Bridge Repair";
 
     /// Part 1: Solve using only addition (+) and multiplication (*).
-    pub fn part_1(&self, input: &str) -> String {
-        let test_values = Self::parse_test_values(input).unwrap(); // Parse the input.
-        Self::calculate_total_calibration(test_values, 2)
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        self.explain_part_1(input, &mut Explain::disabled())
+    }
+
+    /// Explains Part 1 by listing the operators that solve each equation.
+    fn explain_part_1(&self, input: &str, explain: &mut Explain) -> Result<Answer, SolveError> {
+        let test_values = Self::parse_test_values(input)?; // Parse the input.
+        Self::calculate_total_calibration(test_values, 2, explain)
     }
 
     /// Part 2: Solve using addition (+), multiplication (*), and concatenation (||).
-    pub fn part_2(&self, input: &str) -> String {
-        let test_values = Self::parse_test_values(input).unwrap(); // Parse the input.
-        Self::calculate_total_calibration(test_values, 3)
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        self.explain_part_2(input, &mut Explain::disabled())
+    }
+
+    /// Explains Part 2 by listing the operators that solve each equation.
+    fn explain_part_2(&self, input: &str, explain: &mut Explain) -> Result<Answer, SolveError> {
+        let test_values = Self::parse_test_values(input)?; // Parse the input.
+        Self::calculate_total_calibration(test_values, 3, explain)
     }
 }
 
@@ -87,13 +154,9 @@ mod test {
     #[test]
     /// Test for Part 1 of Day 7. Validates calculation of target sums for addition and multiplication operators.
     fn test_aoc2024_day07_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day07;
-        let day07 = Day07 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day07 = Day07;
         let input = r#"
 190: 10 19
 3267: 81 40 27
@@ -105,19 +168,15 @@ mod test {
 21037: 9 7 18 13
 292: 11 6 16 20
         "#;
-        assert_eq!(day07.part_1(input), "3749"); // Check against the expected result for Part 1.
+        assert_eq!(day07.part_1(input).unwrap(), "3749"); // Check against the expected result for Part 1.
     }
 
     #[test]
     /// Test for Part 2 of Day 7. Validates calculation with the concatenation operator.
     fn test_aoc2024_day07_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day07;
-        let day07 = Day07 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day07 = Day07;
         let input = r#"
 190: 10 19
 3267: 81 40 27
@@ -129,6 +188,21 @@ mod test {
 21037: 9 7 18 13
 292: 11 6 16 20
         "#;
-        assert_eq!(day07.part_2(input), "11387"); // Check against the expected result for Part 2.
+        assert_eq!(day07.part_2(input).unwrap(), "11387"); // Check against the expected result for Part 2.
+    }
+
+    #[test]
+    /// The explanation lists every equation with the operators that solve it.
+    fn test_aoc2024_day07_explain() {
+        use crate::aoc_solution::Puzzle;
+        use crate::explain::Explain;
+        use crate::solutions::aoc2024::Day07;
+        let input = "190: 10 19\n83: 17 5\n7290: 6 8 6 15\n";
+        let mut explain = Explain::new(10);
+        assert_eq!(Day07.explain_part_2(input, &mut explain).unwrap(), "7480");
+        assert_eq!(
+            explain.to_text(),
+            "190: 10 * 19\n83: not solvable\n7290: 6 * 8 || 6 * 15\n"
+        );
     }
 }
@@ -1,48 +1,39 @@
-use super::day08::Day08;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::grid::Grid;
+use crate::utils::math;
 use std::collections::{HashMap, HashSet};
 
+/// Day 8: Resonant Collinearity.
+#[derive(Default)]
+pub struct Day08;
+
+/// Antenna locations grouped by frequency.
+type Antennas = HashMap<char, Vec<(i32, i32)>>;
+
 impl Day08 {
     /// Parses a 2D grid string into a `HashMap` where keys are the antenna frequencies (characters),
     /// and values are `Vec` of (x, y) coordinates representing the locations of those antennas.
     /// The function also computes and returns the width and height of the grid.
-    pub fn parse_antenna_grid(grid: &str) -> (i8, i8, HashMap<char, Vec<(i8, i8)>>) {
-        let mut result: HashMap<char, Vec<(i8, i8)>> = HashMap::new();
-
-        // Width and height are assumed to be the same and based on the number of non-empty lines.
-        let width = grid.lines().filter(|s| !s.trim().is_empty()).count() as i8;
-        let height = width;
-
-        // Process only the non-empty lines, trimming whitespace.
-        let lines = grid
-            .lines()
-            .filter(|s| !s.trim().is_empty())
-            .map(|line| line.trim());
-
-        // Iterate through each line (y-coordinate) and each character in the line (x-coordinate).
-        for (y, line) in lines.enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                // Skip empty cells represented by `.`.
-                if c != '.' {
-                    // Add the (x, y) location of the antenna to the hashmap under its frequency key.
-                    result
-                        .entry(c)
-                        .or_insert_with(Vec::new)
-                        .push((x as i8, y as i8));
-                }
-            }
+    pub fn parse_antenna_grid(grid: &str) -> Result<(i32, i32, Antennas), SolveError> {
+        let grid = Grid::parse(grid, Ok)?;
+        let mut result: HashMap<char, Vec<(i32, i32)>> = HashMap::new();
+
+        // Add the (x, y) location of every antenna under its frequency; `.` is an empty cell.
+        for (p, &c) in grid.iter().filter(|(_, &c)| c != '.') {
+            result.entry(c).or_default().push((p.x, p.y));
         }
 
-        (width, height, result)
+        Ok((grid.width() as i32, grid.height() as i32, result))
     }
 
     /// Computes and inserts antinodes for a pair of antennas based on the Part 1 resonance rule.
     /// An antinode is valid if one antenna is twice as far as the other from the antinode position.
     fn find_antinodes(
-        height: i8,
-        width: i8,
-        loc_0: (i8, i8),
-        loc_1: (i8, i8),
-        antinodes: &mut HashSet<(i8, i8)>,
+        height: i32,
+        width: i32,
+        loc_0: (i32, i32),
+        loc_1: (i32, i32),
+        antinodes: &mut HashSet<(i32, i32)>,
     ) {
         // For each antenna pair, test both combinations: loc_0 closer and loc_1 farther, and vice versa.
         for &(r0, c0, r1, c1) in &[
@@ -54,52 +45,26 @@ impl Day08 {
             let ac = 2 * c0 - c1;
 
             // Ensure the computed antinode position is within grid bounds.
-            if ar >= 0 && ar < height && ac >= 0 && ac < width {
+            if ar >= 0 && ar < width && ac >= 0 && ac < height {
                 antinodes.insert((ar, ac)); // Add the valid antinode to the set.
             }
         }
     }
 
-    /// Solves Part 1 of the puzzle: Counts unique antinode positions based on Part 1 resonance rules.
-    pub fn part_1(&self, input: &str) -> String {
-        // Parse the grid into dimensions and antenna locations.
-        let (height, width, antenna_locations) = Self::parse_antenna_grid(input);
-        let mut antinodes = HashSet::new(); // Set to store unique antinode positions.
-
-        // Process each frequency group (antennas with the same character).
-        antenna_locations.into_values().for_each(|locations| {
-            locations
-                .iter()
-                .enumerate()
-                .flat_map(|(i, &loc_0)| {
-                    locations
-                        .iter()
-                        .skip(i + 1)
-                        .map(move |&loc_1| (loc_0, loc_1))
-                })
-                .for_each(|(loc_0, loc_1)| {
-                    Self::find_antinodes(height, width, loc_0, loc_1, &mut antinodes);
-                });
-        });
-
-        // Return the count of unique antinodes as a string.
-        antinodes.len().to_string()
-    }
-
     /// Computes antinodes based on the Part 2 resonance rule, which considers collinear positions.
     fn compute_antinodes(
-        height: i8,
-        width: i8,
-        loc_0: (i8, i8),
-        loc_1: (i8, i8),
-        antinodes: &mut HashSet<(i8, i8)>,
+        height: i32,
+        width: i32,
+        loc_0: (i32, i32),
+        loc_1: (i32, i32),
+        antinodes: &mut HashSet<(i32, i32)>,
     ) {
         // Calculate the directional vector (dr, dc) between the two antennas.
         let mut dr = loc_1.0 - loc_0.0;
         let mut dc = loc_1.1 - loc_0.1;
 
         // Reduce the direction vector to its simplest form using the greatest common divisor (GCD).
-        let gcd = Self::gcd(dr.abs(), dc.abs());
+        let gcd = math::gcd(dr, dc);
         dr /= gcd;
         dc /= gcd;
 
@@ -108,27 +73,50 @@ impl Day08 {
             let (mut r, mut c) = loc_0;
 
             // Step in the direction until moving out of grid bounds.
-            while r >= 0 && r < height && c >= 0 && c < width {
+            while r >= 0 && r < width && c >= 0 && c < height {
                 antinodes.insert((r, c)); // Add each valid position to the set.
                 r += dir * dr;
                 c += dir * dc;
             }
         }
     }
+}
 
-    /// Helper function to compute the greatest common divisor (GCD) using recursion.
-    fn gcd(a: i8, b: i8) -> i8 {
-        if b == 0 {
-            a
-        } else {
-            Self::gcd(b, a % b)
-        }
+impl Puzzle for Day08 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 8;
+    const TITLE: &'static str = "Resonant Collinearity";
+
+    /// Solves Part 1 of the puzzle: Counts unique antinode positions based on Part 1 resonance rules.
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        // Parse the grid into dimensions and antenna locations.
+        let (width, height, antenna_locations) = Self::parse_antenna_grid(input)?;
+        let mut antinodes = HashSet::new(); // Set to store unique antinode positions.
+
+        // Process each frequency group (antennas with the same character).
+        antenna_locations.into_values().for_each(|locations| {
+            locations
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &loc_0)| {
+                    locations
+                        .iter()
+                        .skip(i + 1)
+                        .map(move |&loc_1| (loc_0, loc_1))
+                })
+                .for_each(|(loc_0, loc_1)| {
+                    Self::find_antinodes(height, width, loc_0, loc_1, &mut antinodes);
+                });
+        });
+
+        // Return the count of unique antinodes as an Answer.
+        Ok(antinodes.len().into())
     }
 
     /// Solves Part 2 of the puzzle: Counts unique antinode positions based on the updated rules.
-    pub fn part_2(&self, input: &str) -> String {
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
         // Parse the grid into dimensions and antenna locations.
-        let (height, width, antenna_locations) = Self::parse_antenna_grid(input);
+        let (width, height, antenna_locations) = Self::parse_antenna_grid(input)?;
         let mut antinodes = HashSet::new(); // Set to store unique antinode positions.
 
         // Process each frequency group (antennas with the same character).
@@ -147,8 +135,8 @@ impl Day08 {
                 });
         });
 
-        // Return the count of unique antinodes as a string.
-        antinodes.len().to_string()
+        // Return the count of unique antinodes as an Answer.
+        Ok(antinodes.len().into())
     }
 }
 
@@ -156,13 +144,9 @@ mod test {
     /// Test for Part 1 of Day08.
     #[test]
     fn test_aoc2024_day08_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day08;
-        let day08 = Day08 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day08 = Day08;
         let input = r#"
 ............
 ........0...
@@ -177,19 +161,15 @@ mod test {
 ............
 ............
         "#;
-        assert_eq!(day08.part_1(input), "14"); // Verify the expected result for Part 1.
+        assert_eq!(day08.part_1(input).unwrap(), "14"); // Verify the expected result for Part 1.
     }
 
     /// Test for Part 2 of Day08.
     #[test]
     fn test_aoc2024_day08_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day08;
-        let day08 = Day08 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day08 = Day08;
         let input = r#"
 ............
 ........0...
@@ -204,6 +184,6 @@ mod test {
 ............
 ............
         "#;
-        assert_eq!(day08.part_2(input), "34"); // Verify the expected result for Part 2.
+        assert_eq!(day08.part_2(input).unwrap(), "34"); // Verify the expected result for Part 2.
     }
 }
@@ -1,17 +1,36 @@
-use super::day09::Day09;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::parse;
+
+/// Day 9: Disk Fragmenter.
+#[derive(Default)]
+pub struct Day09;
 
 impl Day09 {
+    /// Reads the digits of the disk map, rejecting any other non-whitespace character.
+    fn parse_digits(input: &str) -> Result<Vec<usize>, SolveError> {
+        let mut digits = Vec::with_capacity(input.len());
+        for line in parse::lines(input) {
+            for (column, c) in line.text.char_indices() {
+                match c.to_digit(10) {
+                    Some(n) => digits.push(n as usize),
+                    None if c.is_whitespace() => {}
+                    None => return Err(line.error_at(&line.text[column..], "expected a digit")),
+                }
+            }
+        }
+        Ok(digits)
+    }
+
     /// Parses the input for Part 1, returning a vector of blocks as (empty: bool, id: usize).
     /// - `empty`: Indicates if the block is free space (`true`) or a file (`false`).
     /// - `id`: The file ID, if the block is part of a file.
-    fn parse_input_for_part_one(input: &str) -> Vec<(bool, usize)> {
+    fn parse_input_for_part_one(input: &str) -> Result<Vec<(bool, usize)>, SolveError> {
         let mut id = 0; // File ID counter
         let mut free = false; // Tracks whether the current segment represents free space
         let mut filesystem = Vec::new(); // Holds the parsed representation of the filesystem
 
         // Process each character in the input string
-        for c in input.chars().filter(|c| c.is_ascii_digit()) {
-            let n = c.to_digit(10).unwrap() as usize; // Convert character to a number
+        for n in Self::parse_digits(input)? {
             match free {
                 true => filesystem.extend((0..n).map(|_| (true, 0))), // Add `n` free space blocks
                 false => {
@@ -21,13 +40,16 @@ impl Day09 {
             }
             free = !free; // Toggle between free space and file segments
         }
-        filesystem
+        Ok(filesystem)
     }
 
     /// Parses the input for Part 2, returning a vector of files and free spaces.
     /// - Files are represented as `(id, position, length)`.
     /// - Free spaces are represented as `(position, length)`.
-    fn parse_input_for_part_two(input: &str) -> (Vec<(usize, usize, usize)>, Vec<(usize, usize)>) {
+    #[allow(clippy::type_complexity)]
+    fn parse_input_for_part_two(
+        input: &str,
+    ) -> Result<(Vec<(usize, usize, usize)>, Vec<(usize, usize)>), SolveError> {
         let mut id = 0; // File ID counter
         let mut pos = 0; // Current position on the disk
         let mut free = false; // Tracks whether the current segment represents free space
@@ -35,8 +57,7 @@ impl Day09 {
         let mut free_space = Vec::with_capacity(input.len() / 2); // Holds free space metadata
 
         // Process each character in the input string
-        for c in input.chars().filter(|c| c.is_ascii_digit()) {
-            let len = c.to_digit(10).unwrap() as usize; // Convert character to a number
+        for len in Self::parse_digits(input)? {
             match free {
                 true => free_space.push((pos, len)), // Record free space at the current position
                 false => {
@@ -47,15 +68,21 @@ impl Day09 {
             free = !free; // Toggle between free space and file segments
             pos += len; // Move the position forward by the segment length
         }
-        (files, free_space)
+        Ok((files, free_space))
     }
+}
+
+impl Puzzle for Day09 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 9;
+    const TITLE: &'static str = "Disk Fragmenter";
 
     /// Solves Part 1 of the problem.
     /// - Moves blocks one at a time to compact the disk and calculates the resulting checksum.
-    pub fn part_1(&self, input: &str) -> String {
-        let mut filesystem = Self::parse_input_for_part_one(input); // Parse input into blocks
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let mut filesystem = Self::parse_input_for_part_one(input)?; // Parse input into blocks
         let mut front = 0; // Pointer to the leftmost block
-        let mut back = filesystem.len() - 1; // Pointer to the rightmost block
+        let mut back = filesystem.len().saturating_sub(1); // Pointer to the rightmost block
 
         // Compact the filesystem by moving file blocks to the leftmost free space
         while front < back {
@@ -86,13 +113,13 @@ impl Day09 {
             })
             .sum(); // Sum the products
 
-        checksum.to_string()
+        Ok(checksum.into())
     }
 
     /// Solves Part 2 of the problem.
     /// - Moves whole files to the leftmost free space that can accommodate them and calculates the checksum.
-    pub fn part_2(&self, input: &str) -> String {
-        let (mut files, mut free_space) = Self::parse_input_for_part_two(input); // Parse input into files and free spaces
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let (mut files, mut free_space) = Self::parse_input_for_part_two(input)?; // Parse input into files and free spaces
 
         // Iterate over files in reverse order (starting with the largest ID)
         for file in files.iter_mut().rev() {
@@ -121,7 +148,7 @@ impl Day09 {
             })
             .sum(); // Sum the checksums for all files
 
-        answer.to_string()
+        Ok(answer.into())
     }
 }
 
@@ -130,33 +157,25 @@ mod test {
     /// Test for part 1 of Day09 for AoC 2024.
     /// Ensures correct calculation of the checksum after compacting the filesystem by moving blocks.
     fn test_aoc2024_day09_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day09;
-        let day09 = Day09 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day09 = Day09;
         let input = r#"
 2333133121414131402
 "#;
-        assert_eq!(day09.part_1(input), "1928"); // Expected result for part 1
+        assert_eq!(day09.part_1(input).unwrap(), "1928"); // Expected result for part 1
     }
 
     #[test]
     /// Test for part 2 of Day09 for AoC 2024.
     /// Verifies correct checksum calculation after compacting by moving whole files.
     fn test_aoc2024_day09_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day09;
-        let day09 = Day09 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day09 = Day09;
         let input = r#"
 2333133121414131402
 "#;
-        assert_eq!(day09.part_2(input), "2858"); // Expected result for part 2
+        assert_eq!(day09.part_2(input).unwrap(), "2858"); // Expected result for part 2
     }
 }
@@ -1,24 +1,30 @@
-use super::day11::Day11;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::parse;
 use std::collections::HashMap;
 
+/// Day 11: Plutonian Pebbles.
+#[derive(Default)]
+pub struct Day11;
+
 impl Day11 {
     /// Parses a string input into a vector of unsigned 64-bit integers (`u64`).
     /// This function handles:
     /// - Splitting the input string by whitespace.
     /// - Parsing each segment into a `u64`.
-    /// - Ignoring any invalid entries (e.g., non-numeric strings).
-    fn parse_numbers(input: &str) -> Vec<u64> {
-        input
-            .split_whitespace() // Split the string into segments based on whitespace.
-            .filter_map(|s| s.parse::<u64>().ok()) // Try parsing each segment into a `u64`. Skip errors.
-            .collect() // Collect the successfully parsed numbers into a vector.
+    /// - Reporting the position of any invalid entry (e.g., non-numeric strings).
+    fn parse_numbers(input: &str) -> Result<Vec<u64>, SolveError> {
+        parse::lines(input)
+            .map(|line| line.list(line.text, "a stone number"))
+            .collect::<Result<Vec<Vec<u64>>, _>>()
+            .map(|lines| lines.concat()) // Join the stones of all lines.
     }
 
     /// Simulates the transformation of stones for a specified number of iterations (blinks).
     /// - `initial_stones`: The initial sequence of numbers on the stones.
     /// - `blinks`: The number of transformation iterations to perform.
+    ///
     /// Returns the total number of stones after all blinks.
-    fn simulate_blinks(initial_stones: &Vec<u64>, blinks: usize) -> u64 {
+    fn simulate_blinks(initial_stones: &[u64], blinks: usize) -> Result<u64, SolveError> {
         let mut stones = Vec::with_capacity(5000); // Tracks transformations between stone indices.
         let mut indices = HashMap::with_capacity(5000); // Maps stone numbers to indices.
         let mut todo = Vec::new(); // Holds new stone numbers to process in the current blink.
@@ -60,7 +66,13 @@ impl Day11 {
                         (index_of(number / power), index_of(number % power))
                     } else {
                         // Rule 3: Multiply other numbers by 2024.
-                        (index_of(number * 2024), usize::MAX)
+                        let product = number.checked_mul(2024).ok_or_else(|| {
+                            SolveError::unsupported(format!(
+                                "stone {} grows beyond a u64 when multiplied by 2024",
+                                number
+                            ))
+                        })?;
+                        (index_of(product), usize::MAX)
                     }
                 };
 
@@ -78,21 +90,27 @@ impl Day11 {
             current = next; // Move to the next iteration.
         }
 
-        current.iter().sum() // Sum all stone counts to get the total.
+        Ok(current.iter().sum()) // Sum all stone counts to get the total.
     }
+}
+
+impl Puzzle for Day11 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 11;
+    const TITLE: &'static str = "Plutonian Pebbles";
 
     /// Solves part 1 of the puzzle by simulating 25 blinks.
     /// Parses the input, simulates the transformations, and returns the result.
-    pub fn part_1(&self, input: &str) -> String {
-        let stones = Self::parse_numbers(input); // Parse the initial stones.
-        Self::simulate_blinks(&stones, 25).to_string() // Simulate and convert the result to a string.
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let stones = Self::parse_numbers(input)?; // Parse the initial stones.
+        Self::simulate_blinks(&stones, 25).map(Answer::from) // Simulate and convert the result to an Answer.
     }
 
     /// Solves part 2 of the puzzle by simulating 75 blinks.
     /// Parses the input, simulates the transformations, and returns the result.
-    pub fn part_2(&self, input: &str) -> String {
-        let stones = Self::parse_numbers(input); // Parse the initial stones.
-        Self::simulate_blinks(&stones, 75).to_string() // Simulate and convert the result to a string.
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let stones = Self::parse_numbers(input)?; // Parse the initial stones.
+        Self::simulate_blinks(&stones, 75).map(Answer::from) // Simulate and convert the result to an Answer.
     }
 }
 
@@ -102,35 +120,27 @@ mod test {
     /// Test for part 1 of Day 11.
     /// Checks if the correct number of stones is computed after 25 blinks.
     fn test_aoc2024_day11_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day11; // Import the solution module.
-        let day11 = Day11 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day11 = Day11;
         let input = r#"
 125 17
         "#;
         // Expected result after 25 blinks is 55312.
-        assert_eq!(day11.part_1(input), "55312");
+        assert_eq!(day11.part_1(input).unwrap(), "55312");
     }
 
     #[test]
     /// Test for part 2 of Day 11.
     /// Verifies the number of stones after 75 blinks.
     fn test_aoc2024_day11_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day11; // Import the solution module.
-        let day11 = Day11 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day11 = Day11;
         let input = r#"
 125 17
         "#;
         // Expected result after 75 blinks is 65601038650482.
-        assert_eq!(day11.part_2(input), "65601038650482");
+        assert_eq!(day11.part_2(input).unwrap(), "65601038650482");
     }
 }
@@ -1,55 +1,47 @@
-use super::day13::Day13;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::explain::Explain;
+use crate::utils::math;
+use crate::utils::parse::{self, Line};
+
+/// Day 13: Claw Contraption.
+#[derive(Default)]
+pub struct Day13;
+
+/// A claw machine as ((dx_a, dy_a), (dx_b, dy_b), (px, py)).
+type Machine = ((i64, i64), (i64, i64), (i64, i64));
 
 impl Day13 {
+    /// Parses a line like `Button A: X+94, Y+34` or `Prize: X=8400, Y=5400` that must
+    /// start with `prefix` into its two coordinates.
+    fn parse_coordinates(line: &Line, prefix: &str) -> Result<(i64, i64), SolveError> {
+        let coordinates = line.value_of(prefix)?;
+        match line.signed::<i64>()?[..] {
+            [x, y] => Ok((x, y)),
+            _ => Err(line.error_at(coordinates, "expected two coordinates like X+94, Y+34")),
+        }
+    }
+
     /// Parses the input string into the machines format.
     /// Each machine is represented as ((dx_a, dy_a), (dx_b, dy_b), (px, py)).
     /// - dx_a, dy_a: Movement caused by pressing button A.
     /// - dx_b, dy_b: Movement caused by pressing button B.
     /// - px, py: Coordinates of the prize.
-    fn parse_input(input: &str) -> Vec<((i32, i32), (i32, i32), (i64, i64))> {
-        let mut machines = Vec::new();
-
-        // Split the input into blocks for each machine
-        for block in input.split("\n\n") {
-            // Initialize variables to store parsed values
-            let mut dx_a = 0;
-            let mut dy_a = 0;
-            let mut dx_b = 0;
-            let mut dy_b = 0;
-            let mut px = 0;
-            let mut py = 0;
-
-            // Parse each line within a block
-            for line in block.lines() {
-                if let Some(rest) = line.strip_prefix("Button A: x+") {
-                    // Parse Button A's movement
-                    let parts: Vec<&str> = rest.split(", y+").collect();
-                    if parts.len() == 2 {
-                        dx_a = parts[0].parse::<i32>().unwrap();
-                        dy_a = parts[1].parse::<i32>().unwrap();
-                    }
-                } else if let Some(rest) = line.strip_prefix("Button B: x+") {
-                    // Parse Button B's movement
-                    let parts: Vec<&str> = rest.split(", y+").collect();
-                    if parts.len() == 2 {
-                        dx_b = parts[0].parse::<i32>().unwrap();
-                        dy_b = parts[1].parse::<i32>().unwrap();
-                    }
-                } else if let Some(rest) = line.strip_prefix("Prize: x=") {
-                    // Parse Prize's coordinates
-                    let parts: Vec<&str> = rest.split(", y=").collect();
-                    if parts.len() == 2 {
-                        px = parts[0].parse::<i64>().unwrap();
-                        py = parts[1].parse::<i64>().unwrap();
-                    }
-                }
-            }
-
-            // Store the parsed data for this machine
-            machines.push(((dx_a, dy_a), (dx_b, dy_b), (px, py)));
-        }
-
-        machines
+    fn parse_input(input: &str) -> Result<Vec<Machine>, SolveError> {
+        parse::sections(input)
+            .iter()
+            .map(|section| {
+                // The three lines of a machine must appear in this order
+                let lines: Vec<Line> = section.lines().collect();
+                let [a, b, prize] = &lines[..] else {
+                    return Err(section.error("expected two button lines and a prize line"));
+                };
+                Ok((
+                    Self::parse_coordinates(a, "Button A:")?,
+                    Self::parse_coordinates(b, "Button B:")?,
+                    Self::parse_coordinates(prize, "Prize:")?,
+                ))
+            })
+            .collect()
     }
 
     /// Solves the system of linear equations for a single claw machine.
@@ -62,77 +54,104 @@ impl Day13 {
     /// - (b_x, b_y): Movement caused by pressing button B.
     /// - (x, y): Prize coordinates.
     ///
-    /// Returns (a, b) if a valid solution exists within the range [0, 100].
+    /// Returns (a, b) if a valid non-negative solution exists.
     /// Returns (0, 0) otherwise.
-    fn solve(x: i64, y: i64, a_x: i32, a_y: i32, b_x: i32, b_y: i32) -> (i64, i64) {
-        // Calculate the determinant of the system
-        let denom = a_x as i64 * b_y as i64 - a_y as i64 * b_x as i64;
-        let num_a = x * b_y as i64 - y * b_x as i64;
-        let num_b = y * a_x as i64 - x * a_y as i64;
-
-        // If determinant is zero, the system has no solution
-        if denom == 0 {
-            return (0, 0);
-        }
+    fn solve(x: i64, y: i64, a_x: i64, a_y: i64, b_x: i64, b_y: i64) -> (i64, i64) {
+        // i128 keeps the products of the elimination from overflowing
+        let matrix = vec![
+            vec![a_x as i128, b_x as i128],
+            vec![a_y as i128, b_y as i128],
+        ];
+        let Some(solution) = math::solve_linear(&matrix, &[x as i128, y as i128]) else {
+            return (0, 0); // The buttons move in the same direction
+        };
 
-        // Check if the solution is an integer
-        if num_b % denom != 0 || num_a % denom != 0 {
-            return (0, 0);
+        // Only whole, non-negative numbers of presses count
+        match solution[..] {
+            [a, b] if a.is_integer() && b.is_integer() => {
+                match (i64::try_from(a.to_integer()), i64::try_from(b.to_integer())) {
+                    (Ok(a), Ok(b)) if a >= 0 && b >= 0 => (a, b),
+                    _ => (0, 0),
+                }
+            }
+            _ => (0, 0),
         }
+    }
+
+    /// Sums the cost of all machines whose solution satisfies `accept`.
+    /// Records the button presses of every machine in `explain`.
+    fn total_cost(
+        machines: &[Machine],
+        offset: i64,
+        accept: impl Fn(i64, i64) -> bool,
+        explain: &mut Explain,
+    ) -> Result<Answer, SolveError> {
+        let mut total_cost = 0i64;
 
-        let a = num_a / denom;
-        let b = num_b / denom;
+        for (index, &((dx_a, dy_a), (dx_b, dy_b), (px, py))) in machines.iter().enumerate() {
+            let (a, b) = Self::solve(px + offset, py + offset, dx_a, dy_a, dx_b, dy_b);
+            // Add the cost if the solution is valid
+            if accept(a, b) {
+                total_cost = a
+                    .checked_mul(3)
+                    .and_then(|cost| cost.checked_add(b))
+                    .and_then(|cost| total_cost.checked_add(cost))
+                    .ok_or_else(|| SolveError::unsolvable("the total cost overflows an i64"))?;
+            }
 
-        // Ensure solution is within bounds
-        if a < 0 || b < 0 {
-            return (0, 0);
+            let machine = format_args!("machine {}", index + 1);
+            if (a, b) == (0, 0) || !accept(a, b) {
+                explain.fact(machine, "no prize");
+            } else {
+                explain.fact(
+                    machine,
+                    format_args!("A {} times, B {} times, {} tokens", a, b, 3 * a + b),
+                );
+            }
         }
 
-        (a, b)
+        Ok(total_cost.into())
     }
+}
+
+impl Puzzle for Day13 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 13;
+    const TITLE: &'static str = "Claw Contraption";
 
     /// Solves Part 1 of the puzzle.
     /// Calculates the minimum total cost to win as many prizes as possible
-    /// for the initial prize coordinates.
-    pub fn part_1(&self, input: &str) -> String {
-        let machines = Self::parse_input(input);
-        let mut total_cost = 0;
-
-        for (dx_a, dy_a, dx_b, dy_b, px, py) in machines
-            .iter()
-            .map(|(a, b, c)| (a.0, a.1, b.0, b.1, c.0, c.1))
-        {
-            let (a, b) = Self::solve(px, py, dx_a, dy_a, dx_b, dy_b);
-            // Add the cost if the solution is valid
-            if a < 100 && b < 100 {
-                total_cost += 3 * a + b;
-            }
-        }
+    /// for the initial prize coordinates, pressing each button at most 100 times.
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        self.explain_part_1(input, &mut Explain::disabled())
+    }
 
-        total_cost.to_string()
+    /// Explains Part 1 by listing how often each button of every machine is pressed.
+    fn explain_part_1(&self, input: &str, explain: &mut Explain) -> Result<Answer, SolveError> {
+        let machines = Self::parse_input(input)?;
+        Self::total_cost(&machines, 0, |a, b| a <= 100 && b <= 100, explain)
     }
 
     /// Solves Part 2 of the puzzle.
     /// Calculates the minimum total cost to win as many prizes as possible
     /// after adjusting the prize coordinates by adding 10^13 to both x and y.
-    pub fn part_2(&self, input: &str) -> String {
-        let machines = Self::parse_input(input);
-        let mut total_cost = 0i64;
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        self.explain_part_2(input, &mut Explain::disabled())
+    }
 
-        for (dx_a, dy_a, dx_b, dy_b, mut px, mut py) in machines
-            .iter()
-            .map(|(a, b, c)| (a.0, a.1, b.0, b.1, c.0, c.1))
-        {
-            // Adjust prize coordinates
-            px += 10000000000000;
-            py += 10000000000000;
+    /// Explains Part 2 by listing how often each button of every machine is pressed.
+    fn explain_part_2(&self, input: &str, explain: &mut Explain) -> Result<Answer, SolveError> {
+        let machines = Self::parse_input(input)?;
 
-            let (a, b) = Self::solve(px, py, dx_a, dy_a, dx_b, dy_b);
-            // Add the cost if the solution is valid
-            total_cost += 3 * a + b;
+        // Adjusted prize coordinates must still fit into an i64
+        const OFFSET: i64 = 10000000000000;
+        if machines.iter().any(|(_, _, (px, py))| {
+            px.checked_add(OFFSET).is_none() || py.checked_add(OFFSET).is_none()
+        }) {
+            return Err(SolveError::unsupported("prize coordinates are too large"));
         }
 
-        total_cost.to_string()
+        Self::total_cost(&machines, OFFSET, |_, _| true, explain)
     }
 }
 
@@ -140,13 +159,9 @@ mod test {
     #[test]
     /// Tests Part 1 of Day 13 for AoC 2024.
     fn test_aoc2024_day13_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day13;
-        let day13 = Day13 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day13 = Day13;
         let input = r#"
 Button A: x+94, y+34
 Button B: x+22, y+67
@@ -164,19 +179,15 @@ Button A: x+69, y+23
 Button B: x+27, y+71
 Prize: x=18641, y=10279
         "#;
-        assert_eq!(day13.part_1(input), "480");
+        assert_eq!(day13.part_1(input).unwrap(), "480");
     }
 
     #[test]
     /// Tests Part 2 of Day 13 for AoC 2024.
     fn test_aoc2024_day13_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day13;
-        let day13 = Day13 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day13 = Day13;
         let input = r#"
 Button A: x+94, y+34
 Button B: x+22, y+67
@@ -194,6 +205,22 @@ Button A: x+69, y+23
 Button B: x+27, y+71
 Prize: x=18641, y=10279
         "#;
-        assert_eq!(day13.part_2(input), "875318608908");
+        assert_eq!(day13.part_2(input).unwrap(), "875318608908");
+    }
+
+    #[test]
+    /// The explanation lists the button presses of every machine.
+    fn test_aoc2024_day13_explain() {
+        use crate::aoc_solution::Puzzle;
+        use crate::explain::Explain;
+        use crate::solutions::aoc2024::Day13;
+        let input = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n\n\
+                     Button A: X+26, Y+66\nButton B: X+67, Y+21\nPrize: X=12748, Y=12176\n";
+        let mut explain = Explain::new(10);
+        assert_eq!(Day13.explain_part_1(input, &mut explain).unwrap(), "280");
+        assert_eq!(
+            explain.to_text(),
+            "machine 1: A 80 times, B 40 times, 280 tokens\nmachine 2: no prize\n"
+        );
     }
 }
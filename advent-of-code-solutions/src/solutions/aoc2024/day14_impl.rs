@@ -1,9 +1,69 @@
-use super::day14::Day14;
-use num::integer::lcm;
-use std::collections::HashSet;
+use crate::aoc_solution::{unknown_param, Answer, ParamInfo, Puzzle, SolveError};
+use crate::explain::Explain;
+use crate::trace::Trace;
+use crate::utils::cycle::{self, History};
+use crate::utils::{math, parse};
+use std::fmt;
+use std::str::FromStr;
 
-/// Represents a robot with its position and velocity.
+/// Day 14: Restroom Redoubt.
+pub struct Day14 {
+    /// The width and height of the field the robots move on.
+    field: (i32, i32),
+    /// How many seconds the robots move in part 1.
+    seconds: i32,
+    /// How part 2 recognises the Christmas tree.
+    detector: Detector,
+}
+
+impl Default for Day14 {
+    fn default() -> Self {
+        Day14 {
+            field: (101, 103),
+            seconds: 100,
+            detector: Detector::Variance,
+        }
+    }
+}
+
+/// How part 2 scores the frames of one period to find the Christmas tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Detector {
+    /// The robots of the tree bunch up along both axes, so the variance of their x and of
+    /// their y coordinates drops. Each axis repeats on its own and is minimised separately.
+    Variance,
+    /// The tree is one large blob: maximise the largest group of adjacent robots.
+    Component,
+    /// The picture is orderly: minimise the entropy of the robot counts over coarse blocks.
+    Entropy,
+}
+
+impl FromStr for Detector {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "variance" => Ok(Detector::Variance),
+            "component" => Ok(Detector::Component),
+            "entropy" => Ok(Detector::Entropy),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The frame of part 2 that looks most like a Christmas tree.
 #[derive(Debug)]
+struct TreeFrame {
+    second: usize,
+    /// How the frame scored.
+    summary: String,
+}
+
+/// How many coordinates part 2 may remember: the robots times the longer side of the field.
+const MAX_HISTORY: usize = 1 << 24;
+
+/// Represents a robot with its position and velocity.
+#[derive(Debug, Clone, PartialEq)]
 struct Robot {
     position: (i32, i32), // The current position of the robot (x, y).
     velocity: (i32, i32), // The velocity of the robot (vx, vy).
@@ -11,62 +71,44 @@ struct Robot {
 
 impl Day14 {
     /// Parses the input string into a vector of `Robot` structs.
-    fn parse_robot_data(input: &str) -> Vec<Robot> {
-        input
-            .lines() // Split the input into lines.
-            .filter(|line| !line.trim().is_empty()) // Ignore empty lines.
-            .filter_map(|line| {
-                // Attempt to parse each line into a `Robot`.
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() == 2 {
-                    // Ensure the line has exactly two parts: position and velocity.
-                    let p = parts[0].strip_prefix("p=")?; // Extract the position part.
-                    let v = parts[1].strip_prefix("v=")?; // Extract the velocity part.
-                    let position: Vec<i32> = p
-                        .split(',') // Split the position into x and y components.
-                        .filter_map(|num| num.parse::<i32>().ok()) // Parse each component as an integer.
-                        .collect();
-                    let velocity: Vec<i32> = v
-                        .split(',') // Split the velocity into x and y components.
-                        .filter_map(|num| num.parse::<i32>().ok()) // Parse each component as an integer.
-                        .collect();
-
-                    // Ensure both position and velocity have exactly two components.
-                    if position.len() == 2 && velocity.len() == 2 {
-                        return Some(Robot {
-                            position: (position[0], position[1]),
-                            velocity: (velocity[0], velocity[1]),
-                        });
-                    }
+    fn parse_robot_data(input: &str) -> Result<Vec<Robot>, SolveError> {
+        parse::lines(input)
+            .map(|line| {
+                // A robot line like `p=0,4 v=3,-3` holds exactly four numbers.
+                match line.signed::<i32>()?[..] {
+                    [px, py, vx, vy] if line.text.starts_with("p=") => Ok(Robot {
+                        position: (px, py),
+                        velocity: (vx, vy),
+                    }),
+                    _ => Err(line.error("expected a robot like p=0,4 v=3,-3")),
                 }
-                None // Return `None` if parsing fails.
             })
-            .collect() // Collect all successfully parsed robots into a vector.
+            .collect() // Collect all parsed robots into a vector.
     }
 
     /// Updates the positions of all robots after `t` seconds, considering field wrapping.
-    fn update_positions(robots: &mut Vec<Robot>, field: (i32, i32), t: i32) {
+    fn update_positions(robots: &mut [Robot], field: (i32, i32), t: i32) {
         for robot in robots.iter_mut() {
             let (x0, y0) = robot.position; // Initial position.
             let (vx, vy) = robot.velocity; // Velocity.
 
-            // Calculate new position based on velocity and time.
-            let new_x = x0 + vx * t;
-            let new_y = y0 + vy * t;
+            // Calculate new position based on velocity and time, wide enough to never overflow.
+            let new_x = x0 as i64 + vx as i64 * t as i64;
+            let new_y = y0 as i64 + vy as i64 * t as i64;
 
             // Wrap the position within the field dimensions.
-            let wrapped_x = ((new_x % field.0) + field.0) % field.0;
-            let wrapped_y = ((new_y % field.1) + field.1) % field.1;
+            let wrapped_x = new_x.rem_euclid(field.0 as i64);
+            let wrapped_y = new_y.rem_euclid(field.1 as i64);
 
             // Update the robot's position.
-            robot.position = (wrapped_x, wrapped_y);
+            robot.position = (wrapped_x as i32, wrapped_y as i32);
         }
     }
 
     /// Counts the number of robots in each of the four quadrants.
     /// Robots on the midpoint lines are excluded.
     fn count_robots_in_quadrants(
-        robots: &mut Vec<Robot>,
+        robots: &[Robot],
         field: (i32, i32),
     ) -> (usize, usize, usize, usize) {
         let x_mid = field.0 / 2; // Horizontal midpoint.
@@ -100,62 +142,385 @@ impl Day14 {
         (top_left, top_right, bottom_left, bottom_right)
     }
 
-    /// Solves part 1 of the puzzle: calculates the safety factor after 100 seconds.
-    pub fn part_1(&self, input: &str) -> String {
-        let mut robots = Self::parse_robot_data(input); // Parse the input into robots.
-        let field;
+    /// Draws the field like the puzzle description: the number of robots on each tile, or `.`
+    /// for an empty tile. Tiles with more than nine robots show `+`.
+    fn render(robots: &[Robot], field: (i32, i32)) -> String {
+        let (width, height) = (field.0 as usize, field.1 as usize);
+        let mut counts = vec![0u32; width * height];
+        for robot in robots {
+            // Robots may start outside a field whose size was overridden.
+            let x = robot.position.0.rem_euclid(field.0) as usize;
+            let y = robot.position.1.rem_euclid(field.1) as usize;
+            counts[y * width + x] += 1;
+        }
 
-        // Determine the field dimensions based on input size (test or actual puzzle).
-        match robots.len() {
-            12 => field = (11, 7),   // Test field dimensions.
-            _ => field = (101, 103), // Puzzle field dimensions.
+        let mut text = String::with_capacity((width + 1) * height);
+        for row in counts.chunks(width) {
+            for &count in row {
+                text.push(match count {
+                    0 => '.',
+                    1..=9 => char::from_digit(count, 10).unwrap_or('+'),
+                    _ => '+',
+                });
+            }
+            text.push('\n');
         }
+        text
+    }
 
-        Self::update_positions(&mut robots, field, 100); // Update positions after 100 seconds.
-        let positions = Self::count_robots_in_quadrants(&mut robots, field); // Count robots in quadrants.
+    /// The variance of the coordinates `values`.
+    fn variance(values: impl Iterator<Item = i64>) -> f64 {
+        let (mut n, mut sum, mut squares) = (0.0, 0.0, 0.0);
+        for value in values {
+            n += 1.0;
+            sum += value as f64;
+            squares += (value * value) as f64;
+        }
+        squares / n - (sum / n) * (sum / n)
+    }
 
-        // Calculate the safety factor and return it as a string.
-        (positions.0 * positions.1 * positions.2 * positions.3).to_string()
+    /// The coordinates of all robots along one axis of `size` tiles, second by second
+    /// until they repeat. Robots that start outside the field are wrapped into it.
+    fn axis_history(
+        robots: &[Robot],
+        axis: fn(&Robot) -> (i32, i32),
+        size: i32,
+    ) -> History<Vec<i32>> {
+        let velocities: Vec<i64> = robots.iter().map(|robot| axis(robot).1 as i64).collect();
+        let start = robots
+            .iter()
+            .map(|robot| axis(robot).0.rem_euclid(size))
+            .collect();
+        cycle::detect(start, |positions: &Vec<i32>| {
+            positions
+                .iter()
+                .zip(&velocities)
+                .map(|(&p, &v)| (p as i64 + v).rem_euclid(size as i64) as i32)
+                .collect()
+        })
     }
 
-    /// Checks if all robot positions are unique (used for part 2).
-    fn all_positions_unique(robots: &[Robot]) -> bool {
-        let mut positions = HashSet::new();
-        for r in robots {
-            // If a position is already in the set, return false.
-            if !positions.insert(r.position) {
-                return false;
+    /// Whether each tile of the field holds a robot, row by row.
+    fn occupancy(robots: &[Robot], field: (i32, i32)) -> Vec<bool> {
+        let mut occupied = vec![false; field.0 as usize * field.1 as usize];
+        for robot in robots {
+            let (x, y) = robot.position;
+            occupied[y as usize * field.0 as usize + x as usize] = true;
+        }
+        occupied
+    }
+
+    /// The tiles of the largest group of occupied tiles that touch horizontally or
+    /// vertically.
+    fn largest_component(occupied: &[bool], field: (i32, i32)) -> Vec<usize> {
+        let width = field.0 as usize;
+        let mut seen = vec![false; occupied.len()];
+        let mut largest = Vec::new();
+
+        for start in 0..occupied.len() {
+            if !occupied[start] || seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let (mut component, mut stack) = (Vec::new(), vec![start]);
+            while let Some(tile) = stack.pop() {
+                component.push(tile);
+                let (x, y) = (tile % width, tile / width);
+                let neighbours = [
+                    (x > 0).then(|| tile - 1),
+                    (x + 1 < width).then_some(tile + 1),
+                    (y > 0).then(|| tile - width),
+                    (tile + width < occupied.len()).then_some(tile + width),
+                ];
+                for next in neighbours.into_iter().flatten() {
+                    if occupied[next] && !seen[next] {
+                        seen[next] = true;
+                        stack.push(next);
+                    }
+                }
+            }
+            if component.len() > largest.len() {
+                largest = component;
             }
         }
-        true
+        largest
     }
 
-    /// Solves part 2 of the puzzle: finds the fewest seconds for all robots to form a unique arrangement.
-    pub fn part_2(&self, input: &str) -> String {
-        let mut robots = Self::parse_robot_data(input); // Parse the input into robots.
-        let field;
+    /// The Shannon entropy in bits of how the robots spread over blocks of about a tenth of
+    /// the field in each direction.
+    fn block_entropy(robots: &[Robot], field: (i32, i32)) -> f64 {
+        let block = ((field.0 + 9) / 10, (field.1 + 9) / 10);
+        let columns = (field.0 + block.0 - 1) / block.0;
+        let mut counts = vec![0usize; (columns * ((field.1 + block.1 - 1) / block.1)) as usize];
+        for robot in robots {
+            let (x, y) = robot.position;
+            counts[((y / block.1) * columns + x / block.0) as usize] += 1;
+        }
+
+        let total = robots.len() as f64;
+        counts
+            .into_iter()
+            .filter(|&count| count > 0)
+            .map(|count| {
+                let p = count as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
 
-        // Determine the field dimensions based on input size (test or actual puzzle).
-        match robots.len() {
-            12 => field = (11, 7),   // Test field dimensions.
-            _ => field = (101, 103), // Puzzle field dimensions.
+    /// Draws the largest group of robots with a margin of one tile: `#` for a robot and `.`
+    /// for an empty tile.
+    fn picture(robots: &[Robot], field: (i32, i32)) -> String {
+        let occupied = Self::occupancy(robots, field);
+        let component = Self::largest_component(&occupied, field);
+        let (width, height) = (field.0 as usize, field.1 as usize);
+        let xs = component.iter().map(|&tile| tile % width);
+        let ys = component.iter().map(|&tile| tile / width);
+        let (Some(left), Some(right), Some(top), Some(bottom)) =
+            (xs.clone().min(), xs.max(), ys.clone().min(), ys.max())
+        else {
+            return String::new();
+        };
+
+        let mut text = String::new();
+        for y in top.saturating_sub(1)..(bottom + 2).min(height) {
+            for x in left.saturating_sub(1)..(right + 2).min(width) {
+                text.push(if occupied[y * width + x] { '#' } else { '.' });
+            }
+            text.push('\n');
         }
+        text
+    }
 
-        let mut final_time = 0;
+    /// Finds the second within one period of the robots that the detector scores as the
+    /// most tree-like. Each axis repeats on its own, and how soon depends on the
+    /// velocities, so the period is found by detecting the cycle of both axes. Later
+    /// seconds only repeat these frames.
+    fn find_tree(&self, robots: &[Robot]) -> Result<TreeFrame, SolveError> {
+        if robots.is_empty() {
+            return Err(SolveError::unsolvable("there are no robots"));
+        }
+        let field = self.field;
+        if (field.0 as usize).max(field.1 as usize) * robots.len() > MAX_HISTORY {
+            return Err(SolveError::unsupported(
+                "the field is too large to remember every second of the robots",
+            ));
+        }
+        let x = Self::axis_history(robots, |r| (r.position.0, r.velocity.0), field.0);
+        let y = Self::axis_history(robots, |r| (r.position.1, r.velocity.1), field.1);
+        // Every second can be undone, so both cycles start at second 0.
+        let (x_period, y_period) = (x.cycle.length as i64, y.cycle.length as i64);
+        let period = math::lcm(x_period, y_period);
+        let seconds = i32::try_from(period).map_err(|_| {
+            SolveError::unsupported(format!("the robots repeat only every {} seconds", period))
+        })?;
+        let frames = || {
+            (0..seconds as usize).map(|second| {
+                let positions = x.state_at(second).iter().zip(y.state_at(second));
+                let robots: Vec<Robot> = positions
+                    .zip(robots)
+                    .map(|((&px, &py), robot)| Robot {
+                        position: (px, py),
+                        velocity: robot.velocity,
+                    })
+                    .collect();
+                (second, robots)
+            })
+        };
 
-        // Simulate until all positions are unique.
-        // since the robots wrap at the borders around I think after lcm
-        // the robots on there starting position again
-        for time in 1..lcm(field.0, field.1) + 1 {
-            Self::update_positions(&mut robots, field, 1); // Incrementally update positions.
-                                                           // I expect the Christmas tree is there when all robots in a unique position
-            if Self::all_positions_unique(&mut robots) {
-                final_time = time; // Record the time when all positions are unique.
-                break;
+        let (second, summary) = match self.detector {
+            Detector::Variance => {
+                let variances = |history: &History<Vec<i32>>| -> Vec<f64> {
+                    history
+                        .states()
+                        .iter()
+                        .map(|positions| Self::variance(positions.iter().map(|&p| p as i64)))
+                        .collect()
+                };
+                let (x, y) = (variances(&x), variances(&y));
+                let best = |variances: &[f64]| {
+                    (0..variances.len())
+                        .min_by(|&a, &b| variances[a].total_cmp(&variances[b]))
+                        .unwrap_or(0) as i64
+                };
+                // The best x repeats with the period of the x axis and the best y with that
+                // of the y axis.
+                let congruences = [(best(&x), x_period), (best(&y), y_period)];
+                let second = match math::crt(&congruences) {
+                    Some((second, _)) => second as usize,
+                    // The periods share a factor and the minima never coincide: take the
+                    // best sum of both.
+                    None => (0..seconds as usize)
+                        .min_by(|&a, &b| {
+                            let score = |t: usize| x[t % x.len()] + y[t % y.len()];
+                            score(a).total_cmp(&score(b))
+                        })
+                        .unwrap_or(0),
+                };
+                let summary = format!(
+                    "x variance {:.1} and y variance {:.1}",
+                    x[second % x.len()],
+                    y[second % y.len()]
+                );
+                (second, summary)
+            }
+            Detector::Component => {
+                let (second, size) = frames()
+                    .map(|(second, robots)| {
+                        let occupied = Self::occupancy(&robots, field);
+                        (second, Self::largest_component(&occupied, field).len())
+                    })
+                    .fold(
+                        (0, 0),
+                        |best, frame| if frame.1 > best.1 { frame } else { best },
+                    );
+                (second, format!("{} adjacent robots", size))
+            }
+            Detector::Entropy => {
+                let (second, entropy) = frames()
+                    .map(|(second, robots)| (second, Self::block_entropy(&robots, field)))
+                    .fold((0, f64::INFINITY), |best, frame| {
+                        if frame.1 < best.1 {
+                            frame
+                        } else {
+                            best
+                        }
+                    });
+                (second, format!("block entropy {:.3} bits", entropy))
             }
+        };
+
+        Ok(TreeFrame { second, summary })
+    }
+}
+
+impl Puzzle for Day14 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 14;
+    const TITLE: &'static str = "Restroom Redoubt";
+    const PARAMS: &'static [ParamInfo] = &[
+        ParamInfo {
+            name: "width",
+            default: "101",
+            description: "the width of the field",
+            choices: &[],
+        },
+        ParamInfo {
+            name: "height",
+            default: "103",
+            description: "the height of the field",
+            choices: &[],
+        },
+        ParamInfo {
+            name: "seconds",
+            default: "100",
+            description: "how many seconds the robots move in part 1",
+            choices: &[],
+        },
+        ParamInfo {
+            name: "detector",
+            default: "variance",
+            description: "how part 2 finds the tree",
+            choices: &["variance", "component", "entropy"],
+        },
+    ];
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), SolveError> {
+        match name {
+            "width" => self.field.0 = Self::PARAMS[0].parse(value)?,
+            "height" => self.field.1 = Self::PARAMS[1].parse(value)?,
+            "seconds" => self.seconds = Self::PARAMS[2].parse(value)?,
+            "detector" => self.detector = Self::PARAMS[3].parse(value)?,
+            _ => return Err(unknown_param::<Self>(name)),
+        }
+        if self.field.0 <= 0 || self.field.1 <= 0 {
+            return Err(SolveError::invalid_param("the field must not be empty"));
+        }
+        Ok(())
+    }
+
+    /// Solves part 1 of the puzzle: calculates the safety factor after 100 seconds (`seconds`).
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        self.trace_part_1(input, &mut Trace::disabled())
+    }
+
+    /// Records the robots second by second while the trace has room, then jumps to the end.
+    fn trace_part_1(&self, input: &str, trace: &mut Trace) -> Result<Answer, SolveError> {
+        let mut robots = Self::parse_robot_data(input)?; // Parse the input into robots.
+
+        let field = self.field;
+
+        // Move one second at a time while the trace records, and the rest in one go.
+        trace.frame(0, || Self::render(&robots, field));
+        let mut elapsed = 0;
+        while elapsed + 1 < self.seconds && trace.is_recording() {
+            Self::update_positions(&mut robots, field, 1);
+            elapsed += 1;
+            trace.frame(elapsed as usize, || Self::render(&robots, field));
         }
+        Self::update_positions(&mut robots, field, self.seconds - elapsed); // Update positions after the given seconds.
+        let positions = Self::count_robots_in_quadrants(&robots, field); // Count robots in quadrants.
+        trace.event(format_args!(
+            "quadrants hold {}, {}, {} and {} robots after {} seconds",
+            positions.0, positions.1, positions.2, positions.3, self.seconds
+        ));
+        trace.frame(self.seconds.max(0) as usize, || {
+            Self::render(&robots, field)
+        });
 
-        final_time.to_string() // Return the time as a string.
+        // Calculate the safety factor and return it as an Answer.
+        Ok((positions.0 * positions.1 * positions.2 * positions.3).into())
+    }
+
+    /// Solves part 2 of the puzzle: finds the fewest seconds until the robots show a Christmas tree.
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        self.trace_part_2(input, &mut Trace::disabled())
+    }
+
+    /// Records the field at the second the detector picked and the tree found in it.
+    fn trace_part_2(&self, input: &str, trace: &mut Trace) -> Result<Answer, SolveError> {
+        let mut robots = Self::parse_robot_data(input)?; // Parse the input into robots.
+        let tree = self.find_tree(&robots)?;
+
+        if trace.is_recording() {
+            Self::update_positions(&mut robots, self.field, tree.second as i32);
+            trace.event(format_args!(
+                "{:?} picks second {}: {}",
+                self.detector, tree.second, tree.summary
+            ));
+            trace.frame(tree.second, || Self::render(&robots, self.field));
+            // The largest group of robots, to check the tree by eye.
+            trace.event("the largest group of robots");
+            trace.frame(tree.second, || Self::picture(&robots, self.field));
+        }
+
+        Ok(tree.second.into())
+    }
+
+    /// Explains part 2 with the score of the second the detector picked and the tree found
+    /// in it.
+    fn explain_part_2(&self, input: &str, explain: &mut Explain) -> Result<Answer, SolveError> {
+        let mut robots = Self::parse_robot_data(input)?;
+        let tree = self.find_tree(&robots)?;
+        Self::update_positions(&mut robots, self.field, tree.second as i32);
+
+        explain.fact("detector", format_args!("{:?}", self.detector));
+        explain.fact(format_args!("second {}", tree.second), &tree.summary);
+        explain.fact("largest group of robots", Picture(&robots, self.field));
+        Ok(tree.second.into())
+    }
+}
+
+/// The largest group of robots drawn by [`Day14::picture`] on the lines after a fact's
+/// name, only rendered when formatted.
+struct Picture<'a>(&'a [Robot], (i32, i32));
+
+impl fmt::Display for Picture<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in Day14::picture(self.0, self.1).lines() {
+            write!(f, "\n{}", row)?;
+        }
+        Ok(())
     }
 }
 
@@ -163,13 +528,11 @@ mod test {
     #[test]
     /// Test for part 1 of Day 14: validates the safety factor calculation.
     fn test_aoc2024_day14_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day14;
-        let day14 = Day14 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let mut day14 = Day14::default();
+        day14.set_param("width", "11").unwrap(); // The example uses a smaller field.
+        day14.set_param("height", "7").unwrap();
         let input = r#"
 p=0,4 v=3,-3
 p=6,3 v=-1,-3
@@ -184,19 +547,18 @@ p=7,3 v=-1,2
 p=2,4 v=2,-3
 p=9,5 v=-3,-3
         "#;
-        assert_eq!(day14.part_1(input), "12"); // Ensure the result matches the expected value.
+        assert_eq!(day14.part_1(input).unwrap(), "12"); // Ensure the result matches the expected value.
     }
 
     #[test]
-    /// Test for part 2 of Day 14: validates the unique arrangement time.
-    fn test_aoc2024_day14_part_2() {
+    /// Test for part 2 of Day 14 on the example input, which shows no tree: each detector
+    /// still picks the second that scores best within one period of the robots.
+    fn test_aoc2024_day14_part_2_example() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day14;
-        let day14 = Day14 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let mut day14 = Day14::default();
+        day14.set_param("width", "11").unwrap();
+        day14.set_param("height", "7").unwrap();
         let input = r#"
 p=0,4 v=3,-3
 p=6,3 v=-1,-3
@@ -211,6 +573,120 @@ p=7,3 v=-1,2
 p=2,4 v=2,-3
 p=9,5 v=-3,-3
         "#;
-        assert_eq!(day14.part_2(input), "1"); // Ensure the result matches the expected value.
+        assert_eq!(day14.part_2(input).unwrap(), "24"); // Ensure the result matches the expected value.
+
+        // Without a tree to find, each detector settles on its own second of the 77.
+        for (detector, second) in [("component", "6"), ("entropy", "25")] {
+            day14.set_param("detector", detector).unwrap();
+            assert_eq!(day14.part_2(input).unwrap(), second);
+        }
+    }
+
+    #[test]
+    /// Test for part 2 of Day 14: every detector finds the second at which robots flying
+    /// through each other form a small tree, among robots moving at random.
+    fn test_aoc2024_day14_part_2() {
+        use crate::aoc_solution::Puzzle;
+        use crate::explain::Explain;
+        use crate::solutions::aoc2024::Day14;
+        use crate::trace::Trace;
+        let (width, height, tree_second) = (31i64, 37i64, 700i64);
+
+        // A triangle of 8 rows and a trunk of 2 tiles, plus robots anywhere.
+        let mut tiles: Vec<(i64, i64)> = (0..8)
+            .flat_map(|row| (-row..=row).map(move |dx| (15 + dx, 10 + row)))
+            .collect();
+        tiles.extend([(15, 18), (15, 19)]);
+        let mut seed = 14u64;
+        let mut random = |range: i64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as i64 % range
+        };
+        let mut input = String::new();
+        for i in 0..tiles.len() + 25 {
+            let (vx, vy) = (random(21) - 10, random(21) - 10);
+            let (x, y) = match tiles.get(i) {
+                Some(&(x, y)) => (x - vx * tree_second, y - vy * tree_second),
+                None => (random(width), random(height)),
+            };
+            input += &format!(
+                "p={},{} v={},{}\n",
+                x.rem_euclid(width),
+                y.rem_euclid(height),
+                vx,
+                vy
+            );
+        }
+
+        for detector in ["variance", "component", "entropy"] {
+            let mut day14 = Day14::default();
+            day14.set_param("width", "31").unwrap();
+            day14.set_param("height", "37").unwrap();
+            day14.set_param("detector", detector).unwrap();
+            let mut trace = Trace::new(2);
+            assert_eq!(day14.trace_part_2(&input, &mut trace).unwrap(), "700");
+
+            // The picture shows the tree with a margin of one tile; random robots may
+            // appear in it as well.
+            let picture = &trace.frames()[1].grid;
+            assert!(picture.len() >= 12);
+            assert!(picture.iter().any(|row| row.contains(&"#".repeat(15))));
+            assert!(picture.concat().matches('#').count() >= tiles.len());
+        }
+
+        // The explanation shows the tree as well.
+        let mut day14 = Day14::default();
+        day14.set_param("width", "31").unwrap();
+        day14.set_param("height", "37").unwrap();
+        let mut explain = Explain::new(usize::MAX);
+        assert_eq!(day14.explain_part_2(&input, &mut explain).unwrap(), "700");
+        let facts = explain.facts();
+        assert_eq!(facts[1].name, "second 700");
+        assert_eq!(facts[2].name, "largest group of robots");
+        assert!(facts[2].value.starts_with('\n'));
+        assert!(facts[2]
+            .value
+            .lines()
+            .any(|row| row.contains(&"#".repeat(15))));
+
+        assert!(day14.set_param("detector", "guess").is_err());
+    }
+
+    #[test]
+    /// Robots that start outside the field are wrapped into it before any detector scores
+    /// them.
+    fn test_aoc2024_day14_outside_field() {
+        use crate::aoc_solution::Puzzle;
+        use crate::solutions::aoc2024::Day14;
+
+        // The same robots as inside the 11x7 field, moved by whole fields.
+        let inside = "p=0,4 v=3,-3\np=6,3 v=-1,-3\np=10,3 v=-1,2\np=2,0 v=2,-1\n";
+        let outside = "p=-11,4 v=3,-3\np=6,-4 v=-1,-3\np=208,3 v=-1,2\np=2,14 v=2,-1\n";
+        for detector in ["variance", "component", "entropy"] {
+            let mut day14 = Day14::default();
+            day14.set_param("width", "11").unwrap();
+            day14.set_param("height", "7").unwrap();
+            day14.set_param("detector", detector).unwrap();
+            assert_eq!(day14.part_2(outside), day14.part_2(inside), "{}", detector);
+            assert!(day14.part_2("p=-1,0 v=1,1").is_ok());
+        }
+    }
+
+    #[test]
+    /// The period of an axis follows from the velocities: robots that only move by even
+    /// steps across a field of 10 tiles repeat after 5 seconds, not 10.
+    fn test_aoc2024_day14_period() {
+        use crate::solutions::aoc2024::day14_impl::{Day14, Robot};
+
+        let robots = [(0, 2), (13, -4), (5, 0)].map(|(x, vx)| Robot {
+            position: (x, 0),
+            velocity: (vx, 0),
+        });
+        let x = Day14::axis_history(&robots, |r| (r.position.0, r.velocity.0), 10);
+        assert_eq!((x.cycle.start, x.cycle.length), (0, 5));
+        assert_eq!(x.states()[0], [0, 3, 5]);
+        assert_eq!(x.state_at(7), &[4, 5, 5]);
     }
 }
@@ -1,155 +1,110 @@
-use super::day15::Day15;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::trace::Trace;
+use crate::utils::grid::Grid;
+use crate::utils::parse;
+use crate::utils::point::{Direction, Point};
 use std::collections::HashSet;
 
-impl Day15 {
-    /// Generalized input parsing for both parts
-    fn parse_input<F>(
-        input: &str,
-        transform_map: F, // Function to transform the map for Part 1 or Part 2
-    ) -> (Vec<Vec<char>>, Vec<(isize, isize)>, (isize, isize))
-    where
-        F: Fn(usize, usize, char, &mut (isize, isize)) -> Vec<char>,
-    {
-        // Filter out empty lines and split into relevant sections
-        let lines: Vec<&str> = input
-            .lines()
-            .filter(|line| !line.trim().is_empty())
-            .collect();
-
-        let mut start = (0, 0); // Starting position of the robot
+/// Day 15: Warehouse Woes.
+#[derive(Default)]
+pub struct Day15;
 
-        // Parse the 2D map
-        let map: Vec<Vec<char>> = lines
-            .iter()
-            .take_while(|line| line.starts_with('#') && line.ends_with('#')) // Only lines of the map
-            .enumerate()
-            .map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .flat_map(|(x, ch)| transform_map(x, y, ch, &mut start))
-                    .collect()
-            })
-            .collect();
-
-        // Parse the movement commands
-        let commands: Vec<(isize, isize)> = lines
-            .iter()
-            .skip_while(|line| line.starts_with('#') && line.ends_with('#')) // Only movement commands
-            .flat_map(|line| {
-                line.chars().map(|ch| match ch {
-                    '<' => (-1, 0), // Left
-                    '^' => (0, -1), // Up
-                    '>' => (1, 0),  // Right
-                    'v' => (0, 1),  // Down
-                    _ => panic!("Unknown direction: {}", ch),
-                })
-            })
-            .collect();
+/// The map, the movement commands and the robot's starting position.
+type ParsedInput = (Grid<char>, Vec<Direction>, Point<i32>);
 
-        (map, commands, start)
-    }
+impl Day15 {
+    /// Parses the map and the movement commands. With `wide`, every tile of the map is
+    /// doubled in width for Part 2 and boxes become `[]`.
+    fn parse_input(input: &str, wide: bool) -> Result<ParsedInput, SolveError> {
+        // The map is the first section, the movement commands follow after a blank line
+        let sections = parse::sections(input);
+        let (map_section, command_sections) = sections
+            .split_first()
+            .ok_or_else(|| SolveError::parse(1, 1, "expected a map"))?;
 
-    /// Transformation function for Part 1 (single-width boxes)
-    fn transform_part_1(x: usize, y: usize, ch: char, start: &mut (isize, isize)) -> Vec<char> {
-        if ch == '@' {
-            *start = (x as isize, y as isize); // Record the robot's starting position
-        }
-        vec![ch] // Return the character unmodified
-    }
+        let mut map = map_section.grid(|ch| match ch {
+            '#' | '.' | 'O' | '@' => Ok(ch),
+            _ => Err("expected one of '#', '.', 'O' or '@'"),
+        })?;
 
-    /// Transformation function for Part 2 (double-width boxes)
-    fn transform_part_2(x: usize, y: usize, ch: char, start: &mut (isize, isize)) -> Vec<char> {
-        match ch {
-            '#' => vec!['#', '#'], // Double walls
-            '.' => vec!['.', '.'], // Double empty spaces
-            'O' => vec!['[', ']'], // Boxes become wider (represented by brackets)
-            '@' => {
-                *start = ((x * 2) as isize, y as isize); // Adjust robot's starting position
-                vec!['@', '.'] // Robot expands to match the scaled map
+        // The map must be enclosed by walls and contain exactly one robot,
+        // so the simulation never leaves it.
+        let (width, height) = (map.width() as i32, map.height() as i32);
+        for (p, &ch) in map.iter() {
+            let border = p.x == 0 || p.y == 0 || p.x + 1 == width || p.y + 1 == height;
+            if border && ch != '#' {
+                return Err(SolveError::parse(
+                    map_section.first_line + p.y as usize,
+                    p.x as usize + 1,
+                    "the map must be enclosed by walls",
+                ));
             }
-            _ => panic!("Unexpected character in map: {}", ch),
         }
-    }
-
-    /// Parse input for Part 1
-    pub fn parse_input_part_1(
-        input: &str,
-    ) -> (Vec<Vec<char>>, Vec<(isize, isize)>, (isize, isize)) {
-        Self::parse_input(input, Self::transform_part_1)
-    }
-
-    /// Parse input for Part 2
-    pub fn parse_input_part_2(
-        input: &str,
-    ) -> (Vec<Vec<char>>, Vec<(isize, isize)>, (isize, isize)) {
-        Self::parse_input(input, Self::transform_part_2)
-    }
-
-    /// Solve Part 1
-    pub fn part_1(&self, input: &str) -> String {
-        let (mut map, commands, mut pos) = Self::parse_input_part_1(input);
-
-        for dir in commands {
-            let mut next = (pos.0 + dir.0, pos.1 + dir.1);
+        let robots = map.find_all(|&ch| ch == '@').count();
+        if robots != 1 {
+            return Err(SolveError::unsolvable(format!(
+                "expected exactly one robot, found {}",
+                robots
+            )));
+        }
 
-            // Keep pushing boxes until a wall or free space is found
-            while map[next.1 as usize][next.0 as usize] == 'O' {
-                next = (next.0 + dir.0, next.1 + dir.1);
-            }
+        if wide {
+            // Double every tile of the rendered map and parse it again
+            let wide_map: String = map
+                .to_string()
+                .chars()
+                .map(|ch| match ch {
+                    '#' => "##", // Double walls
+                    '.' => "..", // Double empty spaces
+                    'O' => "[]", // Boxes become wider (represented by brackets)
+                    '@' => "@.", // Robot expands to match the scaled map
+                    _ => "\n",
+                })
+                .collect();
+            map = Grid::parse(&wide_map, Ok)?;
+        }
+        let start = map.find(|&ch| ch == '@').expect("the map has one robot");
 
-            if map[next.1 as usize][next.0 as usize] == '.' {
-                // Move cells backwards to simulate the push
-                while next != pos {
-                    let prev = (next.0 - dir.0, next.1 - dir.1);
-                    map[next.1 as usize][next.0 as usize] = map[prev.1 as usize][prev.0 as usize];
-                    next = prev;
-                }
-                // Clear the robot's previous position
-                map[pos.1 as usize][pos.0 as usize] = '.';
-                pos = (pos.0 + dir.0, pos.1 + dir.1);
+        // Parse the movement commands
+        let mut commands = Vec::new();
+        for line in command_sections.iter().flat_map(|section| section.lines()) {
+            for (column, ch) in line.text.char_indices() {
+                let direction = Direction::from_arrow(ch).ok_or_else(|| {
+                    line.error_at(&line.text[column..], "expected one of '<', '^', '>' or 'v'")
+                })?;
+                commands.push(direction);
             }
         }
 
-        // Sum the GPS coordinates of all boxes
-        map.iter()
-            .enumerate()
-            .map(|(row, line)| {
-                line.iter()
-                    .enumerate()
-                    .filter(|&(_, &c)| c == 'O') // Only box positions
-                    .map(move |(col, _)| 100u64 * row as u64 + col as u64) // Calculate GPS
-            })
-            .flatten()
-            .sum::<u64>()
-            .to_string()
+        Ok((map, commands, start))
     }
 
     /// Expand connected regions of wide boxes (Part 2)
     fn expand_connected_regions(
-        map: &Vec<Vec<char>>,
-        pos: (isize, isize),
-        dir: (isize, isize),
-    ) -> Option<HashSet<(isize, isize)>> {
+        map: &Grid<char>,
+        pos: Point<i32>,
+        dir: Point<i32>,
+    ) -> Option<HashSet<Point<i32>>> {
         let mut boxes = Vec::from([pos]);
         let mut all_boxes = HashSet::from([pos]);
 
         while let Some(curr) = boxes.pop() {
-            let next = (curr.0 + dir.0, curr.1 + dir.1);
+            let next = curr + dir;
             if all_boxes.contains(&next) {
                 continue;
             }
-            let c = map[next.1 as usize][next.0 as usize];
+            let c = map[next];
             match c {
                 '.' => {} // Found an empty edge
                 ']' | '[' => {
                     // Add the box part to the frontier
                     boxes.push(next);
                     all_boxes.insert(next);
-                    if dir.1 != 0 {
+                    if dir.y != 0 {
                         let other = if c == '[' {
-                            (next.0 + 1, next.1)
+                            next + Point::new(1, 0)
                         } else {
-                            (next.0 - 1, next.1)
+                            next - Point::new(1, 0)
                         };
                         boxes.push(other);
                         all_boxes.insert(other);
@@ -161,40 +116,101 @@ impl Day15 {
         Some(all_boxes)
     }
 
+    /// Sums the GPS coordinates of all cells showing `tile`.
+    fn gps_sum(map: &Grid<char>, tile: char) -> u64 {
+        map.find_all(|&c| c == tile)
+            .map(|p| 100 * p.y as u64 + p.x as u64)
+            .sum()
+    }
+}
+
+impl Puzzle for Day15 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 15;
+    const TITLE: &'static str = "Warehouse Woes";
+
+    /// Solve Part 1
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        self.trace_part_1(input, &mut Trace::disabled())
+    }
+
+    /// Records the warehouse after every move of the robot.
+    fn trace_part_1(&self, input: &str, trace: &mut Trace) -> Result<Answer, SolveError> {
+        let (mut map, commands, mut pos) = Self::parse_input(input, false)?;
+        trace.frame(0, || map.to_string());
+
+        for (step, direction) in (1..).zip(commands) {
+            let dir: Point<i32> = direction.into();
+            let mut next = pos + dir;
+
+            // Keep pushing boxes until a wall or free space is found
+            while map[next] == 'O' {
+                next += dir;
+            }
+
+            if map[next] == '.' {
+                let pushed = next.manhattan(pos) - 1;
+                if pushed > 0 {
+                    trace.event(format_args!("pushed {} boxes {:?}", pushed, direction));
+                }
+                // Move cells backwards to simulate the push
+                while next != pos {
+                    let prev = next - dir;
+                    map[next] = map[prev];
+                    next = prev;
+                }
+                // Clear the robot's previous position
+                map[pos] = '.';
+                pos += dir;
+            } else {
+                trace.event(format_args!("blocked moving {:?}", direction));
+            }
+            trace.frame(step, || map.to_string());
+        }
+
+        // Sum the GPS coordinates of all boxes
+        Ok(Self::gps_sum(&map, 'O').into())
+    }
+
     /// Solve Part 2
-    pub fn part_2(&self, input: &str) -> String {
-        let (mut map, commands, mut pos) = Self::parse_input_part_2(input);
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        self.trace_part_2(input, &mut Trace::disabled())
+    }
+
+    /// Records the wide warehouse after every move of the robot.
+    fn trace_part_2(&self, input: &str, trace: &mut Trace) -> Result<Answer, SolveError> {
+        let (mut map, commands, mut pos) = Self::parse_input(input, true)?;
         let mut scratch_grid = map.clone();
+        trace.frame(0, || map.to_string());
 
-        for dir in commands {
+        for (step, direction) in (1..).zip(commands) {
+            let dir: Point<i32> = direction.into();
             if let Some(boxes) = Self::expand_connected_regions(&map, pos, dir) {
+                // The region holds the robot and both halves of every box it pushes
+                if boxes.len() > 1 {
+                    trace.event(format_args!(
+                        "pushed {} boxes {:?}",
+                        (boxes.len() - 1) / 2,
+                        direction
+                    ));
+                }
                 // Backup and move all connected cells
                 for &cell in &boxes {
-                    scratch_grid[cell.1 as usize][cell.0 as usize] =
-                        map[cell.1 as usize][cell.0 as usize];
-                    map[cell.1 as usize][cell.0 as usize] = '.'; // Clear old positions
+                    scratch_grid[cell] = map[cell];
+                    map[cell] = '.'; // Clear old positions
                 }
                 for &cell in &boxes {
-                    let new_c = (cell.0 + dir.0, cell.1 + dir.1);
-                    map[new_c.1 as usize][new_c.0 as usize] =
-                        scratch_grid[cell.1 as usize][cell.0 as usize];
+                    map[cell + dir] = scratch_grid[cell];
                 }
-                pos = (pos.0 + dir.0, pos.1 + dir.1);
+                pos += dir;
+            } else {
+                trace.event(format_args!("blocked moving {:?}", direction));
             }
+            trace.frame(step, || map.to_string());
         }
 
-        // Sum the GPS coordinates of all wide boxes
-        map.iter()
-            .enumerate()
-            .map(|(row, line)| {
-                line.iter()
-                    .enumerate()
-                    .filter(|&(_, &c)| c == '[') // Left edge of wide boxes
-                    .map(move |(col, _)| 100u64 * row as u64 + col as u64)
-            })
-            .flatten()
-            .sum::<u64>()
-            .to_string()
+        // Sum the GPS coordinates of all wide boxes by their left edge
+        Ok(Self::gps_sum(&map, '[').into())
     }
 }
 
@@ -203,13 +219,9 @@ mod test {
     /// Test for part 1 of Day15 for AoC 2024.
     /// Ensures correct calculation of the sum of first and last digits in each line.
     fn test_aoc2024_day15_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day15;
-        let day15 = Day15 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day15 = Day15;
         let input = r#"
 ##########
 #..O..O.O#
@@ -233,20 +245,16 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
 v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
         "#;
-        assert_eq!(day15.part_1(input), "10092"); // Asserts if the function output matches the expected result.
+        assert_eq!(day15.part_1(input).unwrap(), "10092"); // Asserts if the function output matches the expected result.
     }
 
     #[test]
     /// Test for part 2 of Day15 for AoC 2024.
     /// Verifies correct handling and sum of lines with spelled-out numbers.
     fn test_aoc2024_day15_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day15;
-        let day15 = Day15 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day15 = Day15;
         let input = r#"
 ##########
 #..O..O.O#
@@ -270,6 +278,41 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
 v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
         "#;
-        assert_eq!(day15.part_2(input), "9021"); // Asserts if the function output matches the expected result.
+        assert_eq!(day15.part_2(input).unwrap(), "9021"); // Asserts if the function output matches the expected result.
+    }
+
+    #[test]
+    /// The trace has a frame per move, and blocked moves and pushes are logged.
+    fn test_aoc2024_day15_trace() {
+        use crate::aoc_solution::Puzzle;
+        use crate::solutions::aoc2024::Day15;
+        use crate::trace::Trace;
+        let day15 = Day15;
+        let input = r#"
+########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<
+        "#;
+        let mut trace = Trace::new(usize::MAX);
+        assert_eq!(day15.trace_part_1(input, &mut trace).unwrap(), "2028");
+
+        let frames = trace.frames();
+        assert_eq!(frames.len(), 16);
+        assert_eq!(
+            frames[0].grid.join("\n"),
+            input.trim().split("\n\n").next().unwrap()
+        );
+        assert_eq!(frames[1].events, ["blocked moving W"]);
+        assert_eq!(frames[2].grid[1], "#.@O.O.#");
+        assert_eq!(frames[5].events, ["pushed 2 boxes E"]);
+        assert_eq!(frames[5].grid[1], "#...@OO#");
+        assert_eq!(frames[7].events, ["pushed 4 boxes S"]);
     }
 }
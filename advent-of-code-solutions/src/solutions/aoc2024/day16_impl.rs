@@ -1,103 +1,101 @@
-use super::day16::Day16;
-use crate::utils::point::Point;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::grid::Grid;
+use crate::utils::point::{Direction, Point};
+use crate::utils::search::{self, Found, Graph};
 use hashbrown::HashSet;
-use pathfinding::prelude::astar_bag;
+
+/// Day 16: Reindeer Maze.
+#[derive(Default)]
+pub struct Day16;
 
 /// Represents the state of the Reindeer in the maze.
 #[derive(Clone, Eq, Hash, PartialEq, Debug)]
 pub struct Reindeer {
-    pos: Point<i16>, // Current position in the maze.
-    dir: Point<i16>, // Current direction (e.g., (1, 0) for East).
+    pos: Point<i32>, // Current position in the maze.
+    dir: Direction,  // Current direction.
+}
+
+/// The maze with its start and end tiles.
+type Maze = (Grid<char>, Point<i32>, Point<i32>);
+
+/// The maze as a graph of reindeer states, weighted by the score of every move.
+struct MazeGraph<'a>(&'a Grid<char>);
+
+impl Graph for MazeGraph<'_> {
+    type Node = Reindeer;
+
+    fn successors(&self, r: &Reindeer) -> impl Iterator<Item = (Reindeer, u64)> {
+        Day16::get_successors(r, self.0).into_iter()
+    }
 }
 
 /// Contains the solution logic for Day 16.
 impl Day16 {
     /// Parses the maze input into a map grid, start position, and end position.
-    fn parse_map(input: &str) -> (Vec<Vec<char>>, Point<i16>, Point<i16>) {
-        let mut map = Vec::new();
-        let mut start = Point { x: 0, y: 0 };
-        let mut end = Point { x: 0, y: 0 };
-
-        // Process each line in the input.
-        for (row_idx, line) in input.lines().filter(|s| !s.trim().is_empty()).enumerate() {
-            let mut row = Vec::new();
-            for (col_idx, ch) in line.trim().chars().enumerate() {
-                match ch {
-                    'S' => {
-                        start = Point {
-                            x: col_idx as i16,
-                            y: row_idx as i16,
-                        }
-                    }
-                    'E' => {
-                        end = Point {
-                            x: col_idx as i16,
-                            y: row_idx as i16,
-                        }
-                    }
-                    '#' | '.' => {} // Valid map characters.
-                    _ => panic!(
-                        "Invalid character '{}' in map at ({}, {})",
-                        ch, row_idx, col_idx
-                    ),
-                }
-                row.push(ch);
-            }
-            map.push(row);
-        }
-
-        (map, start, end)
+    fn parse_map(input: &str) -> Result<Maze, SolveError> {
+        let map = Grid::parse(input, |ch| match ch {
+            'S' | 'E' | '#' | '.' => Ok(ch),
+            _ => Err("expected one of '#', '.', 'S' or 'E'"),
+        })?;
+
+        let start = map
+            .find(|&ch| ch == 'S')
+            .ok_or_else(|| SolveError::unsolvable("the maze has no start tile"))?;
+        let end = map
+            .find(|&ch| ch == 'E')
+            .ok_or_else(|| SolveError::unsolvable("the maze has no end tile"))?;
+        Ok((map, start, end))
     }
 
     /// Generates all valid moves for the given reindeer state.
-    fn get_successors(r: &Reindeer, map: &Vec<Vec<char>>) -> Vec<(Reindeer, u32)> {
+    fn get_successors(r: &Reindeer, map: &Grid<char>) -> Vec<(Reindeer, u64)> {
         let mut potential_positions = vec![];
 
         // Move forward.
-        let ahead = r.pos + r.dir;
+        let ahead = r.pos + r.dir.into();
         if Self::is_valid(&ahead, map) {
             potential_positions.push((
                 Reindeer {
                     pos: ahead,
                     dir: r.dir,
                 },
-                1u32,
+                1u64,
             ));
         }
 
         // Turn left and move.
-        let left = r.pos + r.dir.rotate_ccw();
+        let left = r.pos + r.dir.turn_left().into();
         if Self::is_valid(&left, map) {
             potential_positions.push((
                 Reindeer {
                     pos: left,
-                    dir: r.dir.rotate_ccw(),
+                    dir: r.dir.turn_left(),
                 },
-                1001u32,
+                1001u64,
             ));
         }
 
         // Turn right and move.
-        let right = r.pos + r.dir.rotate_cw();
+        let right = r.pos + r.dir.turn_right().into();
         if Self::is_valid(&right, map) {
             potential_positions.push((
                 Reindeer {
                     pos: right,
-                    dir: r.dir.rotate_cw(),
+                    dir: r.dir.turn_right(),
                 },
-                1001u32,
+                1001u64,
             ));
         }
 
         // Move backward.
-        let behind = r.pos - r.dir;
+        let behind = r.pos - r.dir.into();
         if Self::is_valid(&behind, map) {
             potential_positions.push((
                 Reindeer {
                     pos: behind,
-                    dir: r.dir.rotate_cw().rotate_cw(),
+                    dir: r.dir.opposite(),
                 },
-                2001u32,
+                2001u64,
             ));
         }
 
@@ -106,56 +104,57 @@ impl Day16 {
 
     #[inline]
     /// Checks if a point is valid (within bounds and not a wall).
-    fn is_valid(p: &Point<i16>, map: &Vec<Vec<char>>) -> bool {
-        (0..map[0].len()).contains(&(p.x as usize))
-            && (0..map.len()).contains(&(p.y as usize))
-            && map[p.y as usize][p.x as usize] != '#'
+    fn is_valid(p: &Point<i32>, map: &Grid<char>) -> bool {
+        map.get(*p).is_some_and(|&ch| ch != '#')
     }
 
-    /// Solves Part 1: Finds the minimum cost to traverse the maze.
-    pub fn part_1(&self, input: &str) -> String {
-        let (map, start, end) = Self::parse_map(input);
+    /// Finds all cheapest ways from the start, facing east, to the end tile.
+    fn search_maze(input: &str) -> Result<Found<Reindeer>, SolveError> {
+        let (map, start, end) = Self::parse_map(input)?;
         let reindeer = Reindeer {
             pos: start,
-            dir: Point { x: 1, y: 0 },
+            dir: Direction::E,
         };
 
-        // Use A* search to find the minimum cost.
-        let (_solution, cost) = astar_bag(
-            &reindeer,
-            |r| Self::get_successors(r, &map),
-            |r| r.pos.manhattan(end) as u32,
+        search::astar(
+            &MazeGraph(&map),
+            reindeer,
+            |r| r.pos.manhattan(end) as u64,
             |r| r.pos == end,
         )
-        .unwrap();
-
-        cost.to_string()
+        .ok_or_else(Self::no_path)
     }
 
-    /// Solves Part 2: Counts the tiles in the optimal paths.
-    pub fn part_2(&self, input: &str) -> String {
-        let (map, start, end) = Self::parse_map(input);
-        let reindeer = Reindeer {
-            pos: start,
-            dir: Point { x: 1, y: 0 },
-        };
+    /// The error returned when the end tile cannot be reached.
+    fn no_path() -> SolveError {
+        SolveError::unsolvable("there is no path from the start to the end tile")
+    }
+}
 
-        // Use A* search to find all optimal paths and their tiles.
-        let (solution, _cost) = astar_bag(
-            &reindeer,
-            |r| Self::get_successors(r, &map),
-            |r| r.pos.manhattan(end) as u32,
-            |r| r.pos == end,
-        )
-        .unwrap();
+impl Puzzle for Day16 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 16;
+    const TITLE: &'static str = "Reindeer Maze";
 
-        // Collect all tiles from the optimal paths.
-        let all_points: HashSet<Point<i16>> = solution.fold(HashSet::default(), |mut set, rs| {
-            set.extend(rs.iter().map(|r| r.pos));
-            set
-        });
+    /// Solves Part 1: Finds the minimum cost to traverse the maze.
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let found = Self::search_maze(input)?;
+        Ok(found.cost.into())
+    }
 
-        all_points.len().to_string()
+    /// Solves Part 2: Counts the tiles in the optimal paths.
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let found = Self::search_maze(input)?;
+
+        // Collect all tiles from the optimal paths, whichever way the reindeer faced.
+        let all_points: HashSet<Point<i32>> = found
+            .paths
+            .on_shortest_paths(&found.goals)
+            .into_iter()
+            .map(|r| r.pos)
+            .collect();
+
+        Ok(all_points.len().into())
     }
 }
 
@@ -164,13 +163,9 @@ mod test {
     /// Test for part 1 of Day16 for AoC 2024.
     /// Ensures correct calculation of the sum of first and last digits in each line.
     fn test_aoc2024_day16_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day16;
-        let day16 = Day16 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day16 = Day16;
         let input = r#"
 ###############
 #.......#....E#
@@ -188,20 +183,16 @@ mod test {
 #S..#.....#...#
 ###############
         "#;
-        assert_eq!(day16.part_1(input), "7036"); // Asserts if the function output matches the expected result.
+        assert_eq!(day16.part_1(input).unwrap(), "7036"); // Asserts if the function output matches the expected result.
     }
 
     #[test]
     /// Test for part 2 of Day16 for AoC 2024.
     /// Verifies correct handling and sum of lines with spelled-out numbers.
     fn test_aoc2024_day16_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day16;
-        let day16 = Day16 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day16 = Day16;
         let input = r#"
 ###############
 #.......#....E#
@@ -219,6 +210,6 @@ mod test {
 #S..#.....#...#
 ###############
         "#;
-        assert_eq!(day16.part_2(input), "45"); // Asserts if the function output matches the expected result.
+        assert_eq!(day16.part_2(input).unwrap(), "45"); // Asserts if the function output matches the expected result.
     }
 }
@@ -1,236 +1,120 @@
-use super::day17::Day17;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::parse::{self, Line};
+use crate::utils::three_bit::{self, Machine, Program, Registers, Stop};
 
-/// Represents the 3-Bit Computer's State.
-/// This includes registers (A, B, C), the program input, an instruction pointer,
-/// and the output buffer for collected results.
-pub struct State {
-    pub a: u32,             // Register A
-    pub b: u32,             // Register B
-    pub c: u32,             // Register C
-    pub input: Vec<usize>,  // Program input (sequence of 3-bit opcodes and operands)
-    pub inst: usize,        // Instruction pointer, tracks the current program position
-    pub output: Vec<usize>, // Storage for output values produced by the 'out' instruction
-}
+/// Day 17: Chronospatial Computer.
+#[derive(Default)]
+pub struct Day17;
+
+impl Day17 {
+    /// Upper bound on executed instructions, so programs that never halt are reported.
+    const MAX_STEPS: usize = 10_000_000;
+
+    /// Upper bound on executed instructions for each candidate value of A in part 2.
+    const MAX_CANDIDATE_STEPS: usize = 100_000;
 
-impl State {
-    /// Parses the input string and initializes the `State` structure.
-    /// Extracts initial register values (A, B, C) and the program instructions.
-    pub fn from_input(input: &str) -> Self {
-        let mut a = 0;
-        let mut b = 0;
-        let mut c = 0;
-        let mut input_vec = Vec::new();
+    /// Parses the input string into the initial registers (A, B, C) and the program.
+    fn parse_input(input: &str) -> Result<(Registers, Program), SolveError> {
+        let mut registers = Registers::default();
+        let mut program = None;
 
         // Parse each line of input to extract registers and program instructions.
-        for line in input.lines() {
-            if line.starts_with("Register A:") {
-                a = Self::parse_value(line);
-            } else if line.starts_with("Register B:") {
-                b = Self::parse_value(line);
-            } else if line.starts_with("Register C:") {
-                c = Self::parse_value(line);
-            } else if line.starts_with("Program:") {
-                input_vec = Self::parse_program(line);
+        for line in parse::lines(input) {
+            let (key, value) = line.key_value()?;
+            match key {
+                "Register A" => registers.a = line.number(value, "a register value")?,
+                "Register B" => registers.b = line.number(value, "a register value")?,
+                "Register C" => registers.c = line.number(value, "a register value")?,
+                "Program" => program = Some(Self::parse_program(&line, value)?),
+                _ => return Err(line.error("expected a register or the program")),
             }
         }
 
-        // Initialize the state with parsed values.
-        State {
-            a,
-            b,
-            c,
-            input: input_vec,
-            inst: 0,            // Instruction pointer starts at 0
-            output: Vec::new(), // Initially empty output buffer
-        }
-    }
-
-    /// Runs the program by processing each instruction until the halt condition is met.
-    pub fn run_program(&mut self) {
-        // Continue execution until the instruction pointer is out of bounds.
-        while self.inst < self.input.len() {
-            let opcode = self.input[self.inst]; // Current instruction (opcode)
-            let operand = self.input.get(self.inst + 1).cloned().unwrap_or(0) as u8; // Operand
-
-            // Match the opcode to the corresponding instruction and execute it.
-            match opcode {
-                0 => self.adv(operand), // ADV: Divide A by 2^operand
-                1 => self.bxl(operand), // BXL: XOR B with operand
-                2 => self.bst(operand), // BST: Set B = operand % 8
-                3 => {
-                    // JNZ: Jump if A != 0
-                    if self.jnz(operand) {
-                        continue; // Skip instruction pointer increment if jump occurs
-                    }
+        let program = program.ok_or_else(|| {
+            SolveError::parse(input.lines().count() + 1, 1, "expected a 'Program:' line")
+        })?;
+        Ok((registers, program))
+    }
+
+    /// Parses the program (comma-separated 3-bit numbers).
+    fn parse_program(line: &Line, program: &str) -> Result<Program, SolveError> {
+        let code = program
+            .split(',')
+            .map(|token| {
+                // Accept only 3-bit values
+                let value: u8 = line.number(token, "a 3-bit number")?;
+                if value < 8 {
+                    Ok(value)
+                } else {
+                    Err(line.error_at(token.trim(), "expected a 3-bit number"))
                 }
-                4 => self.bxc(operand), // BXC: XOR B with C
-                5 => self.out(operand), // OUT: Output operand % 8
-                6 => self.bdv(operand), // BDV: Divide A by 2^operand, store result in B
-                7 => self.cdv(operand), // CDV: Divide A by 2^operand, store result in C
-                _ => break,             // Invalid opcode halts the program
-            }
-
-            self.inst += 2; // Move to the next instruction (each instruction is 2 values)
+            })
+            .collect::<Result<Vec<u8>, SolveError>>()?;
+        Program::new(code).ok_or_else(|| line.error_at(program, "expected 3-bit numbers"))
+    }
+
+    /// Runs the program to the end and returns the machine with its output.
+    /// Fails if the program does not halt within `MAX_STEPS` instructions.
+    fn run_program(program: &Program, registers: Registers) -> Result<Machine<'_>, SolveError> {
+        let mut machine = Machine::new(program, registers);
+        match machine.run(Self::MAX_STEPS) {
+            Stop::Halted => Ok(machine),
+            _ => Err(SolveError::unsolvable(format!(
+                "the program does not halt within {} instructions",
+                Self::MAX_STEPS
+            ))),
         }
     }
 
-    /// Converts the program output (a list of numbers) into a comma-separated string.
-    pub fn to_string(&self) -> String {
-        self.output
+    /// Formats the program output (a list of numbers) as a comma-separated string.
+    fn format_output(output: &[u8]) -> String {
+        output
             .iter()
             .map(|num| num.to_string()) // Convert each number to a string
             .collect::<Vec<String>>() // Collect into a vector of strings
             .join(",") // Join the vector into a single string
     }
-
-    /// Resolves the value of a combo operand based on its type.
-    /// Combo operands map to literal values (0-3) or register values (4-6).
-    fn resolve_operand(&self, operand: u8) -> u32 {
-        match operand {
-            0..=3 => operand as u32, // Literal values 0-3
-            4 => self.a,             // Register A
-            5 => self.b,             // Register B
-            6 => self.c,             // Register C
-            _ => 0,                  // Operand 7 is reserved
-        }
-    }
-
-    /// Opcode 0 - ADV: Divide register A by 2^operand.
-    fn adv(&mut self, operand: u8) {
-        let denom = 2_u32.pow(self.resolve_operand(operand) as u32);
-        self.a /= denom;
-    }
-
-    /// Opcode 1 - BXL: XOR register B with the literal operand.
-    fn bxl(&mut self, operand: u8) {
-        self.b ^= operand as u32;
-    }
-
-    /// Opcode 2 - BST: Set register B to operand % 8.
-    fn bst(&mut self, operand: u8) {
-        self.b = self.resolve_operand(operand) % 8;
-    }
-
-    /// Opcode 3 - JNZ: Jump to operand if register A is not zero.
-    /// Returns `true` if a jump occurred.
-    fn jnz(&mut self, operand: u8) -> bool {
-        if self.a != 0 {
-            self.inst = self.resolve_operand(operand) as usize;
-            true
-        } else {
-            false
-        }
-    }
-
-    /// Opcode 4 - BXC: XOR register B with register C (ignores operand).
-    fn bxc(&mut self, _operand: u8) {
-        self.b ^= self.c;
-    }
-
-    /// Opcode 5 - OUT: Output operand % 8.
-    fn out(&mut self, operand: u8) {
-        let value = (self.resolve_operand(operand) % 8) as u8;
-        self.output.push(value as usize);
-    }
-
-    /// Opcode 6 - BDV: Divide register A by 2^operand and store the result in register B.
-    fn bdv(&mut self, operand: u8) {
-        let denom = 2_u32.pow(self.resolve_operand(operand));
-        self.b = self.a / denom;
-    }
-
-    /// Opcode 7 - CDV: Divide register A by 2^operand and store the result in register C.
-    fn cdv(&mut self, operand: u8) {
-        let denom = 2_u32.pow(self.resolve_operand(operand));
-        self.c = self.a / denom;
-    }
-
-    /// Parses a line containing a register value and returns the numeric value.
-    fn parse_value(line: &str) -> u32 {
-        line.split(':')
-            .nth(1) // Extract the part after the colon
-            .and_then(|val| val.trim().parse::<u32>().ok()) // Convert to u32
-            .unwrap_or(0) // Default to 0 if parsing fails
-    }
-
-    /// Parses the program input (comma-separated numbers) into a vector of `usize`.
-    fn parse_program(line: &str) -> Vec<usize> {
-        line.split(':')
-            .nth(1) // Extract the part after the colon
-            .unwrap_or("")
-            .split(',') // Split numbers by commas
-            .filter_map(|num| num.trim().parse::<usize>().ok()) // Parse numbers into usize
-            .collect()
-    }
 }
 
-impl Day17 {
-    /// Solves Part 1: Executes the program and returns the output as a string.
-    pub fn part_1(&self, input: &str) -> String {
-        let mut state = State::from_input(input);
-        state.run_program();
-        state.to_string()
-    }
-
-    /// Solves Part 2: Finds the lowest positive value for A that satisfies the program conditions.
-    pub fn part_2(&self, input: &str) -> String {
-        let state = State::from_input(input);
-        let program = state.input;
-        let mut a = 0;
-
-        // Extract values from the BXL (opcode 1) instructions
-        let [bxl1, bxl2] = program
-            .chunks(2)
-            .filter(|p| p[0] == 1) // Only consider BXL instructions
-            .map(|p| p[1]) // Extract operands
-            .take(2)
-            .collect::<Vec<usize>>()[..]
-        else {
-            panic!("Did not find two bxl commands")
-        };
-
-        for n in 1..=program.len() {
-            let target = program[program.len() - n..].to_vec();
-
-            let mut new_a = a << 3;
-            loop {
-                let mut digits = Vec::new();
-                let mut test_a = new_a;
-
-                while test_a != 0 {
-                    let mut b = test_a & 0x07;
-                    b = b ^ bxl1;
-                    let c = test_a >> b;
-                    b = b ^ c;
-                    b = b ^ bxl2;
-                    test_a >>= 3;
+impl Puzzle for Day17 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 17;
+    const TITLE: &'static str = "Chronospatial Computer";
 
-                    let test_digit = b & 0x07;
-                    if test_digit != *target.get(digits.len()).unwrap() {
-                        break;
-                    }
-                    digits.push(test_digit);
-                }
-
-                if digits == target {
-                    a = new_a;
-                    break;
-                }
-                new_a += 1;
-            }
-        }
-
-        a.to_string()
+    /// Solves Part 1: Executes the program and returns the output as a string.
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let (registers, program) = Self::parse_input(input)?;
+        let machine = Self::run_program(&program, registers)?;
+        Ok(Self::format_output(machine.output()).into())
+    }
+
+    /// Solves Part 2: Finds the lowest positive value for A that makes the program output
+    /// itself, searching backwards over the 3-bit chunks of A.
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let (registers, program) = Self::parse_input(input)?;
+        three_bit::find_register_a(
+            &program,
+            registers,
+            program.code(),
+            Self::MAX_CANDIDATE_STEPS,
+        )
+        .map(Answer::from)
+        .ok_or_else(|| SolveError::unsolvable("no value for register A reproduces the program"))
     }
 }
 
 mod test {
-
     #[test]
     /// Test for part 1 of Day17 for AoC 2024.
-    /// Ensures correct calculation of the sum of first and last digits in each line.
+    /// Runs the small examples and checks the registers and output they leave behind.
     fn test_aoc2024_day17_part_1() {
-        use crate::solutions::aoc2024::day17_impl::State;
+        use crate::solutions::aoc2024::Day17;
+        let run = |input: &str| {
+            let (registers, program) = Day17::parse_input(input).unwrap();
+            let machine = Day17::run_program(&program, registers).unwrap();
+            (machine.registers, Day17::format_output(machine.output()))
+        };
+
         let mut input = r#"
 Register A: 0
 Register B: 0
@@ -238,9 +122,7 @@ Register C: 9
 
 Program: 2,6
         "#;
-        let mut state = State::from_input(input);
-        state.run_program();
-        assert_eq!(state.b, 1);
+        assert_eq!(run(input).0.b, 1);
 
         input = r#"
 Register A: 10
@@ -249,9 +131,7 @@ Register C: 0
 
 Program: 5,0,5,1,5,4
         "#;
-        state = State::from_input(input);
-        state.run_program();
-        assert_eq!(state.to_string(), "0,1,2");
+        assert_eq!(run(input).1, "0,1,2");
 
         input = r#"
 Register A: 2024
@@ -260,10 +140,9 @@ Register C: 0
 
 Program: 0,1,5,4,3,0
         "#;
-        state = State::from_input(input);
-        state.run_program();
-        assert_eq!(state.a, 0);
-        assert_eq!(state.to_string(), "4,2,5,6,7,7,7,7,3,1,0");
+        let (registers, output) = run(input);
+        assert_eq!(registers.a, 0);
+        assert_eq!(output, "4,2,5,6,7,7,7,7,3,1,0");
 
         input = r#"
 Register A: 0
@@ -272,9 +151,7 @@ Register C: 0
 
 Program: 1,7
         "#;
-        state = State::from_input(input);
-        state.run_program();
-        assert_eq!(state.b, 26);
+        assert_eq!(run(input).0.b, 26);
 
         input = r#"
 Register A: 0
@@ -283,9 +160,7 @@ Register C: 43690
 
 Program: 4,0
         "#;
-        state = State::from_input(input);
-        state.run_program();
-        assert_eq!(state.b, 44354);
+        assert_eq!(run(input).0.b, 44354);
 
         input = r#"
 Register A: 729
@@ -294,23 +169,18 @@ Register C: 0
 
 Program: 0,1,5,4,3,0
         "#;
-        state = State::from_input(input);
-        state.run_program();
-        assert_eq!(state.a, 0);
-        assert_eq!(state.to_string(), "4,6,3,5,6,3,5,2,1,0");
+        let (registers, output) = run(input);
+        assert_eq!(registers.a, 0);
+        assert_eq!(output, "4,6,3,5,6,3,5,2,1,0");
     }
 
     #[test]
     /// Test for part 2 of Day17 for AoC 2024.
-    /// Verifies correct handling and sum of lines with spelled-out numbers.
+    /// Finds the value of A that makes the program output itself.
     fn test_aoc2024_day17_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day17;
-        let day17 = Day17 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day17 = Day17;
         let input = r#"
 Register A: 52042868
 Register B: 0
@@ -318,6 +188,16 @@ Register C: 0
 
 Program: 2,4,1,7,7,5,0,3,4,4,1,7,5,5,3,0
         "#;
-        assert_eq!(day17.part_2(input), "267265166222235"); // Asserts if the function output matches the expected result.
+        assert_eq!(day17.part_2(input).unwrap(), "267265166222235"); // Asserts if the function output matches the expected result.
+
+        // The example quine has no bxl instruction at all
+        let input = r#"
+Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0
+        "#;
+        assert_eq!(day17.part_2(input).unwrap(), "117440");
     }
 }
@@ -1,98 +1,224 @@
-use super::day18::Day18;
-use pathfinding::prelude::{bfs, Grid};
+use crate::aoc_solution::{unknown_param, Answer, ParamInfo, Puzzle, SolveError};
+use crate::trace::Trace;
+use crate::utils::grid::Grid;
+use crate::utils::parse;
+use crate::utils::point::Point;
+use crate::utils::search::{self, GridGraph};
+
+/// Day 18: RAM Run.
+pub struct Day18 {
+    /// The largest coordinate of the memory space; the exit is at `(size, size)`.
+    size: usize,
+    /// How many bytes have fallen in part 1.
+    bytes: usize,
+}
+
+/// The most cells a memory space of `(size + 1)²` cells may have, so it fits into memory.
+const MAX_CELLS: usize = 1 << 24;
+
+impl Default for Day18 {
+    fn default() -> Self {
+        Day18 {
+            size: 70,
+            bytes: 1024,
+        }
+    }
+}
 
 impl Day18 {
     /// Parses the input string into a vector of `(usize, usize)` tuples, representing the coordinates
     /// of falling bytes as specified in the input. Each line of the input corresponds to one coordinate pair.
-    fn parse_coordinates_to_grid(input: &str) -> Vec<(usize, usize)> {
-        input
-            .lines()
-            .filter(|s| !s.trim().is_empty()) // Ignore empty lines.
-            .filter_map(|line| line.trim().split_once(',')) // Split each line by ',' to separate x and y.
-            .filter_map(|(x, y)| Some((x.parse::<usize>().ok()?, y.parse::<usize>().ok()?))) // Parse x and y as usize.
+    fn parse_coordinates_to_grid(input: &str) -> Result<Vec<(usize, usize)>, SolveError> {
+        parse::lines(input)
+            .map(|line| {
+                // Split each line by ',' to separate x and y.
+                let (x, y) = line.split_once(",", "a coordinate pair like 5,4")?;
+                Ok((
+                    line.number(x, "a coordinate")?,
+                    line.number(y, "a coordinate")?,
+                ))
+            })
             .collect()
     }
 
+    /// Builds a memory space reaching to `goal` in which `bytes` are corrupted (`true`).
+    /// Bytes outside the memory space are ignored.
+    fn corrupt<'a>(
+        goal: (usize, usize),
+        bytes: impl IntoIterator<Item = &'a (usize, usize)>,
+    ) -> Grid<bool> {
+        let mut grid = Grid::new(goal.0 + 1, goal.1 + 1, false);
+        for &(x, y) in bytes {
+            let point = Point { x, y }.checked_cast();
+            if let Some(cell) = point.and_then(|p| grid.get_mut(p)) {
+                *cell = true;
+            }
+        }
+        grid
+    }
+
+    /// Uses BFS to find the number of steps from the top-left corner to `goal`.
+    fn shortest_path(grid: &Grid<bool>, goal: (usize, usize)) -> Option<u64> {
+        let start = Point::new(0, 0); // Starting position at the top-left corner.
+        if grid[start] {
+            return None; // A corrupted start cannot be left.
+        }
+
+        // Steps are only possible onto cells that are not corrupted.
+        let graph = GridGraph::new(grid, |_, &corrupted| !corrupted);
+        let goal = Point {
+            x: goal.0,
+            y: goal.1,
+        }
+        .checked_cast()?;
+        search::bfs(&graph, start).distance(&goal)
+    }
+
+    /// Draws the memory space like the puzzle description: corrupted bytes as `#`, safe
+    /// cells as `.` and, `with_path`, a shortest path to `goal` as `O`.
+    fn render(grid: &Grid<bool>, goal: (usize, usize), with_path: bool) -> String {
+        let mut map = Grid::new(grid.width(), grid.height(), '.');
+        for (p, &corrupted) in grid.iter() {
+            if corrupted {
+                map[p] = '#';
+            }
+        }
+        if with_path && !grid[Point::new(0, 0)] {
+            let graph = GridGraph::new(grid, |_, &corrupted| !corrupted);
+            let goal = Point {
+                x: goal.0,
+                y: goal.1,
+            }
+            .checked_cast();
+            let path = goal.and_then(|goal| search::bfs(&graph, Point::new(0, 0)).path_to(&goal));
+            for p in path.unwrap_or_default() {
+                map[p] = 'O';
+            }
+        }
+        map.to_string()
+    }
+}
+
+impl Puzzle for Day18 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 18;
+    const TITLE: &'static str = "RAM Run";
+    const PARAMS: &'static [ParamInfo] = &[
+        ParamInfo {
+            name: "size",
+            default: "70",
+            description: "the largest coordinate of the memory space",
+            choices: &[],
+        },
+        ParamInfo {
+            name: "bytes",
+            default: "1024",
+            description: "how many bytes have fallen in part 1",
+            choices: &[],
+        },
+    ];
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), SolveError> {
+        match name {
+            "size" => self.size = Self::PARAMS[0].parse(value)?,
+            "bytes" => self.bytes = Self::PARAMS[1].parse(value)?,
+            _ => return Err(unknown_param::<Self>(name)),
+        }
+        match self
+            .size
+            .checked_add(1)
+            .and_then(|side| side.checked_mul(side))
+        {
+            Some(cells) if cells <= MAX_CELLS => Ok(()),
+            _ => Err(SolveError::invalid_param(format!(
+                "the memory space must not exceed {} cells",
+                MAX_CELLS
+            ))),
+        }
+    }
+
     /// Solves part 1 of the problem:
     /// Calculates the minimum number of steps required to move from the top-left corner `(0,0)`
-    /// to the bottom-right corner `(6,6)` or `(70,70)` based on the grid size, simulating corrupted bytes.
-    pub fn part_1(&self, input: &str) -> String {
-        let bytes;
-        let coords = Self::parse_coordinates_to_grid(input); // Parse falling bytes from input.
-        let start = (0usize, 0usize); // Starting position at the top-left corner.
-        let goal;
-
-        // Determine grid size and how many bytes to simulate based on input length.
-        if input.len() < 1024 {
-            goal = (6, 6); // Example case for smaller input.
-            bytes = coords.iter().take(12).cloned(); // Simulate first 12 bytes.
-        } else {
-            goal = (70, 70); // Larger grid for full input.
-            bytes = coords.iter().take(1024).cloned(); // Simulate first 1024 bytes.
-        }
+    /// to the bottom-right corner `(size,size)` after the first `bytes` bytes have fallen.
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        self.trace_part_1(input, &mut Trace::disabled())
+    }
+
+    /// Records the memory space after every fallen byte and finally the shortest path.
+    fn trace_part_1(&self, input: &str, trace: &mut Trace) -> Result<Answer, SolveError> {
+        let coords = Self::parse_coordinates_to_grid(input)?; // Parse falling bytes from input.
 
-        let mut grid = Grid::new(goal.0 + 1, goal.1 + 1); // Create a grid with size (goal.x + 1, goal.y + 1).
-        grid.fill(); // Initially, mark all positions as accessible.
+        let goal = (self.size, self.size);
+        let bytes = &coords[..coords.len().min(self.bytes)]; // Only the first bytes have fallen.
 
-        // Mark positions corrupted by falling bytes.
-        for byte in bytes {
-            grid.remove_vertex(byte);
+        if trace.is_recording() {
+            for count in 0..=bytes.len() {
+                if let Some(&(x, y)) = count.checked_sub(1).map(|last| &bytes[last]) {
+                    trace.event(format_args!("a byte falls at {},{}", x, y));
+                }
+                trace.frame(count, || {
+                    Self::render(&Self::corrupt(goal, &bytes[..count]), goal, false)
+                });
+            }
         }
 
-        // Define a closure to calculate valid neighbors for BFS traversal.
-        let neighbors = |&(x, y): &(usize, usize)| -> Vec<(usize, usize)> {
-            grid.neighbours((x, y)) // Use the grid's helper to find accessible neighbors.
-        };
+        let grid = Self::corrupt(goal, bytes); // Mark positions corrupted by falling bytes.
 
-        // Use BFS to find the shortest path from the start to the goal.
-        let path = bfs(&start, neighbors, |&p| p == goal).unwrap();
+        let steps = Self::shortest_path(&grid, goal)
+            .ok_or_else(|| SolveError::unsolvable("the exit is not reachable"))?;
+        trace.event(format_args!("the shortest path takes {} steps", steps));
+        trace.frame(bytes.len(), || Self::render(&grid, goal, true));
 
-        (path.len() - 1).to_string() // Return the path length minus one (steps count).
+        Ok(steps.into()) // Return the number of steps.
     }
 
     /// Solves part 2 of the problem:
     /// Determines the first byte that makes the exit unreachable from the starting point.
-    pub fn part_2(&self, input: &str) -> String {
-        let bytes = Self::parse_coordinates_to_grid(input); // Parse falling bytes from input.
-        let start = (0usize, 0usize); // Starting position at the top-left corner.
-        let goal;
-
-        // Determine the goal based on input length.
-        if input.len() < 1024 {
-            goal = (6, 6); // Example case for smaller input.
-        } else {
-            goal = (70, 70); // Larger grid for full input.
-        }
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        self.trace_part_2(input, &mut Trace::disabled())
+    }
 
-        let mut grid = Grid::new(goal.0 + 1, goal.1 + 1); // Create the grid.
+    /// Records the memory space at every step of the binary search, with the shortest path
+    /// while there is one.
+    fn trace_part_2(&self, input: &str, trace: &mut Trace) -> Result<Answer, SolveError> {
+        let bytes = Self::parse_coordinates_to_grid(input)?; // Parse falling bytes from input.
 
-        let mut possible = 0; // Index of the last byte where the path was still reachable.
-        let mut impossible = bytes.len() - 1; // Index of the first byte where the path becomes unreachable.
+        let goal = (self.size, self.size);
 
-        // Binary search to find the critical byte that blocks the path.
-        while impossible > (possible + 1) {
-            let midpoint = (possible + impossible) / 2; // Check the midpoint of the range.
-            let bytes = bytes.iter().take(midpoint).collect::<Vec<_>>(); // Simulate bytes up to midpoint.
-
-            grid.fill(); // Reset the grid to be fully accessible.
+        // Checks if the exit is reachable after the first `count` bytes have fallen.
+        let mut reachable_after = |count: usize| {
+            let grid = Self::corrupt(goal, &bytes[..count]);
+            let reachable = Self::shortest_path(&grid, goal).is_some();
+            trace.event(format_args!(
+                "after {} bytes the exit is {}",
+                count,
+                if reachable { "reachable" } else { "cut off" }
+            ));
+            trace.frame(count, || Self::render(&grid, goal, reachable));
+            reachable
+        };
 
-            // Mark positions corrupted by falling bytes.
-            for byte in bytes {
-                grid.remove_vertex(*byte);
-            }
+        if !reachable_after(0) || reachable_after(bytes.len()) {
+            return Err(SolveError::unsolvable(
+                "no single byte cuts the exit off from the start",
+            ));
+        }
 
-            // Define a closure to calculate valid neighbors for BFS traversal.
-            let neighbors =
-                |&(x, y): &(usize, usize)| -> Vec<(usize, usize)> { grid.neighbours((x, y)) };
+        let mut possible = 0; // Number of bytes after which the path is still reachable.
+        let mut impossible = bytes.len(); // Number of bytes after which the path is unreachable.
 
-            // Check if a path from start to goal exists with the current set of bytes.
-            if let Some(_) = bfs(&start, neighbors, |&p| p == goal) {
+        // Binary search to find the critical byte that blocks the path.
+        while impossible > (possible + 1) {
+            let midpoint = (possible + impossible) / 2; // Check the midpoint of the range.
+            if reachable_after(midpoint) {
                 possible = midpoint; // Path exists; move `possible` forward.
             } else {
                 impossible = midpoint; // Path blocked; move `impossible` back.
             }
         }
-        format!("{},{}", bytes[possible].0, bytes[possible].1) // Return the critical byte coordinates.
+
+        // The byte at index `possible` is the first one that blocks the path.
+        Ok(format!("{},{}", bytes[possible].0, bytes[possible].1).into())
     }
 }
 
@@ -101,13 +227,11 @@ mod test {
     /// Test for part 1 of Day 18 for Advent of Code 2024.
     /// Verifies the correct number of steps from start to goal with initial corrupted bytes.
     fn test_aoc2024_day18_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day18;
-        let day18 = Day18 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let mut day18 = Day18::default();
+        day18.set_param("size", "6").unwrap(); // The example uses a smaller memory space.
+        day18.set_param("bytes", "12").unwrap();
         let input = r#"
 5,4
 4,2
@@ -135,20 +259,17 @@ mod test {
 1,6
 2,0
         "#;
-        assert_eq!(day18.part_1(input), "22"); // Expected result: 22 steps.
+        assert_eq!(day18.part_1(input).unwrap(), "22"); // Expected result: 22 steps.
     }
 
     #[test]
     /// Test for part 2 of Day 18 for Advent of Code 2024.
     /// Verifies the correct detection of the first critical byte that blocks the path to the goal.
     fn test_aoc2024_day18_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day18;
-        let day18 = Day18 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let mut day18 = Day18::default();
+        day18.set_param("size", "6").unwrap(); // The example uses a smaller memory space.
         let input = r#"
 5,4
 4,2
@@ -176,6 +297,43 @@ mod test {
 1,6
 2,0
         "#;
-        assert_eq!(day18.part_2(input), "6,1"); // Expected result: byte at (6,1).
+        assert_eq!(day18.part_2(input).unwrap(), "6,1"); // Expected result: byte at (6,1).
+    }
+
+    #[test]
+    /// The trace of part 1 shows the bytes falling one by one and then the shortest path.
+    fn test_aoc2024_day18_trace() {
+        use crate::aoc_solution::Puzzle;
+        use crate::solutions::aoc2024::Day18;
+        use crate::trace::Trace;
+        let mut day18 = Day18::default();
+        day18.set_param("size", "6").unwrap(); // The example uses a smaller memory space.
+        day18.set_param("bytes", "12").unwrap();
+        let input = "5,4\n4,2\n4,5\n3,0\n2,1\n6,3\n2,4\n1,5\n0,6\n3,3\n2,6\n5,1\n1,2";
+        let mut trace = Trace::new(usize::MAX);
+        assert_eq!(day18.trace_part_1(input, &mut trace).unwrap(), "22");
+
+        let frames = trace.frames();
+        assert_eq!(frames.len(), 14); // Before any byte, after each of 12 bytes, and the path
+        assert_eq!(frames[1].grid[4], ".....#.");
+        assert_eq!(frames[1].events, ["a byte falls at 5,4"]);
+        assert_eq!(frames[12].grid.concat().matches('#').count(), 12);
+        assert_eq!(frames[13].grid.concat().matches('O').count(), 23);
+        assert_eq!(frames[13].events, ["the shortest path takes 22 steps"]);
+    }
+
+    #[test]
+    /// Memory spaces too large to allocate are refused instead of overflowing.
+    fn test_aoc2024_day18_size() {
+        use crate::aoc_solution::{Puzzle, SolveError};
+        use crate::solutions::aoc2024::Day18;
+        let mut day18 = Day18::default();
+        for size in ["18446744073709551615", "4294967296", "4096"] {
+            assert!(matches!(
+                day18.set_param("size", size),
+                Err(SolveError::InvalidParam(_))
+            ));
+        }
+        assert_eq!(day18.set_param("size", "4095"), Ok(()));
     }
 }
@@ -1,6 +1,11 @@
-use super::day19::Day19;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::parse;
 use hashbrown::HashSet; // Efficient hash-based set for storing towel patterns.
 
+/// Day 19: Linen Layout.
+#[derive(Default)]
+pub struct Day19;
+
 impl Day19 {
     /// Parses the input into two components:
     /// - A set of available towel patterns.
@@ -13,26 +18,34 @@ impl Day19 {
     /// A tuple `(HashSet<String>, Vec<String>)` where:
     /// - The first element is a set of towel patterns.
     /// - The second element is a vector of desired designs.
-    fn parse_input(input: &str) -> (HashSet<String>, Vec<String>) {
+    ///
+    /// Designs may only contain ASCII characters, so they can be sliced byte by byte.
+    fn parse_input(input: &str) -> Result<(HashSet<String>, Vec<String>), SolveError> {
         // Split the input into lines and ignore empty ones.
-        let mut lines = input
-            .lines()
-            .map(|line| line.trim())
-            .filter(|line| !line.is_empty());
+        let mut lines = parse::lines(input);
 
         // Parse the first line containing towel patterns, separated by commas.
         let towels_line = lines
             .next()
-            .expect("Input must contain at least one line for towels.");
+            .ok_or_else(|| SolveError::parse(1, 1, "expected a line of towel patterns"))?;
         let towels: HashSet<String> = towels_line
+            .text
             .split(',')
             .map(|t| t.trim().to_string()) // Clean up and convert to String.
             .collect();
 
         // Parse the remaining lines as desired designs.
-        let patterns: Vec<String> = lines.map(|line| line.to_string()).collect();
-
-        (towels, patterns)
+        let patterns = lines
+            .map(|line| match line.text.find(|c: char| !c.is_ascii()) {
+                Some(offset) => Err(line.error_at(
+                    &line.text[offset..],
+                    "designs may only contain ASCII characters",
+                )),
+                None => Ok(line.text.to_string()),
+            })
+            .collect::<Result<Vec<String>, _>>()?;
+
+        Ok((towels, patterns))
     }
 
     /// Checks if a given pattern can be formed using the available towel patterns.
@@ -61,22 +74,6 @@ impl Day19 {
         dp[pattern.len()] // The last entry indicates if the full pattern is feasible.
     }
 
-    /// Solves Part 1: Counts how many desired designs can be formed.
-    ///
-    /// # Arguments
-    /// - `input`: The puzzle input as a string.
-    ///
-    /// # Returns
-    /// A string representing the count of feasible designs.
-    pub fn part_1(&self, input: &str) -> String {
-        let (towels, patterns) = Self::parse_input(input);
-        patterns
-            .iter()
-            .filter(|pattern| Self::can_form_pattern(&towels, pattern))
-            .count()
-            .to_string()
-    }
-
     /// Counts the number of ways a given pattern can be formed using the available towel patterns.
     ///
     /// # Arguments
@@ -84,8 +81,8 @@ impl Day19 {
     /// - `pattern`: The desired design to check.
     ///
     /// # Returns
-    /// The number of distinct ways the pattern can be formed.
-    fn count_combinations(towels: &HashSet<String>, pattern: &str) -> u64 {
+    /// The number of distinct ways the pattern can be formed, or `None` if it overflows a u64.
+    fn count_combinations(towels: &HashSet<String>, pattern: &str) -> Option<u64> {
         // Dynamic programming array to track the number of ways to form substrings.
         let mut dp = vec![0u64; pattern.len() + 1];
         dp[0] = 1; // Base case: One way to form an empty pattern.
@@ -95,12 +92,34 @@ impl Day19 {
                 // If the substring pattern[j..i] matches a towel pattern,
                 // add the number of ways to form the prefix ending at j.
                 if towels.contains(&pattern[j..i]) {
-                    dp[i] += dp[j];
+                    dp[i] = dp[i].checked_add(dp[j])?;
                 }
             }
         }
 
-        dp[pattern.len()] // The last entry contains the total combinations.
+        Some(dp[pattern.len()]) // The last entry contains the total combinations.
+    }
+}
+
+impl Puzzle for Day19 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 19;
+    const TITLE: &'static str = "Linen Layout";
+
+    /// Solves Part 1: Counts how many desired designs can be formed.
+    ///
+    /// # Arguments
+    /// - `input`: The puzzle input as a string.
+    ///
+    /// # Returns
+    /// The count of feasible designs.
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let (towels, patterns) = Self::parse_input(input)?;
+        let count = patterns
+            .iter()
+            .filter(|pattern| Self::can_form_pattern(&towels, pattern))
+            .count();
+        Ok(count.into())
     }
 
     /// Solves Part 2: Calculates the sum of all possible arrangements for feasible designs.
@@ -109,14 +128,16 @@ impl Day19 {
     /// - `input`: The puzzle input as a string.
     ///
     /// # Returns
-    /// A string representing the total number of arrangements.
-    pub fn part_2(&self, input: &str) -> String {
-        let (towels, patterns) = Self::parse_input(input);
+    /// The total number of arrangements.
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let (towels, patterns) = Self::parse_input(input)?;
         patterns
             .iter()
-            .map(|pattern| Self::count_combinations(&towels, pattern))
-            .sum::<u64>()
-            .to_string()
+            .try_fold(0u64, |sum, pattern| {
+                sum.checked_add(Self::count_combinations(&towels, pattern)?)
+            })
+            .map(Answer::from)
+            .ok_or_else(|| SolveError::unsolvable("the number of arrangements overflows a u64"))
     }
 }
 
@@ -124,13 +145,9 @@ mod test {
     #[test]
     /// Test for Part 1: Checks if the number of feasible designs is correct.
     fn test_aoc2024_day19_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day19;
-        let day19 = Day19 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day19 = Day19;
         let input = r#"
 r, wr, b, g, bwu, rb, gb, br
 
@@ -143,19 +160,15 @@ bwurrg
 brgr
 bbrgwb
         "#;
-        assert_eq!(day19.part_1(input), "6"); // Verifies Part 1 output.
+        assert_eq!(day19.part_1(input).unwrap(), "6"); // Verifies Part 1 output.
     }
 
     #[test]
     /// Test for Part 2: Checks if the total arrangements are calculated correctly.
     fn test_aoc2024_day19_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day19;
-        let day19 = Day19 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day19 = Day19;
         let input = r#"
 r, wr, b, g, bwu, rb, gb, br
 
@@ -168,6 +181,6 @@ bwurrg
 brgr
 bbrgwb
         "#;
-        assert_eq!(day19.part_2(input), "16"); // Verifies Part 2 output.
+        assert_eq!(day19.part_2(input).unwrap(), "16"); // Verifies Part 2 output.
     }
 }
@@ -1,57 +1,46 @@
-use super::day20::Day20;
+use crate::aoc_solution::{unknown_param, Answer, ParamInfo, Puzzle, SolveError};
+use crate::utils::grid::Grid;
 use crate::utils::point::Point;
-use pathfinding::prelude::{bfs, Grid};
+use crate::utils::search::{self, GridGraph};
+
+/// Day 20: Race Condition.
+pub struct Day20 {
+    /// The fewest picoseconds a cheat must save to be counted.
+    saving: u32,
+}
+
+impl Default for Day20 {
+    fn default() -> Self {
+        Day20 { saving: 100 }
+    }
+}
+
+/// The racetrack with its start and end tiles.
+type Track = (Grid<char>, Point<i32>, Point<i32>);
 
 impl Day20 {
     /// Parses the map and identifies the grid, start (S), and end (E) points.
-    fn parse_map(map: &str) -> (Grid, Point<i16>, Point<i16>) {
-        let lines: Vec<&str> = map.lines().filter(|line| !line.trim().is_empty()).collect();
-        let height = lines.len();
-        let width = lines
-            .first()
-            .expect("Map must have at least one line")
-            .len();
-
-        let mut grid = Grid::new(width, height);
-        let mut start = Point { x: 0, y: 0 };
-        let mut end = Point { x: 0, y: 0 };
-
-        for (y, line) in lines.iter().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let point = Point {
-                    x: x as i16,
-                    y: y as i16,
-                };
-                match c {
-                    'S' => {
-                        start = point;
-                        grid.add_vertex((x, y));
-                    }
-                    'E' => {
-                        end = point;
-                        grid.add_vertex((x, y));
-                    }
-                    '#' => {} // Wall, skip adding.
-                    _ => {
-                        grid.add_vertex((x, y));
-                    }
-                }
-            }
-        }
-
-        (grid, start, end)
+    fn parse_map(map: &str) -> Result<Track, SolveError> {
+        let grid = Grid::parse(map, Ok)?;
+
+        let start = grid
+            .find(|&c| c == 'S')
+            .ok_or_else(|| SolveError::unsolvable("the map has no start"))?;
+        let end = grid
+            .find(|&c| c == 'E')
+            .ok_or_else(|| SolveError::unsolvable("the map has no end"))?;
+        Ok((grid, start, end))
     }
 
     /// Finds valid cheats based on the condition function.
-    fn find_cheats<F>(path: &[Point<i16>], level: u32, condition: F) -> u32
+    fn find_cheats<F>(path: &[Point<i32>], level: u32, condition: F) -> u32
     where
-        F: Fn(i16, usize, u32) -> bool,
+        F: Fn(i32, usize, u32) -> bool,
     {
         let mut count = 0u32;
 
         for (i, &position) in path.iter().enumerate() {
-            for j in (i + 3)..path.len() {
-                let target = path[j];
+            for (j, &target) in path.iter().enumerate().skip(i + 3) {
                 let steps = j - i;
                 let dist = position.manhattan(target);
 
@@ -65,48 +54,53 @@ impl Day20 {
     }
 
     /// Generalized solution for both parts of the puzzle.
-    pub fn solve<F>(input: &str, level_fn: fn(usize) -> u32, condition: F) -> String
+    pub fn solve<F>(input: &str, level: u32, condition: F) -> Result<Answer, SolveError>
     where
-        F: Fn(i16, usize, u32) -> bool,
+        F: Fn(i32, usize, u32) -> bool,
     {
-        let (map, start, end) = Self::parse_map(input);
-
-        let neighbors = |&p: &Point<i16>| -> Vec<Point<i16>> {
-            map.neighbours((p.x as usize, p.y as usize))
-                .into_iter()
-                .map(|(x, y)| Point {
-                    x: x as i16,
-                    y: y as i16,
-                })
-                .collect()
-        };
-
-        if let Some(path) = bfs(&start, neighbors, |&p| p == end) {
-            let level = level_fn(input.len());
-            return Self::find_cheats(&path, level, condition).to_string();
-        }
+        let (map, start, end) = Self::parse_map(input)?;
+
+        // Every tile but a wall is part of the track
+        let track = GridGraph::new(&map, |_, &to| to != '#');
 
-        "".to_string()
+        let path = search::bfs(&track, start)
+            .path_to(&end)
+            .ok_or_else(|| SolveError::unsolvable("there is no path from the start to the end"))?;
+        Ok(Self::find_cheats(&path, level, condition).into())
+    }
+}
+
+impl Puzzle for Day20 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 20;
+    const TITLE: &'static str = "Race Condition";
+    const PARAMS: &'static [ParamInfo] = &[ParamInfo {
+        name: "saving",
+        default: "100",
+        description: "the fewest picoseconds a cheat must save",
+        choices: &[],
+    }];
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), SolveError> {
+        match name {
+            "saving" => self.saving = Self::PARAMS[0].parse(value)?,
+            _ => return Err(unknown_param::<Self>(name)),
+        }
+        Ok(())
     }
 
     /// Part 1 solution: finds cheats saving 2 picoseconds.
-    pub fn part_1(&self, input: &str) -> String {
-        Self::solve(
-            input,
-            |len| if len < 1000 { 20 } else { 100 },
-            |dist, steps, level| dist == 2 && (steps as u32 - 2 >= level),
-        )
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        Self::solve(input, self.saving, |dist, steps, level| {
+            dist == 2 && (steps as u32 - 2 >= level)
+        })
     }
 
     /// Part 2 solution: finds cheats with updated rules allowing up to 20 picoseconds.
-    pub fn part_2(&self, input: &str) -> String {
-        Self::solve(
-            input,
-            |len| if len < 1000 { 50 } else { 100 },
-            |dist, steps, level| {
-                dist <= 20 && dist < steps as i16 && (steps as u32 - dist as u32 >= level)
-            },
-        )
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        Self::solve(input, self.saving, |dist, steps, level| {
+            dist <= 20 && (dist as usize) < steps && (steps as u32 - dist as u32 >= level)
+        })
     }
 }
 
@@ -114,13 +108,10 @@ mod test {
     #[test]
     /// Test for Part 1.
     fn test_aoc2024_day20_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day20;
-        let day20 = Day20 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let mut day20 = Day20::default();
+        day20.set_param("saving", "20").unwrap(); // Only few cheats in the example save 100.
         let input = r#"
 ###############
 #...#...#.....#
@@ -138,19 +129,16 @@ mod test {
 #...#...#...###
 ###############
         "#;
-        assert_eq!(day20.part_1(input), "5");
+        assert_eq!(day20.part_1(input).unwrap(), "5");
     }
 
     #[test]
     /// Test for Part 2.
     fn test_aoc2024_day20_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day20;
-        let day20 = Day20 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let mut day20 = Day20::default();
+        day20.set_param("saving", "50").unwrap(); // Only few cheats in the example save 100.
         let input = r#"
 ###############
 #...#...#.....#
@@ -168,6 +156,6 @@ mod test {
 #...#...#...###
 ###############
         "#;
-        assert_eq!(day20.part_2(input), "285");
+        assert_eq!(day20.part_2(input).unwrap(), "285");
     }
 }
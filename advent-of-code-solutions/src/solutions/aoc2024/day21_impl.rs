@@ -1,16 +1,39 @@
-use super::day21::Day21;
-use crate::utils::point::Point;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::parse;
+use crate::utils::point::{Direction, Point};
 use hashbrown::HashMap;
 use std::cmp;
 
+/// Day 21: Keypad Conundrum.
+#[derive(Default)]
+pub struct Day21;
+
 impl Day21 {
     /// Parses the input into a vector of codes. Each code corresponds to a sequence of
     /// numbers and letters that the numeric keypad robot must type.
-    fn parse_codes(input: &str) -> Vec<String> {
-        input
-            .lines()
-            .filter(|line| !line.trim().is_empty()) // Skip empty lines.
-            .map(|line| line.trim().to_string()) // Trim whitespace and convert to String.
+    /// Returns each code together with its numeric part.
+    fn parse_codes(input: &str) -> Result<Vec<(String, u64)>, SolveError> {
+        parse::lines(input)
+            .map(|line| {
+                let code = line.text;
+
+                // Only keys of the numeric keypad can be typed.
+                if let Some(offset) = code.find(|c: char| !c.is_ascii_digit() && c != 'A') {
+                    return Err(line.error_at(&code[offset..], "expected 0-9 or A"));
+                }
+
+                // The numeric part consists of all digits, ignoring leading zeros.
+                let digits: String = code.chars().filter(char::is_ascii_digit).collect();
+                let numeric = if digits.is_empty() {
+                    0
+                } else {
+                    digits
+                        .parse::<u64>()
+                        .map_err(|_| line.error("the numeric part is too large"))?
+                };
+
+                Ok((code.to_string(), numeric)) // Convert to String.
+            })
             .collect()
     }
 
@@ -29,7 +52,7 @@ impl Day21 {
             '3' => Point::new(2, 2),
             '0' => Point::new(3, 1),
             'A' => Point::new(3, 2),
-            _ => panic!(), // Panic if an invalid key is encountered.
+            _ => unreachable!("the parser only accepts keys of the numeric keypad"),
         }
     }
 
@@ -42,7 +65,7 @@ impl Day21 {
             '<' => Point::new(1, 0),
             'v' => Point::new(1, 1),
             '>' => Point::new(1, 2),
-            _ => panic!(), // Panic if an invalid key is encountered.
+            _ => unreachable!("only moves and 'A' are typed on the directional keypad"),
         }
     }
 
@@ -55,28 +78,23 @@ impl Day21 {
         let (dx, dy) = (offset.x, offset.y);
 
         // Determine the number of vertical (up/down) and horizontal (left/right) steps required.
-        let vertical_steps = dx.abs() as usize;
-        let horizontal_steps = dy.abs() as usize;
+        let vertical_steps = dx.unsigned_abs() as usize;
+        let horizontal_steps = dy.unsigned_abs() as usize;
 
         // Determine the direction of vertical and horizontal moves.
-        let vertical_move = if dx > 0 { '^' } else { 'v' };
-        let horizontal_move = if dy > 0 { '<' } else { '>' };
+        let vertical_move = if dx > 0 { Direction::N } else { Direction::S };
+        let horizontal_move = if dy > 0 { Direction::W } else { Direction::E };
 
         // Create repeated move characters for the required steps.
-        let vertical_moves = vec![vertical_move; vertical_steps];
-        let horizontal_moves = vec![horizontal_move; horizontal_steps];
+        let arrow = |direction: Direction| direction.arrow().expect("moves are orthogonal");
+        let vertical_moves = vec![arrow(vertical_move); vertical_steps];
+        let horizontal_moves = vec![arrow(horizontal_move); horizontal_steps];
 
         // Combine the moves in the specified order.
         let mut moves: Vec<_> = if horizontal_first {
-            horizontal_moves
-                .into_iter()
-                .chain(vertical_moves.into_iter())
-                .collect()
+            horizontal_moves.into_iter().chain(vertical_moves).collect()
         } else {
-            vertical_moves
-                .into_iter()
-                .chain(horizontal_moves.into_iter())
-                .collect()
+            vertical_moves.into_iter().chain(horizontal_moves).collect()
         };
 
         // Add 'A' to press the button at the target position.
@@ -115,7 +133,7 @@ impl Day21 {
         }
 
         // Recursive case: Traverse the directional keypad and calculate distances.
-        let mut total_cost = 0;
+        let mut total_cost: u64 = 0;
         let mut current_pos = Self::control_pad('A'); // Start at the 'A' button.
 
         for c in moves {
@@ -148,11 +166,13 @@ impl Day21 {
 
     /// Calculates the total sequence length needed to type a code on the numeric keypad.
     /// Traverses the entire chain of robots recursively, updating the position at each step.
-    fn calculate_chain_traversal(sequence: &str, steps: usize) -> u64 {
+    /// The result is the complexity: the sequence length times the numeric part of the code.
+    /// Returns `None` if the complexity overflows a u64.
+    fn calculate_chain_traversal(sequence: &str, numeric: u64, steps: usize) -> Option<u64> {
         let mut cache = HashMap::new();
 
         let mut current_position = Self::num_pad('A'); // Starting at 'A' on the numeric keypad.
-        let mut total_cost = 0;
+        let mut total_cost: u64 = 0;
 
         // Iterate over each character in the sequence.
         for c in sequence.chars() {
@@ -173,32 +193,41 @@ impl Day21 {
                 )
             };
 
-            total_cost += steps; // Add the steps to the total.
+            total_cost = total_cost.checked_add(steps)?; // Add the steps to the total.
         }
 
-        total_cost * sequence[0..3].parse::<u64>().unwrap()
+        total_cost.checked_mul(numeric)
     }
 
-    /// Solution for Part 1: Calculates the sum of complexities for the five codes
-    /// with a chain length of 2 robots.
-    pub fn part_1(&self, input: &str) -> String {
-        let codes = Self::parse_codes(input);
+    /// Sums the complexities of all codes for a chain of `steps` directional keypad robots.
+    fn sum_of_complexities(codes: &[(String, u64)], steps: usize) -> Result<Answer, SolveError> {
         codes
             .iter()
-            .map(|code| Self::calculate_chain_traversal(code, 2))
-            .sum::<u64>()
-            .to_string()
+            .try_fold(0u64, |sum, (code, numeric)| {
+                sum.checked_add(Self::calculate_chain_traversal(code, *numeric, steps)?)
+            })
+            .map(Answer::from)
+            .ok_or_else(|| SolveError::unsolvable("the sum of complexities overflows a u64"))
+    }
+}
+
+impl Puzzle for Day21 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 21;
+    const TITLE: &'static str = "Keypad Conundrum";
+
+    /// Solution for Part 1: Calculates the sum of complexities for the five codes
+    /// with a chain length of 2 robots.
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let codes = Self::parse_codes(input)?;
+        Self::sum_of_complexities(&codes, 2)
     }
 
     /// Solution for Part 2: Calculates the sum of complexities for the five codes
     /// with a chain length of 25 robots.
-    pub fn part_2(&self, input: &str) -> String {
-        let codes = Self::parse_codes(input);
-        codes
-            .iter()
-            .map(|code| Self::calculate_chain_traversal(code, 25))
-            .sum::<u64>()
-            .to_string()
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let codes = Self::parse_codes(input)?;
+        Self::sum_of_complexities(&codes, 25)
     }
 }
 
@@ -207,14 +236,10 @@ mod test {
     /// Tests Part 1 of Day 21. Verifies the correct calculation of complexities for
     /// the given codes with a chain of 2 robots.
     fn test_aoc2024_day21_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day21;
 
-        let day21 = Day21 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day21 = Day21;
 
         // Input representing the five codes to process.
         let input = r#"
@@ -226,21 +251,17 @@ mod test {
         "#;
 
         // Ensure the output matches the expected sum of complexities.
-        assert_eq!(day21.part_1(input), "126384");
+        assert_eq!(day21.part_1(input).unwrap(), "126384");
     }
 
     #[test]
     /// Tests Part 2 of Day 21. Verifies correct handling of a longer chain (25 robots)
     /// and accurate complexity calculations for the same codes.
     fn test_aoc2024_day21_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day21;
 
-        let day21 = Day21 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day21 = Day21;
 
         // Input representing the five codes to process.
         let input = r#"
@@ -252,6 +273,6 @@ mod test {
         "#;
 
         // Ensure the output matches the expected sum of complexities.
-        assert_eq!(day21.part_2(input), "154115708116294");
+        assert_eq!(day21.part_2(input).unwrap(), "154115708116294");
     }
 }
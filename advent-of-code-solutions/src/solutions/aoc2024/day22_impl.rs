@@ -1,13 +1,17 @@
-use super::day22::Day22;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::parse;
+
+/// Day 22: Monkey Market.
+#[derive(Default)]
+pub struct Day22;
 
 impl Day22 {
-    /// Parses a string input to extract valid numbers (u32) from each line.
-    /// Lines that fail to parse (e.g., non-numeric content) are ignored.
-    fn parse_numbers(input: &str) -> Vec<u32> {
-        input
-            .lines()
-            .filter_map(|line| line.trim().parse::<u32>().ok()) // Attempt to parse each line, ignoring invalid ones
-            .collect() // Collect the successfully parsed numbers into a vector
+    /// Parses a string input to extract one number (u32) from each non-empty line.
+    /// Lines that fail to parse (e.g., non-numeric content) are reported as errors.
+    fn parse_numbers(input: &str) -> Result<Vec<u32>, SolveError> {
+        parse::lines(input)
+            .map(|line| line.number(line.text, "a secret number")) // Parse each line
+            .collect() // Collect the parsed numbers into a vector
     }
 
     /// Computes the next secret number in the pseudorandom sequence.
@@ -54,11 +58,17 @@ impl Day22 {
         }
         secrets
     }
+}
+
+impl Puzzle for Day22 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 22;
+    const TITLE: &'static str = "Monkey Market";
 
     /// Part 1: Calculates the sum of the 2000th secret number in each buyer's sequence.
     /// Parses the input to obtain initial secrets, computes sequences, and sums the results.
-    pub fn part_1(&self, input: &str) -> String {
-        let initial_secrets = Self::parse_numbers(input); // Extract initial secrets from input
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let initial_secrets = Self::parse_numbers(input)?; // Extract initial secrets from input
         let mut total_sum: u64 = 0; // Use u64 to handle potential overflow during summation
 
         for secret in initial_secrets {
@@ -66,13 +76,13 @@ impl Day22 {
             total_sum += *secrets.last().unwrap_or(&0) as u64; // Add the last number (2000th secret) to the total sum
         }
 
-        total_sum.to_string() // Convert the result to a string for output
+        Ok(total_sum.into()) // Convert the result to an Answer for output
     }
 
     /// Part 2: Calculates the sum of sale prices based on unique delta sequences.
     /// Finds unique 4-element delta sequences in modulo-10 secret numbers, computes indices, and sums prices.
-    pub fn part_2(&self, input: &str) -> String {
-        let initial_secrets = Self::parse_numbers(input); // Parse initial secrets from input
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        let initial_secrets = Self::parse_numbers(input)?; // Parse initial secrets from input
         let mut sale_prices = vec![0u32; 130321]; // Array to store sales prices for each delta sequence
         let mut seen_tuples = vec![false; 130321]; // Bitset to track which delta sequences have been processed
 
@@ -102,7 +112,7 @@ impl Day22 {
         }
 
         let max_total_sales = sale_prices.into_iter().max().unwrap_or(0); // Find the maximum sale price
-        max_total_sales.to_string() // Convert the result to a string for output
+        Ok(max_total_sales.into()) // Convert the result to an Answer for output
     }
 }
 
@@ -110,38 +120,30 @@ mod test {
     #[test]
     /// Test for Part 1: Ensures the correct sum of the 2000th secret numbers is calculated.
     fn test_aoc2024_day22_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day22;
-        let day22 = Day22 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
-        let input = r#"cha
+        let day22 = Day22;
+        let input = r#"
 1
 10
 100
 2024
         "#;
-        assert_eq!(day22.part_1(input), "37327623"); // Check if Part 1 produces the expected result
+        assert_eq!(day22.part_1(input).unwrap(), "37327623"); // Check if Part 1 produces the expected result
     }
 
     #[test]
     /// Test for Part 2: Verifies the correct sum of sales for the optimal delta sequence.
     fn test_aoc2024_day22_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day22;
-        let day22 = Day22 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day22 = Day22;
         let input = r#"
 1
 2
 3
 2024
         "#;
-        assert_eq!(day22.part_2(input), "23"); // Check if Part 2 produces the expected result
+        assert_eq!(day22.part_2(input).unwrap(), "23"); // Check if Part 2 produces the expected result
     }
 }
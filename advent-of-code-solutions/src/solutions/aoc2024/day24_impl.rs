@@ -1,230 +1,185 @@
-use super::day24::Day24;
-use hashbrown::{HashMap, HashSet};
-use std::collections::VecDeque;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::explain::Explain;
+use crate::utils::circuit::{Netlist, Op, Wire};
+use crate::utils::parse;
+use std::fmt;
 
-/// Represents a logic gate in the system. Each gate takes two inputs, applies a specified operator,
-/// and writes the result to an output wire. The gates simulate the malfunctioning boolean logic
-/// described in the AoC story.
-#[derive(Debug)]
-struct Gate<'a> {
-    input1: &'a str,
-    input2: &'a str,
-    operator: &'a str,
-    output: &'a str,
-}
+/// Day 24: Crossed Wires.
+#[derive(Default)]
+pub struct Day24;
 
 /// Holds the parsed input of the circuit. This includes:
-/// - `initial_values`: A map of wire names to their starting values (0 or 1).
-/// - `gates`: A list of logic gates describing the connections and operations in the circuit.
+/// - `initial_values`: The wires given a starting value, with that value.
+/// - `netlist`: The logic gates describing the connections and operations in the circuit.
 #[derive(Debug)]
-struct ParsedInput<'a> {
-    initial_values: HashMap<&'a str, u8>,
-    gates: Vec<Gate<'a>>,
+struct ParsedInput {
+    initial_values: Vec<(Wire, bool)>,
+    netlist: Netlist,
+}
+
+/// The number of pairs of gate outputs that were swapped in the puzzle's adder.
+const SWAPPED_PAIRS: usize = 4;
+
+/// The circuit in the Graphviz DOT language with the `highlight`ed wires in red, only
+/// rendered when formatted.
+struct Dot<'a>(&'a Netlist, &'a [Wire]);
+
+impl fmt::Display for Dot<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0.to_dot(self.1))
+    }
 }
 
 impl Day24 {
     /// Parses the input string to extract initial wire values and gate definitions.
     /// The input format is as follows:
     /// - Lines of the form `wire: value` specify initial wire values.
-    /// - Lines of the form `input1 OPERATOR input2 -> output` define gates.
-    fn parse_input(input: &str) -> ParsedInput {
-        let mut initial_values = HashMap::new();
-        let mut gates = Vec::new();
-
-        for line in input.lines() {
-            let line = line.trim();
-            if line.is_empty() {
-                continue; // Ignore empty lines
-            }
-
-            if let Some((wire, value)) = line.split_once(":") {
-                // Parse an initial wire value
-                let wire = wire.trim();
-                let value: u8 = value.trim().parse().expect("Invalid value");
-                initial_values.insert(wire, value);
-            } else if let Some((expression, output)) = line.split_once("->") {
-                // Parse a gate definition
-                let output = output.trim();
-                let parts: Vec<&str> = expression.trim().split_whitespace().collect();
+    /// - After a blank line, lines of the form `input1 OPERATOR input2 -> output` define gates.
+    fn parse_input(input: &str) -> Result<ParsedInput, SolveError> {
+        let sections = parse::sections(input);
+        let (wires, gate_sections) = sections
+            .split_first()
+            .ok_or_else(|| SolveError::parse(1, 1, "expected the initial wire values"))?;
 
-                if parts.len() == 3 {
-                    // Gate format: "input1 OPERATOR input2 -> output"
-                    let input1 = parts[0];
-                    let operator = parts[1];
-                    let input2 = parts[2];
+        let mut netlist = Netlist::new();
+        let mut initial_values = Vec::new();
+        for line in wires.lines() {
+            // Parse an initial wire value, which must be 0 or 1
+            let (wire, value) = line.key_value()?;
+            let value = match value {
+                "0" => false,
+                "1" => true,
+                _ => return Err(line.error_at(value, "expected a wire value of 0 or 1")),
+            };
+            initial_values.push((netlist.wire(wire), value));
+        }
 
-                    gates.push(Gate {
-                        input1,
-                        input2,
-                        operator,
-                        output,
-                    });
-                } else {
-                    panic!("Invalid gate format");
-                }
-            } else {
-                panic!("Invalid line format");
+        for line in gate_sections.iter().flat_map(|section| section.lines()) {
+            // Gate format: "input1 OPERATOR input2 -> output"
+            let (expression, output) = line.split_once("->", "a gate like 'x00 AND y00 -> z00'")?;
+            let parts: Vec<&str> = expression.split_whitespace().collect();
+            let [input1, operator, input2] = parts[..] else {
+                return Err(line.error("expected a gate like 'x00 AND y00 -> z00'"));
+            };
+            let op = Op::from_name(operator)
+                .ok_or_else(|| line.error_at(operator, "expected AND, OR or XOR"))?;
+            if output.is_empty() {
+                return Err(line.error_at(output, "expected an output wire"));
+            }
+            if !netlist.add_gate(input1, op, input2, output) {
+                return Err(line.error_at(output, "this wire is already driven by another gate"));
             }
         }
 
-        ParsedInput {
+        Ok(ParsedInput {
             initial_values,
-            gates,
-        }
+            netlist,
+        })
     }
 
-    /// Simulates the circuit by processing gates iteratively until all outputs are resolved.
-    /// This function models the system described in the AoC story, where wires carry boolean values
-    /// and gates compute their outputs based on input values and operators.
-    fn simulate<'a>(input: &'a ParsedInput<'a>) -> HashMap<&'a str, u8> {
-        let mut wire_values = input.initial_values.clone();
-        let mut pending_gates: VecDeque<&Gate> = VecDeque::new();
-
-        // Tracks which gates depend on each wire. This ensures efficient re-evaluation of gates.
-        let mut dependents: HashMap<&str, Vec<&Gate>> = HashMap::new();
-
-        // Initialize the dependents map and schedule all gates for processing.
-        for gate in &input.gates {
-            pending_gates.push_back(gate);
-            dependents.entry(gate.input1).or_default().push(gate);
-            dependents.entry(gate.input2).or_default().push(gate);
-        }
-
-        while let Some(gate) = pending_gates.pop_front() {
-            // Check if both inputs for the gate are resolved
-            if let (Some(&val1), Some(&val2)) =
-                (wire_values.get(gate.input1), wire_values.get(gate.input2))
-            {
-                // Perform the operation specified by the gate
-                let result = match gate.operator {
-                    "AND" => val1 & val2,
-                    "OR" => val1 | val2,
-                    "XOR" => val1 ^ val2,
-                    _ => panic!("Unsupported operator: {}", gate.operator),
-                };
-
-                // Update the output wire value only if it has changed
-                let output_updated = if let Some(existing_val) = wire_values.get(gate.output) {
-                    *existing_val != result
-                } else {
-                    true
-                };
+    /// Simulates the circuit and combines the values on wires starting with 'z' to form a binary
+    /// number, where the number of each wire (z00, z01, ...) is the position of its bit.
+    ///
+    /// Fails if the circuit contains a feedback loop, if an output wire never receives a value or
+    /// if an output wire is beyond the 64 bits of the result.
+    fn output(input: &ParsedInput) -> Result<u64, SolveError> {
+        let netlist = &input.netlist;
+        let values = netlist.evaluate(&input.initial_values).map_err(|wire| {
+            SolveError::unsolvable(format!(
+                "the circuit does not settle, wire {} is on a feedback loop",
+                netlist.name(wire)
+            ))
+        })?;
 
-                if output_updated {
-                    wire_values.insert(gate.output, result);
-
-                    // Re-schedule dependent gates for evaluation
-                    if let Some(dependent_gates) = dependents.get(gate.output) {
-                        for &dependent_gate in dependent_gates {
-                            pending_gates.push_back(dependent_gate);
-                        }
-                    }
-                }
-            } else {
-                // Re-schedule the gate if inputs are not yet resolved
-                pending_gates.push_back(gate);
+        let mut combined_value: u64 = 0;
+        for (number, wire) in netlist.bus('z') {
+            let Some(value) = values[wire.index()] else {
+                return Err(SolveError::unsolvable(format!(
+                    "wire {} never receives a value",
+                    netlist.name(wire)
+                )));
+            };
+            if number >= u64::BITS as usize {
+                return Err(SolveError::unsupported(format!(
+                    "more than {} output wires",
+                    u64::BITS
+                )));
+            }
+            if value {
+                combined_value |= 1 << number; // Set the corresponding bit
             }
         }
 
-        wire_values
+        Ok(combined_value)
     }
 
-    /// Combines the values on wires starting with 'z' to form a binary number, interpreted as a decimal.
-    /// The wires are sorted lexicographically (z00, z01, ...), and their binary values are combined
-    /// to calculate the final output of the system.
-    fn combine_bits(wire_values: HashMap<&str, u8>) -> u64 {
-        // Filter keys that start with 'z' and collect them
-        let mut filtered_keys: Vec<&str> = wire_values
-            .keys()
-            .filter(|key| key.starts_with('z'))
-            .cloned()
-            .collect();
+    /// Identifies the swapped wires for Part 2 by searching for the fewest swaps of gate outputs
+    /// that turn the circuit into a ripple-carry adder of x and y into z, verified by simulating
+    /// the repaired circuit. Returns a sorted list of the affected wire names.
+    ///
+    /// `explain` receives every swap and the circuit as found, in DOT with the swapped
+    /// wires highlighted, to inspect the broken wiring visually.
+    fn identify_swapped_wires(
+        parsed_input: ParsedInput,
+        explain: &mut Explain,
+    ) -> Result<Answer, SolveError> {
+        let netlist = parsed_input.netlist;
+        if netlist.gates().is_empty() {
+            return Err(SolveError::unsolvable("the circuit has no gates"));
+        }
 
-        // Sort keys lexicographically (z00 < z01 < ...)
-        filtered_keys.sort();
+        let swaps = netlist.repair_adder(SWAPPED_PAIRS).ok_or_else(|| {
+            let fault = netlist.check_adder().err();
+            SolveError::unsolvable(format!(
+                "no {} swaps of gate outputs repair the adder{}",
+                SWAPPED_PAIRS,
+                fault.map_or(String::new(), |fault| format!(
+                    ", it first fails at bit {}: {}",
+                    fault.bit, fault.reason
+                ))
+            ))
+        })?;
 
-        // Combine values into a binary number
-        let mut combined_value: u64 = 0;
-        for (i, key) in filtered_keys.iter().enumerate() {
-            if let Some(&value) = wire_values.get(key) {
-                if value == 1 {
-                    combined_value |= 1 << i; // Set the corresponding bit
-                }
-            }
+        for (i, &(a, b)) in swaps.iter().enumerate() {
+            explain.fact(
+                format_args!("swap {}", i + 1),
+                format_args!("{} and {}", netlist.name(a), netlist.name(b)),
+            );
         }
+        let highlight: Vec<Wire> = swaps.iter().flat_map(|&(a, b)| [a, b]).collect();
+        explain.fact("circuit", Dot(&netlist, &highlight));
 
-        combined_value
+        let mut swapped_wires: Vec<&str> = swaps
+            .iter()
+            .flat_map(|&(a, b)| [netlist.name(a), netlist.name(b)])
+            .collect();
+        swapped_wires.sort();
+        Ok(swapped_wires.join(",").into())
     }
+}
+
+impl Puzzle for Day24 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 24;
+    const TITLE: &'static str = "Crossed Wires";
 
     /// Solves Part 1 of the AoC problem by parsing the input, simulating the circuit, and combining
     /// the output bits to compute the final result as a decimal number.
-    pub fn part_1(&self, input: &str) -> String {
-        let logic_circuit = Self::parse_input(input);
-        let wire_values = Self::simulate(&logic_circuit);
-        Self::combine_bits(wire_values).to_string()
-    }
-
-    /// Identifies swapped wires for Part 2 of the AoC problem by analyzing gate dependencies and
-    /// constraints to determine misconfigured outputs. Returns a sorted list of affected wire names.
-    fn identify_swapped_wires(parsed_input: ParsedInput) -> String {
-        let mut edges: HashMap<&str, Vec<&str>> = HashMap::new();
-        for g in &parsed_input.gates {
-            edges.entry(g.input1).or_default().push(g.output);
-            edges.entry(g.input2).or_default().push(g.output);
-        }
-
-        let mut broken_nodes = HashSet::new();
-        for g in &parsed_input.gates {
-            // z nodes must be XOR (except for the last one, z45)
-            if g.output.starts_with("z") && g.output != "z45" && g.operator != "XOR" {
-                broken_nodes.insert(g.output);
-            }
-            // z nodes must not be inputs of other nodes
-            if g.input1.starts_with("z") {
-                broken_nodes.insert(g.input1);
-            }
-            if g.input2.starts_with("z") {
-                broken_nodes.insert(g.input2);
-            }
-
-            // inputs of XOR nodes (except for z nodes) must be x and y nodes
-            if g.operator == "XOR"
-                && !g.output.starts_with("z")
-                && !((g.input1.starts_with("x") && g.input2.starts_with("y"))
-                    || (g.input1.starts_with("y") && g.input2.starts_with("x")))
-            {
-                broken_nodes.insert(g.output);
-            }
-
-            // XOR nodes (except z nodes) must always be input of exactly two
-            // other nodes
-            if g.operator == "XOR" && !g.output.starts_with("z") && edges[g.output].len() != 2 {
-                broken_nodes.insert(g.output);
-            }
-
-            // AND nodes must always be input of exactly one other node (except
-            // the very first one wired to x00 and y00)
-            if g.operator == "AND"
-                && !g.output.starts_with("z")
-                && edges[g.output].len() != 1
-                && !((g.input1 == "x00" && g.input2 == "y00")
-                    || (g.input1 == "y00" && g.input2 == "x00"))
-            {
-                broken_nodes.insert(g.output);
-            }
-        }
-
-        // this should be the answer:
-        let mut swapped_wires = broken_nodes.into_iter().collect::<Vec<_>>();
-        swapped_wires.sort();
-        swapped_wires.join(",")
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let logic_circuit = Self::parse_input(input)?;
+        Self::output(&logic_circuit).map(Answer::from)
     }
 
     /// Solves Part 2 of the AoC problem by identifying swapped wires and returning their names in
     /// lexicographical order, as a comma-separated string.
-    pub fn part_2(&self, input: &str) -> String {
-        let logic_circuit = Self::parse_input(input);
-        Self::identify_swapped_wires(logic_circuit)
+    fn part_2(&self, input: &str) -> Result<Answer, SolveError> {
+        self.explain_part_2(input, &mut Explain::disabled())
+    }
+
+    /// Explains Part 2 with the swaps that repair the adder and the circuit as DOT.
+    fn explain_part_2(&self, input: &str, explain: &mut Explain) -> Result<Answer, SolveError> {
+        let logic_circuit = Self::parse_input(input)?;
+        Self::identify_swapped_wires(logic_circuit, explain)
     }
 }
 
@@ -233,13 +188,9 @@ mod test {
     /// Test for part 1 of Day24 for AoC 2024.
     /// Ensures correct calculation of the sum of first and last digits in each line.
     fn test_aoc2024_day24_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day24;
-        let day24 = Day24 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day24 = Day24;
         let input = r#"
 x00: 1
 x01: 0
@@ -289,21 +240,81 @@ hwm AND bqk -> z03
 tgd XOR rvg -> z12
 tnw OR pbm -> gnj
         "#;
-        assert_eq!(day24.part_1(input), "2024"); // Asserts if the function output matches the expected result.
+        assert_eq!(day24.part_1(input).unwrap(), "2024"); // Asserts if the function output matches the expected result.
     }
 
     #[test]
     /// Test for part 2 of Day24 for AoC 2024.
-    /// Verifies correct handling and sum of lines with spelled-out numbers.
+    /// Verifies that a circuit without gates is reported as unsolvable.
     fn test_aoc2024_day24_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day24;
-        let day24 = Day24 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day24 = Day24;
         let input = r#""#;
-        assert_eq!(day24.part_2(input), "TBD"); // Asserts if the function output matches the expected result.
+        assert!(day24.part_2(input).is_err()); // An empty circuit contains no adder to repair.
+
+        // A 3-bit adder with the outputs of two pairs of gates swapped.
+        let input = r#"
+x00: 1
+x01: 1
+x02: 0
+y00: 1
+y01: 0
+y02: 1
+
+x00 XOR y00 -> z00
+x00 AND y00 -> c00
+x01 XOR y01 -> g01
+x01 AND y01 -> p01
+p01 XOR c00 -> z01
+p01 AND c00 -> t01
+g01 OR t01 -> c01
+x02 XOR y02 -> p02
+x02 AND y02 -> g02
+p02 XOR c01 -> z03
+p02 AND c01 -> t02
+g02 OR t02 -> z02
+        "#;
+        assert_eq!(day24.part_2(input).unwrap(), "g01,p01,z02,z03");
+    }
+
+    #[test]
+    /// The explanation of part 2 lists the swaps and highlights their wires in the DOT graph.
+    fn test_aoc2024_day24_explain() {
+        use crate::aoc_solution::Puzzle;
+        use crate::explain::Explain;
+        use crate::solutions::aoc2024::Day24;
+        let day24 = Day24;
+        let input = r#"
+x00: 1
+x01: 1
+y00: 1
+y01: 0
+
+x00 XOR y00 -> z00
+x00 AND y00 -> c00
+x01 XOR y01 -> p01
+x01 AND y01 -> g01
+p01 XOR c00 -> t01
+p01 AND c00 -> z01
+g01 OR t01 -> z02
+        "#;
+        let mut explain = Explain::new(usize::MAX);
+        assert_eq!(
+            day24.explain_part_2(input, &mut explain).unwrap(),
+            "t01,z01"
+        );
+
+        let facts = explain.facts();
+        assert_eq!(facts.len(), 2);
+        assert_eq!(
+            (facts[0].name.as_str(), facts[0].value.as_str()),
+            ("swap 1", "t01 and z01")
+        );
+        assert_eq!(facts[1].name, "circuit");
+        assert!(facts[1].value.starts_with("digraph circuit {"));
+        assert!(facts[1]
+            .value
+            .contains("\"t01\" [label=\"t01\\nXOR\", shape=ellipse, color=red"));
     }
 }
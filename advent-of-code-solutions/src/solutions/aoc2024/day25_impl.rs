@@ -1,52 +1,51 @@
-use super::day25::Day25;
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
+use crate::utils::parse;
+
+/// Day 25: Code Chronicle.
+#[derive(Default)]
+pub struct Day25;
 
 impl Day25 {
     /// Parses the input into two separate collections: locks and keys.
-    /// Each block in the input is 7 lines, where the first and last lines
-    /// determine if it is a lock or a key, and the middle lines represent
-    /// the block's structure as a 5x5 grid.
-    fn parse_blocks(input: &str) -> (Vec<u64>, Vec<u64>) {
+    /// Each block in the input is a 5x7 grid, where the first and last rows
+    /// determine if it is a lock or a key, and the middle rows represent
+    /// the block's pins as a 5x5 grid.
+    fn parse_blocks(input: &str) -> Result<(Vec<u64>, Vec<u64>), SolveError> {
         let mut locks: Vec<u64> = Vec::new(); // Stores parsed locks as 64-bit values.
-        let mut keys: Vec<u64> = Vec::new();  // Stores parsed keys as 64-bit values.
-
-        // Filters out empty lines from the input.
-        let lines: Vec<&str> = input
-            .lines()
-            .filter(|line| !line.trim().is_empty())
-            .collect();
-
-        // Processes blocks of 7 lines at a time.
-        for chunk in lines.chunks(7) {
-            if chunk.len() != 7 {
-                continue; // Skip invalid blocks that are not 7 lines.
+        let mut keys: Vec<u64> = Vec::new(); // Stores parsed keys as 64-bit values.
+
+        // Blocks are separated by blank lines.
+        for section in parse::sections(input) {
+            let grid = section.grid(|c| match c {
+                '#' => Ok(true),
+                '.' => Ok(false),
+                _ => Err("expected '#' or '.'"),
+            })?;
+            if grid.width() != 5 || grid.height() != 7 {
+                return Err(section.error("expected a block of 7 lines with five pins each"));
             }
 
-            // Determine if the block is a lock or key based on its first and last lines.
-            let is_lock = chunk[0] == "#####" && chunk[6] == ".....";
-            let is_key = chunk[0] == "....." && chunk[6] == "#####";
+            // Determine if the block is a lock or key based on its first and last rows.
+            let full = |y: usize| grid.row(y).all(|&pin| pin);
+            let empty = |y: usize| grid.row(y).all(|&pin| !pin);
+            let is_lock = full(0) && empty(6);
+            let is_key = empty(0) && full(6);
 
             if !is_lock && !is_key {
-                continue; // Skip invalid blocks that do not match either pattern.
+                return Err(section.error("expected a lock or a key"));
             }
 
-            // Extracts the grid content from the middle 5 lines.
-            let content = &chunk[1..6];
-
-            // Converts the block's grid to a single 64-bit integer.
+            // Converts the middle 5 rows to a single 64-bit integer, one byte per column.
             let mut block_value: u64 = 0;
-
-            // Iterate over each column in the grid.
-            for (col, _) in content[0].chars().enumerate() {
-                let mut column_bits: u8 = 0;
-
+            for col in 0..5 {
                 // Build the bit representation for the current column.
-                for (row, line) in content.iter().enumerate() {
-                    if let Some(c) = line.chars().nth(col) {
-                        if c == '#' {
-                            column_bits |= 1 << row; // Set the bit if the character is '#'.
-                        }
-                    }
-                }
+                let column_bits = grid
+                    .column(col)
+                    .skip(1)
+                    .take(5)
+                    .enumerate()
+                    .filter(|(_, &pin)| pin)
+                    .fold(0u8, |bits, (row, _)| bits | 1 << row);
 
                 // Shift the column's bit representation into the correct position in the u64 value.
                 block_value |= (column_bits as u64) << (col * 8);
@@ -55,18 +54,24 @@ impl Day25 {
             // Add the parsed block to the appropriate vector (locks or keys).
             if is_lock {
                 locks.push(block_value);
-            } else if is_key {
+            } else {
                 keys.push(block_value);
             }
         }
 
-        (locks, keys)
+        Ok((locks, keys))
     }
+}
+
+impl Puzzle for Day25 {
+    const YEAR: u32 = 2024;
+    const DAY: u32 = 25;
+    const TITLE: &'static str = "Code Chronicle";
 
     /// Solves part 1 of the puzzle by determining the number of valid lock-key pairs.
     /// A lock-key pair is valid if their bitwise AND result is 0, meaning no overlapping pins.
-    pub fn part_1(&self, input: &str) -> String {
-        let (locks, keys) = Self::parse_blocks(input); // Parse locks and keys from the input.
+    fn part_1(&self, input: &str) -> Result<Answer, SolveError> {
+        let (locks, keys) = Self::parse_blocks(input)?; // Parse locks and keys from the input.
         let (smaller, larger, _is_keys_smaller) = if keys.len() <= locks.len() {
             (keys, locks, true) // Use keys as the smaller list if they have fewer elements.
         } else {
@@ -78,23 +83,24 @@ impl Day25 {
         sorted_smaller.sort_unstable();
 
         // Count valid combinations of lock-key pairs.
-        larger
+        let count = larger
             .iter()
             .map(|&larger_elem| {
                 let larger_code = larger_elem & 0xFFFFFFFFFF; // Use only the lower 40 bits of the lock.
-                // Check against all elements in the smaller list.
+                                                              // Check against all elements in the smaller list.
                 sorted_smaller
                     .iter()
                     .filter(|&&smaller_code| (smaller_code & larger_code) == 0) // No overlapping pins.
                     .count()
             })
-            .sum::<usize>() // Sum up the counts for all locks.
-            .to_string()
+            .sum::<usize>(); // Sum up the counts for all locks.
+        Ok(count.into())
     }
 
-    /// Placeholder for part 2 of the puzzle, returning an empty string.
-    pub fn part_2(&self, _input: &str) -> String {
-        "".to_string()
+    /// Placeholder for part 2 of the puzzle, returning an empty answer.
+    /// Day 25 only has one puzzle; the second star is awarded for all others.
+    fn part_2(&self, _input: &str) -> Result<Answer, SolveError> {
+        Ok("".into())
     }
 }
 
@@ -103,13 +109,9 @@ mod test {
     /// Test for part 1 of Day25 for AoC 2024.
     /// Ensures correct calculation of valid lock-key pair counts.
     fn test_aoc2024_day25_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day25;
-        let day25 = Day25 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day25 = Day25;
         let input = r#"
 #####
 .####
@@ -151,21 +153,17 @@ mod test {
 #.#.#
 #####
         "#;
-        assert_eq!(day25.part_1(input), "3"); // Asserts if the function output matches the expected result.
+        assert_eq!(day25.part_1(input).unwrap(), "3"); // Asserts if the function output matches the expected result.
     }
 
     #[test]
     /// Test for part 2 of Day25 for AoC 2024.
     /// Verifies correct handling of input and outputs for part 2 (currently empty).
     fn test_aoc2024_day25_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc2024::Day25;
-        let day25 = Day25 {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day25 = Day25;
         let input = r#""#;
-        assert_eq!(day25.part_2(input), ""); // Asserts if the function output matches the expected result.
+        assert_eq!(day25.part_2(input).unwrap(), ""); // Asserts if the function output matches the expected result.
     }
 }
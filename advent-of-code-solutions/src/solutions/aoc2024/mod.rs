@@ -1,75 +1,50 @@
-pub use day01::Day01;
-mod day01;
+pub use day01_impl::Day01;
 mod day01_impl;
-pub use day02::Day02;
-mod day02;
+pub use day02_impl::Day02;
 mod day02_impl;
-pub use day03::Day03;
-mod day03;
+pub use day03_impl::Day03;
 mod day03_impl;
-pub use day04::Day04;
-mod day04;
+pub use day04_impl::Day04;
 mod day04_impl;
-pub use day05::Day05;
-mod day05;
+pub use day05_impl::Day05;
 mod day05_impl;
-pub use day06::Day06;
-mod day06;
+pub use day06_impl::Day06;
 mod day06_impl;
-pub use day07::Day07;
-mod day07;
+pub use day07_impl::Day07;
 mod day07_impl;
-pub use day08::Day08;
-mod day08;
+pub use day08_impl::Day08;
 mod day08_impl;
-pub use day09::Day09;
-mod day09;
+pub use day09_impl::Day09;
 mod day09_impl;
-pub use day10::Day10;
-mod day10;
+pub use day10_impl::Day10;
 mod day10_impl;
-pub use day11::Day11;
-mod day11;
+pub use day11_impl::Day11;
 mod day11_impl;
-pub use day12::Day12;
-mod day12;
+pub use day12_impl::Day12;
 mod day12_impl;
-pub use day13::Day13;
-mod day13;
+pub use day13_impl::Day13;
 mod day13_impl;
-pub use day14::Day14;
-mod day14;
+pub use day14_impl::Day14;
 mod day14_impl;
-pub use day15::Day15;
-mod day15;
+pub use day15_impl::Day15;
 mod day15_impl;
-pub use day16::Day16;
-mod day16;
+pub use day16_impl::Day16;
 mod day16_impl;
-pub use day17::Day17;
-mod day17;
+pub use day17_impl::Day17;
 mod day17_impl;
-pub use day18::Day18;
-mod day18;
+pub use day18_impl::Day18;
 mod day18_impl;
-pub use day19::Day19;
-mod day19;
+pub use day19_impl::Day19;
 mod day19_impl;
-pub use day20::Day20;
-mod day20;
+pub use day20_impl::Day20;
 mod day20_impl;
-pub use day21::Day21;
-mod day21;
+pub use day21_impl::Day21;
 mod day21_impl;
-pub use day22::Day22;
-mod day22;
+pub use day22_impl::Day22;
 mod day22_impl;
-pub use day23::Day23;
-mod day23;
+pub use day23_impl::Day23;
 mod day23_impl;
-pub use day24::Day24;
-mod day24;
+pub use day24_impl::Day24;
 mod day24_impl;
-pub use day25::Day25;
-mod day25;
+pub use day25_impl::Day25;
 mod day25_impl;
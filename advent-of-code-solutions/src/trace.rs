@@ -0,0 +1,120 @@
+use std::fmt::{self, Write};
+
+use crate::utils::bounded::Bounded;
+
+/// A snapshot of a simulated grid, with what happened since the previous snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    /// The step of the simulation, e.g. the move or second the snapshot was taken after.
+    pub step: usize,
+    /// The grid as text, one string per row.
+    pub grid: Vec<String>,
+    /// What happened since the previous frame, oldest first.
+    pub events: Vec<String>,
+}
+
+/// Records the frames of a simulation for debugging and visualisation. A disabled trace
+/// records nothing and renders no snapshots, so solvers take one unconditionally.
+#[derive(Debug, Clone, Default)]
+pub struct Trace {
+    frames: Bounded<Frame>,
+    events: Vec<String>,
+}
+
+impl Trace {
+    /// A trace that keeps the first `limit` frames and counts the rest.
+    pub fn new(limit: usize) -> Self {
+        Trace {
+            frames: Bounded::new(limit),
+            events: Vec::new(),
+        }
+    }
+
+    /// A trace that records nothing.
+    pub fn disabled() -> Self {
+        Trace::default()
+    }
+
+    /// Whether the next frame will be kept. Solvers can check this to skip work that only
+    /// feeds the trace.
+    pub fn is_recording(&self) -> bool {
+        self.frames.has_room()
+    }
+
+    /// Logs an event for the next frame; `message` is only formatted while recording.
+    pub fn event(&mut self, message: impl fmt::Display) {
+        if self.is_recording() {
+            self.events.push(message.to_string());
+        }
+    }
+
+    /// Takes a snapshot after `step`; `grid` is only called while recording.
+    pub fn frame<R: ToString>(&mut self, step: usize, grid: impl FnOnce() -> R) {
+        let events = &mut self.events;
+        self.frames.push_with(|| Frame {
+            step,
+            grid: grid().to_string().lines().map(str::to_string).collect(),
+            events: std::mem::take(events),
+        });
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        self.frames.items()
+    }
+
+    /// The number of frames that were not kept because of the limit.
+    pub fn skipped(&self) -> usize {
+        self.frames.skipped()
+    }
+
+    /// Renders every frame as a heading with the step, the grid and its events.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for frame in self.frames() {
+            let _ = writeln!(text, "== step {} ==", frame.step);
+            for row in &frame.grid {
+                let _ = writeln!(text, "{}", row);
+            }
+            for event in &frame.events {
+                let _ = writeln!(text, "- {}", event);
+            }
+            text.push('\n');
+        }
+        self.frames.write_skipped(&mut text, "frames");
+        text
+    }
+}
+
+mod test {
+    #[test]
+    /// Frames are kept up to the limit and carry the events logged before them.
+    fn test_trace() {
+        use crate::trace::Trace;
+
+        let mut trace = Trace::new(2);
+        trace.event("start");
+        trace.frame(0, || "#.\n.#\n");
+        trace.event(format_args!("moved to {}", 1));
+        trace.event("turned");
+        trace.frame(1, || "..\n##");
+        trace.event("lost");
+        trace.frame(2, || -> String { unreachable!("the limit is reached") });
+        trace.frame(3, || "");
+
+        assert_eq!(trace.frames().len(), 2);
+        assert_eq!(trace.frames()[0].grid, ["#.", ".#"]);
+        assert_eq!(trace.frames()[1].events, ["moved to 1", "turned"]);
+        assert_eq!(trace.skipped(), 2);
+        assert_eq!(
+            trace.to_text(),
+            "== step 0 ==\n#.\n.#\n- start\n\n== step 1 ==\n..\n##\n- moved to 1\n- turned\n\n\
+             (2 more frames not recorded)\n"
+        );
+
+        let mut disabled = Trace::disabled();
+        disabled.event("ignored");
+        disabled.frame(0, || "#");
+        assert!(!disabled.is_recording());
+        assert!(disabled.frames().is_empty());
+    }
+}
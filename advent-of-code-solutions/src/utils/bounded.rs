@@ -0,0 +1,78 @@
+use std::fmt::Write;
+
+/// Keeps the first `limit` items pushed to it and counts the rest, so that a recording of
+/// a long computation stays small. With a limit of 0 it keeps nothing.
+#[derive(Debug, Clone)]
+pub struct Bounded<T> {
+    limit: usize,
+    items: Vec<T>,
+    skipped: usize,
+}
+
+impl<T> Default for Bounded<T> {
+    fn default() -> Self {
+        Bounded::new(0)
+    }
+}
+
+impl<T> Bounded<T> {
+    pub fn new(limit: usize) -> Self {
+        Bounded {
+            limit,
+            items: Vec::new(),
+            skipped: 0,
+        }
+    }
+
+    /// Whether the next item will be kept.
+    pub fn has_room(&self) -> bool {
+        self.items.len() < self.limit
+    }
+
+    /// Keeps the item made by `make` if there is room, and otherwise only counts it;
+    /// `make` is only called for an item that is kept.
+    pub fn push_with(&mut self, make: impl FnOnce() -> T) {
+        if self.has_room() {
+            self.items.push(make());
+        } else {
+            self.skipped += 1;
+        }
+    }
+
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// The number of items that were not kept because of the limit.
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Appends a line that tells how many `items` were not kept, if any were not.
+    pub fn write_skipped(&self, text: &mut String, items: &str) {
+        if self.skipped > 0 {
+            let _ = writeln!(text, "({} more {} not recorded)", self.skipped, items);
+        }
+    }
+}
+
+mod test {
+    #[test]
+    /// Items are kept in order up to the limit and only made while there is room.
+    fn test_bounded() {
+        use crate::utils::bounded::Bounded;
+
+        let mut bounded = Bounded::new(2);
+        bounded.push_with(|| 1);
+        bounded.push_with(|| 2);
+        bounded.push_with(|| unreachable!("the limit is reached"));
+        assert_eq!(bounded.items(), [1, 2]);
+        assert_eq!(bounded.skipped(), 1);
+        let mut text = String::new();
+        bounded.write_skipped(&mut text, "numbers");
+        assert_eq!(text, "(1 more numbers not recorded)\n");
+
+        let empty: Bounded<u8> = Bounded::default();
+        assert!(!empty.has_room());
+    }
+}
@@ -0,0 +1,568 @@
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+use hashbrown::{HashMap, HashSet};
+
+/// The operation of a logic gate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    And,
+    Or,
+    Xor,
+}
+
+impl Op {
+    /// The operation named `AND`, `OR` or `XOR`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "AND" => Some(Op::And),
+            "OR" => Some(Op::Or),
+            "XOR" => Some(Op::Xor),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Op::And => "AND",
+            Op::Or => "OR",
+            Op::Xor => "XOR",
+        }
+    }
+
+    pub fn apply(&self, a: bool, b: bool) -> bool {
+        match self {
+            Op::And => a & b,
+            Op::Or => a | b,
+            Op::Xor => a ^ b,
+        }
+    }
+}
+
+/// A wire of a [`Netlist`], valid only for the netlist that created it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Wire(usize);
+
+impl Wire {
+    /// The position of the wire's value in the result of [`Netlist::evaluate`].
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// A gate that drives `output` with `op` applied to its two inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gate {
+    pub inputs: [Wire; 2],
+    pub op: Op,
+    pub output: Wire,
+}
+
+/// Why a circuit is not a ripple-carry adder, found by [`Netlist::check_adder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdderFault {
+    /// The first bit whose wiring is wrong.
+    pub bit: usize,
+    /// How many gates of the bit were found before the fault, so a fault later within
+    /// the same bit has a higher stage.
+    pub stage: usize,
+    pub reason: &'static str,
+}
+
+/// A circuit of named wires and the gates between them. Every wire is driven by at most
+/// one gate; wires without a gate are inputs.
+#[derive(Debug, Clone, Default)]
+pub struct Netlist {
+    names: Vec<String>,
+    wires: HashMap<String, Wire>,
+    gates: Vec<Gate>,
+    /// The index of the gate driving each wire.
+    drivers: Vec<Option<usize>>,
+}
+
+impl Netlist {
+    pub fn new() -> Self {
+        Netlist::default()
+    }
+
+    /// The wire called `name`, created if it does not exist yet.
+    pub fn wire(&mut self, name: &str) -> Wire {
+        if let Some(&wire) = self.wires.get(name) {
+            return wire;
+        }
+        let wire = Wire(self.names.len());
+        self.names.push(name.to_string());
+        self.wires.insert(name.to_string(), wire);
+        self.drivers.push(None);
+        wire
+    }
+
+    /// The wire called `name`, if it exists.
+    pub fn find(&self, name: &str) -> Option<Wire> {
+        self.wires.get(name).copied()
+    }
+
+    pub fn name(&self, wire: Wire) -> &str {
+        &self.names[wire.0]
+    }
+
+    pub fn gates(&self) -> &[Gate] {
+        &self.gates
+    }
+
+    /// The gate driving `wire`, if it is not an input.
+    pub fn driver(&self, wire: Wire) -> Option<&Gate> {
+        self.drivers[wire.0].map(|index| &self.gates[index])
+    }
+
+    /// Adds a gate `input1 op input2 -> output`. Returns `false` and changes nothing if
+    /// `output` is already driven by another gate.
+    pub fn add_gate(&mut self, input1: &str, op: Op, input2: &str, output: &str) -> bool {
+        let inputs = [self.wire(input1), self.wire(input2)];
+        let output = self.wire(output);
+        if self.drivers[output.0].is_some() {
+            return false;
+        }
+        self.drivers[output.0] = Some(self.gates.len());
+        self.gates.push(Gate { inputs, op, output });
+        true
+    }
+
+    /// Exchanges the gates driving `a` and `b`, as if their output wires were swapped.
+    pub fn swap_outputs(&mut self, a: Wire, b: Wire) {
+        let (driver_a, driver_b) = (self.drivers[a.0], self.drivers[b.0]);
+        if let Some(index) = driver_a {
+            self.gates[index].output = b;
+        }
+        if let Some(index) = driver_b {
+            self.gates[index].output = a;
+        }
+        self.drivers[a.0] = driver_b;
+        self.drivers[b.0] = driver_a;
+    }
+
+    /// The wires named `prefix` followed by a number, like `z00`, with their numbers in
+    /// ascending order.
+    pub fn bus(&self, prefix: char) -> Vec<(usize, Wire)> {
+        let mut bus: Vec<(usize, Wire)> = self
+            .names
+            .iter()
+            .enumerate()
+            .filter_map(|(index, name)| {
+                let digits = name.strip_prefix(prefix)?;
+                if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+                Some((digits.parse().ok()?, Wire(index)))
+            })
+            .collect();
+        bus.sort();
+        bus
+    }
+
+    /// Computes every wire from the values of the `inputs`; values given for driven wires
+    /// are ignored. Wires that depend on an input without a value stay `None`. Fails with
+    /// a wire on a feedback loop if the circuit has one.
+    pub fn evaluate(&self, inputs: &[(Wire, bool)]) -> Result<Vec<Option<bool>>, Wire> {
+        let mut values = vec![None; self.names.len()];
+        for &(wire, value) in inputs {
+            if self.drivers[wire.0].is_none() {
+                values[wire.0] = Some(value);
+            }
+        }
+
+        // Kahn's algorithm: a gate is ready once none of its inputs waits for a gate.
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); self.names.len()];
+        let mut waiting = vec![0; self.gates.len()];
+        for (index, gate) in self.gates.iter().enumerate() {
+            for input in gate.inputs {
+                if self.drivers[input.0].is_some() {
+                    dependents[input.0].push(index);
+                    waiting[index] += 1;
+                }
+            }
+        }
+        let mut ready: VecDeque<usize> = (0..self.gates.len())
+            .filter(|&index| waiting[index] == 0)
+            .collect();
+        let mut evaluated = 0;
+
+        while let Some(index) = ready.pop_front() {
+            let gate = self.gates[index];
+            evaluated += 1;
+            values[gate.output.0] = match gate.inputs.map(|input| values[input.0]) {
+                [Some(a), Some(b)] => Some(gate.op.apply(a, b)),
+                _ => None,
+            };
+            for &dependent in &dependents[gate.output.0] {
+                waiting[dependent] -= 1;
+                if waiting[dependent] == 0 {
+                    ready.push_back(dependent);
+                }
+            }
+        }
+
+        // Gates that never became ready lie on a loop or behind one.
+        if evaluated < self.gates.len() {
+            if let Some(gate) = self.gates.iter().find(|gate| self.on_loop(gate.output)) {
+                return Err(gate.output);
+            }
+        }
+        Ok(values)
+    }
+
+    /// Whether following the drivers backwards from `wire` leads back to it.
+    fn on_loop(&self, wire: Wire) -> bool {
+        let mut seen = HashSet::new();
+        let mut stack = vec![wire];
+        while let Some(current) = stack.pop() {
+            let Some(gate) = self.driver(current) else {
+                continue;
+            };
+            for input in gate.inputs {
+                if input == wire {
+                    return true;
+                }
+                if seen.insert(input) {
+                    stack.push(input);
+                }
+            }
+        }
+        false
+    }
+
+    /// Feeds `x` and `y` into the `x` and `y` buses and reads the `z` bus as a number.
+    /// Returns `None` if the circuit has a loop, an output bit stays unknown or the `z`
+    /// bus is wider than 64 bits.
+    pub fn simulate(&self, x: u64, y: u64) -> Option<u64> {
+        let bit = |value: u64, number: usize| number < 64 && value >> number & 1 == 1;
+        let inputs: Vec<(Wire, bool)> = self
+            .bus('x')
+            .into_iter()
+            .map(|(number, wire)| (wire, bit(x, number)))
+            .chain(
+                self.bus('y')
+                    .into_iter()
+                    .map(|(number, wire)| (wire, bit(y, number))),
+            )
+            .collect();
+        let values = self.evaluate(&inputs).ok()?;
+
+        self.bus('z')
+            .into_iter()
+            .try_fold(0u64, |z, (number, wire)| match values[wire.0]? {
+                true => Some(z | 1u64.checked_shl(u32::try_from(number).ok()?)?),
+                false if number < 64 => Some(z),
+                false => None,
+            })
+    }
+
+    /// Checks that the circuit is exactly a ripple-carry adder of `x` and `y` into `z`:
+    /// a half adder for bit 0 and a full adder of five gates for every further bit, with
+    /// the last carry as the highest output bit. Returns the width of the inputs.
+    pub fn check_adder(&self) -> Result<usize, AdderFault> {
+        let fault = |bit, stage, reason| Err(AdderFault { bit, stage, reason });
+        let (x, y, z) = (self.bus('x'), self.bus('y'), self.bus('z'));
+        let width = x.len();
+        let numbered = |bus: &[(usize, Wire)]| bus.iter().enumerate().all(|(i, &(n, _))| i == n);
+        if width == 0 || y.len() != width || z.len() != width + 1 {
+            return fault(0, 0, "the buses do not fit an adder of x and y into z");
+        }
+        if !numbered(&x) || !numbered(&y) || !numbered(&z) {
+            return fault(0, 0, "the bus wires are not numbered from 0 without gaps");
+        }
+
+        let mut by_inputs: HashMap<(Op, Wire, Wire), Wire> = HashMap::new();
+        for gate in &self.gates {
+            let [a, b] = gate.inputs;
+            by_inputs.insert((gate.op, a.min(b), a.max(b)), gate.output);
+        }
+        let find = |op, a: Wire, b: Wire| by_inputs.get(&(op, a.min(b), a.max(b))).copied();
+
+        if find(Op::Xor, x[0].1, y[0].1) != Some(z[0].1) {
+            return fault(
+                0,
+                0,
+                "the lowest output bit is not the XOR of the lowest input bits",
+            );
+        }
+        let Some(mut carry) = find(Op::And, x[0].1, y[0].1) else {
+            return fault(0, 1, "there is no carry from the lowest bit");
+        };
+
+        for bit in 1..width {
+            let (Some(partial), Some(generate)) = (
+                find(Op::Xor, x[bit].1, y[bit].1),
+                find(Op::And, x[bit].1, y[bit].1),
+            ) else {
+                return fault(bit, 0, "the input bits are not combined by XOR and AND");
+            };
+            let Some(sum) = find(Op::Xor, partial, carry) else {
+                return fault(bit, 2, "the partial sum is not combined with the carry");
+            };
+            if sum != z[bit].1 {
+                return fault(bit, 3, "the sum does not reach the output bit");
+            }
+            let Some(propagate) = find(Op::And, partial, carry) else {
+                return fault(bit, 4, "the carry is not propagated");
+            };
+            let Some(next) = find(Op::Or, generate, propagate) else {
+                return fault(bit, 5, "there is no carry to the next bit");
+            };
+            carry = next;
+        }
+
+        if carry != z[width].1 {
+            return fault(
+                width,
+                0,
+                "the last carry does not reach the highest output bit",
+            );
+        }
+        if self.gates.len() != 5 * width - 3 {
+            return fault(width, 1, "the circuit has gates beyond the adder");
+        }
+        Ok(width)
+    }
+
+    /// Checks by simulation that the circuit adds `x` and `y` into `z` for single bits,
+    /// long carry chains and a few pseudo-random values of the given input `width`.
+    pub fn verify_adder(&self, width: usize) -> bool {
+        if width == 0 || width >= 64 {
+            return false;
+        }
+        let mask = (1u64 << width) - 1;
+        let mut cases = vec![(0, 0), (mask, 1), (1, mask), (mask, mask)];
+        for bit in 0..width {
+            cases.extend([(1 << bit, 0), (0, 1 << bit), (1 << bit, 1 << bit)]);
+        }
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        for _ in 0..16 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            let x = seed & mask;
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            cases.push((x, (seed >> 7) & mask));
+        }
+        cases
+            .into_iter()
+            .all(|(x, y)| self.simulate(x, y) == Some(x + y))
+    }
+
+    /// Finds the fewest swaps of gate outputs, at most `max_swaps`, after which the circuit
+    /// is a ripple-carry adder, and verifies the result by simulation. Each swap is
+    /// returned with its wires in ascending order.
+    ///
+    /// The search deepens one swap at a time, so at most `max_swaps` are tried in a row.
+    /// A swap is only pursued if the first fault moves on, to a later bit or to a later
+    /// stage of the same bit, as two swaps within one full adder may be needed to fix its
+    /// bit. Only wires near the faulty bit are considered for one side of a swap.
+    pub fn repair_adder(&self, max_swaps: usize) -> Option<Vec<(Wire, Wire)>> {
+        let mut circuit = self.clone();
+        let mut swaps = Vec::new();
+        (0..=max_swaps).find_map(|depth| circuit.repair(depth, &mut swaps).then(|| swaps.clone()))
+    }
+
+    fn repair(&mut self, depth: usize, swaps: &mut Vec<(Wire, Wire)>) -> bool {
+        let fault = match self.check_adder() {
+            Ok(width) => return self.verify_adder(width),
+            Err(fault) => fault,
+        };
+        if depth == 0 {
+            return false;
+        }
+
+        let outputs: Vec<Wire> = self.gates.iter().map(|gate| gate.output).collect();
+        for near in self.wires_near(fault.bit) {
+            for &other in &outputs {
+                if near == other || swaps.contains(&(near.min(other), near.max(other))) {
+                    continue;
+                }
+                self.swap_outputs(near, other);
+                let fixed = match self.check_adder() {
+                    Ok(_) => true,
+                    Err(next) => (next.bit, next.stage) > (fault.bit, fault.stage),
+                };
+                if fixed {
+                    swaps.push((near.min(other), near.max(other)));
+                    if self.repair(depth - 1, swaps) {
+                        return true;
+                    }
+                    swaps.pop();
+                }
+                self.swap_outputs(near, other);
+            }
+        }
+        false
+    }
+
+    /// The outputs of the gates up to three steps after the input bits `bit - 1` and
+    /// `bit`, and the output bit itself: the wires of the full adders around `bit`.
+    fn wires_near(&self, bit: usize) -> Vec<Wire> {
+        let (x, y, z) = (self.bus('x'), self.bus('y'), self.bus('z'));
+        let mut frontier: Vec<Wire> = [&x, &y]
+            .iter()
+            .flat_map(|bus| bus.iter())
+            .filter(|&&(number, _)| number + 1 == bit || number == bit)
+            .map(|&(_, wire)| wire)
+            .collect();
+        let mut near: Vec<Wire> = z
+            .iter()
+            .filter(|&&(number, _)| number == bit)
+            .map(|&(_, wire)| wire)
+            .filter(|&wire| self.driver(wire).is_some())
+            .collect();
+
+        for _ in 0..3 {
+            frontier = self
+                .gates
+                .iter()
+                .filter(|gate| gate.inputs.iter().any(|input| frontier.contains(input)))
+                .map(|gate| gate.output)
+                .collect();
+            near.extend(&frontier);
+        }
+        near.sort();
+        near.dedup();
+        near
+    }
+
+    /// The circuit in the Graphviz DOT language: one node per wire, labelled with the
+    /// gate driving it, and an edge from every gate input. The `highlight`ed wires are
+    /// drawn in red, e.g. to show swapped outputs.
+    pub fn to_dot(&self, highlight: &[Wire]) -> String {
+        let mut dot = String::from("digraph circuit {\n    rankdir=LR;\n");
+        for (index, name) in self.names.iter().enumerate() {
+            let wire = Wire(index);
+            let (label, shape) = match self.driver(wire) {
+                Some(gate) => (format!("{}\\n{}", name, gate.op.name()), "ellipse"),
+                None => (name.clone(), "box"),
+            };
+            let color = if highlight.contains(&wire) {
+                ", color=red, fontcolor=red"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                dot,
+                "    \"{}\" [label=\"{}\", shape={}{}];",
+                name, label, shape, color
+            );
+        }
+        for gate in &self.gates {
+            for input in gate.inputs {
+                let _ = writeln!(
+                    dot,
+                    "    \"{}\" -> \"{}\";",
+                    self.name(input),
+                    self.name(gate.output)
+                );
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+mod test {
+    /// A ripple-carry adder of `width` bits named like the puzzle input.
+    #[allow(dead_code)]
+    fn adder(width: usize) -> crate::utils::circuit::Netlist {
+        use crate::utils::circuit::{Netlist, Op};
+
+        let mut netlist = Netlist::new();
+        netlist.add_gate("x00", Op::Xor, "y00", "z00");
+        netlist.add_gate("x00", Op::And, "y00", "c00");
+        for bit in 1..width {
+            let (x, y) = (format!("x{:02}", bit), format!("y{:02}", bit));
+            let carry = format!("c{:02}", bit - 1);
+            let (partial, generate) = (format!("p{:02}", bit), format!("g{:02}", bit));
+            let propagate = format!("t{:02}", bit);
+            let next = if bit + 1 == width {
+                format!("z{:02}", width)
+            } else {
+                format!("c{:02}", bit)
+            };
+            netlist.add_gate(&x, Op::Xor, &y, &partial);
+            netlist.add_gate(&y, Op::And, &x, &generate);
+            netlist.add_gate(&partial, Op::Xor, &carry, &format!("z{:02}", bit));
+            netlist.add_gate(&carry, Op::And, &partial, &propagate);
+            netlist.add_gate(&generate, Op::Or, &propagate, &next);
+        }
+        netlist
+    }
+
+    #[test]
+    /// A correct adder simulates, passes the structural check and exports to DOT.
+    fn test_circuit_adder() {
+        use crate::utils::circuit::{test::adder, Op};
+
+        let netlist = adder(8);
+        assert_eq!(netlist.check_adder(), Ok(8));
+        assert_eq!(netlist.simulate(200, 100), Some(300));
+        assert!(netlist.verify_adder(8));
+        assert_eq!(netlist.repair_adder(2), Some(vec![]));
+
+        let mut looped = netlist.clone();
+        assert!(!looped.add_gate("z01", Op::Or, "x01", "z00")); // z00 is already driven
+        looped.add_gate("q", Op::Or, "r", "r");
+        assert_eq!(looped.evaluate(&[]), Err(looped.find("r").unwrap()));
+        assert_eq!(looped.simulate(1, 1), None);
+
+        let dot = netlist.to_dot(&[netlist.find("z03").unwrap()]);
+        assert!(dot.starts_with("digraph circuit {"));
+        assert!(dot.contains("\"z03\" [label=\"z03\\nXOR\", shape=ellipse, color=red"));
+        assert!(dot.contains("\"x03\" -> \"p03\";"));
+    }
+
+    #[test]
+    /// Swapped outputs are found, whichever parts of the full adders they connect.
+    fn test_circuit_repair() {
+        use crate::utils::circuit::test::adder;
+
+        let mut netlist = adder(12);
+        let swaps = [("z03", "c03"), ("p06", "g06"), ("z09", "t09")];
+        for (a, b) in swaps {
+            netlist.swap_outputs(netlist.find(a).unwrap(), netlist.find(b).unwrap());
+        }
+        assert_eq!(netlist.check_adder().unwrap_err().bit, 3);
+        assert_ne!(netlist.simulate(0b1000, 0b1000), Some(0b10000));
+
+        assert_eq!(netlist.repair_adder(2), None);
+        let repaired = netlist.repair_adder(4).unwrap();
+        let mut names: Vec<&str> = repaired
+            .iter()
+            .flat_map(|&(a, b)| [netlist.name(a), netlist.name(b)])
+            .collect();
+        names.sort();
+        assert_eq!(names, ["c03", "g06", "p06", "t09", "z03", "z09"]);
+    }
+
+    #[test]
+    /// Two swaps within the same full adder are found, although neither fixes the bit alone.
+    fn test_circuit_repair_one_bit() {
+        use crate::utils::circuit::test::adder;
+
+        let mut netlist = adder(8);
+        for (a, b) in [("p04", "g04"), ("z04", "t04")] {
+            netlist.swap_outputs(netlist.find(a).unwrap(), netlist.find(b).unwrap());
+        }
+
+        // Swapping back the partial sum and the generated carry leaves the fault in bit 4,
+        // only at a later stage.
+        let fault = netlist.check_adder().unwrap_err();
+        let mut swapped = netlist.clone();
+        swapped.swap_outputs(netlist.find("p04").unwrap(), netlist.find("g04").unwrap());
+        let next = swapped.check_adder().unwrap_err();
+        assert_eq!((fault.bit, next.bit), (4, 4));
+        assert!(next.stage > fault.stage);
+
+        assert_eq!(netlist.repair_adder(1), None);
+        let repaired = netlist.repair_adder(2).unwrap();
+        let mut names: Vec<&str> = repaired
+            .iter()
+            .flat_map(|&(a, b)| [netlist.name(a), netlist.name(b)])
+            .collect();
+        names.sort();
+        assert_eq!(names, ["g04", "p04", "t04", "z04"]);
+    }
+}
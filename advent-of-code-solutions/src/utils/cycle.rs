@@ -0,0 +1,193 @@
+use std::hash::Hash;
+
+use hashbrown::HashMap;
+
+/// Where the sequence `x0, f(x0), f(f(x0)), ...` starts repeating: the state after
+/// `start` steps comes back every `length` steps from then on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as `step`, which is before `start + length`.
+    /// This is how a simulation is extrapolated to a step far in the future.
+    pub fn equivalent_step(&self, step: usize) -> usize {
+        if step < self.start {
+            step
+        } else {
+            self.start + (step - self.start) % self.length
+        }
+    }
+}
+
+/// Every state of a sequence up to the point where it repeats.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History<S> {
+    pub cycle: Cycle,
+    states: Vec<S>,
+}
+
+impl<S> History<S> {
+    /// The states from the initial one up to the last before the first repetition.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+
+    /// The state after `step` steps, for any `step`.
+    pub fn state_at(&self, step: usize) -> &S {
+        &self.states[self.cycle.equivalent_step(step)]
+    }
+}
+
+/// Finds the cycle by remembering every state in a hash map. This takes memory for all
+/// states but applies `step` only once per state and keeps them for [`History::state_at`].
+pub fn detect<S, F>(initial: S, mut step: F) -> History<S>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let length = states.len() - start;
+            return History {
+                cycle: Cycle { start, length },
+                states,
+            };
+        }
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+/// Finds the cycle with Floyd's tortoise and hare, keeping only three states at a time.
+pub fn floyd<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    // The hare moves twice as fast; they meet somewhere inside the cycle.
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+
+    // Going on from the meeting point reaches the start of the cycle after as many steps
+    // as going on from the initial state.
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds the cycle with Brent's algorithm, which keeps two states like Floyd's but
+/// usually needs fewer steps.
+pub fn brent<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    // The tortoise teleports to the hare at every power of two until the hare catches it.
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // With the hare `length` steps ahead, both meet at the start of the cycle.
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+mod test {
+    #[test]
+    /// A sequence with a tail of 3 states and a cycle of 4.
+    fn test_cycle() {
+        use crate::utils::cycle::{brent, detect, floyd, Cycle};
+
+        let next = [1, 2, 3, 4, 5, 6, 3];
+        let step = |&state: &usize| next[state];
+        let expected = Cycle {
+            start: 3,
+            length: 4,
+        };
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+
+        let history = detect(0, step);
+        assert_eq!(history.cycle, expected);
+        assert_eq!(history.states(), &[0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(history.state_at(2), &2);
+        assert_eq!(
+            history.state_at(1_000_000_000),
+            &(3 + (1_000_000_000 - 3) % 4)
+        );
+        assert_eq!(expected.equivalent_step(10), 6);
+    }
+
+    #[test]
+    /// All three detectors agree on random functions over a small set of states.
+    fn test_cycle_properties() {
+        use crate::utils::cycle::{brent, detect, floyd};
+        use proptest::collection::vec;
+        use proptest::test_runner::TestRunner;
+
+        let mut runner = TestRunner::default();
+        runner
+            .run(&(vec(0usize..40, 40), 0usize..40), |(next, initial)| {
+                let step = |&state: &usize| next[state];
+                let history = detect(initial, step);
+                assert_eq!(floyd(initial, step), history.cycle);
+                assert_eq!(brent(initial, step), history.cycle);
+
+                // Walking the sequence directly agrees with the extrapolation.
+                let mut state = initial;
+                for n in 0..100 {
+                    assert_eq!(*history.state_at(n), state);
+                    state = step(&state);
+                }
+                Ok(())
+            })
+            .unwrap();
+    }
+}
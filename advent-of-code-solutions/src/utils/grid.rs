@@ -0,0 +1,279 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::aoc_solution::SolveError;
+use crate::utils::point::Point;
+
+/// A rectangular grid of cells, addressed by `Point { x: column, y: row }` with the
+/// origin in the top-left corner. Cells are stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a `width` x `height` grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses one row per non-empty line, mapping every character with `cell`.
+    ///
+    /// Leading and trailing whitespace is ignored and all rows must have the same length.
+    /// If `cell` rejects a character, its message becomes a parse error pointing at it.
+    pub fn parse<F>(input: &str, mut cell: F) -> Result<Self, SolveError>
+    where
+        F: FnMut(char) -> Result<T, &'static str>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (index, line) in input.lines().enumerate() {
+            let row = line.trim();
+            if row.is_empty() {
+                continue;
+            }
+            // Columns in errors count from the start of the untrimmed line.
+            let indent = line.len() - line.trim_start().len();
+
+            let mut length = 0;
+            for (column, ch) in row.chars().enumerate() {
+                let value = cell(ch).map_err(|message| {
+                    SolveError::parse(index + 1, indent + column + 1, message)
+                })?;
+                cells.push(value);
+                length += 1;
+            }
+
+            let width = *width.get_or_insert(length);
+            if length != width {
+                return Err(SolveError::parse(
+                    index + 1,
+                    indent + length.min(width) + 1,
+                    format!("expected {} cells per row", width),
+                ));
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `p` lies inside the grid.
+    pub fn contains(&self, p: Point<i32>) -> bool {
+        self.offset(p).is_some()
+    }
+
+    pub fn get(&self, p: Point<i32>) -> Option<&T> {
+        self.offset(p).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, p: Point<i32>) -> Option<&mut T> {
+        self.offset(p).map(|offset| &mut self.cells[offset])
+    }
+
+    /// All points of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point<i32>> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |offset| Self::point(width, offset))
+    }
+
+    /// All cells together with their points, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<i32>, &T)> {
+        let width = self.width;
+        (0..)
+            .zip(&self.cells)
+            .map(move |(offset, cell)| (Self::point(width, offset), cell))
+    }
+
+    /// The orthogonal neighbours of `p` that lie inside the grid, clockwise from north.
+    pub fn neighbors4(&self, p: Point<i32>) -> impl Iterator<Item = Point<i32>> + '_ {
+        p.neighbors4().into_iter().filter(|&n| self.contains(n))
+    }
+
+    /// The orthogonal and diagonal neighbours of `p` that lie inside the grid.
+    pub fn neighbors8(&self, p: Point<i32>) -> impl Iterator<Item = Point<i32>> + '_ {
+        p.neighbors8().into_iter().filter(|&n| self.contains(n))
+    }
+
+    /// The cells of row `y`, left to right. Panics if `y` is not below the height.
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        assert!(y < self.height, "row {} outside the grid", y);
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    /// The cells of column `x`, top to bottom. Panics if `x` is not below the width.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} outside the grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The cells from `start` in steps of `step` until the edge of the grid.
+    pub fn ray(
+        &self,
+        start: Point<i32>,
+        step: Point<i32>,
+    ) -> impl Iterator<Item = (Point<i32>, &T)> {
+        std::iter::successors(Some(start), move |&p| Some(p + step))
+            .map_while(|p| self.get(p).map(|cell| (p, cell)))
+    }
+
+    /// The cells on the diagonal from `start` towards the bottom-right corner.
+    pub fn diagonal(&self, start: Point<i32>) -> impl Iterator<Item = (Point<i32>, &T)> {
+        self.ray(start, Point { x: 1, y: 1 })
+    }
+
+    /// The cells on the anti-diagonal from `start` towards the bottom-left corner.
+    pub fn anti_diagonal(&self, start: Point<i32>) -> impl Iterator<Item = (Point<i32>, &T)> {
+        self.ray(start, Point { x: -1, y: 1 })
+    }
+
+    /// The first point, row by row, whose cell matches `predicate`.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point<i32>> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(p, _)| p)
+    }
+
+    /// All points, row by row, whose cells match `predicate`.
+    pub fn find_all<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point<i32>> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(p, _)| p)
+    }
+
+    fn offset(&self, p: Point<i32>) -> Option<usize> {
+        let (x, y) = (usize::try_from(p.x).ok()?, usize::try_from(p.y).ok()?);
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    fn point(width: usize, offset: usize) -> Point<i32> {
+        Point {
+            x: (offset % width) as i32,
+            y: (offset / width) as i32,
+        }
+    }
+}
+
+impl<T> Index<Point<i32>> for Grid<T> {
+    type Output = T;
+
+    /// Panics if `p` lies outside the grid.
+    fn index(&self, p: Point<i32>) -> &T {
+        let offset = self.offset(p).expect("point outside the grid");
+        &self.cells[offset]
+    }
+}
+
+impl<T> IndexMut<Point<i32>> for Grid<T> {
+    fn index_mut(&mut self, p: Point<i32>) -> &mut T {
+        let offset = self.offset(p).expect("point outside the grid");
+        &mut self.cells[offset]
+    }
+}
+
+/// Renders the grid back to text, one line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for cell in self.row(y) {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+mod test {
+    #[test]
+    /// Parsing, lookups, neighbours and rendering on a small grid.
+    fn test_grid() {
+        use crate::utils::grid::Grid;
+        use crate::utils::point::Point;
+
+        let input = r#"
+#.#
+.S.
+        "#;
+        let grid = Grid::parse(input, Ok).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.to_string(), "#.#\n.S.\n");
+
+        let start = grid.find(|&c| c == 'S').unwrap();
+        assert_eq!(start, Point::new(1, 1));
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.neighbors4(start).count(), 3);
+        assert_eq!(grid.neighbors8(Point::new(0, 0)).count(), 3);
+        assert_eq!(grid.find_all(|&c| c == '#').count(), 2);
+
+        assert_eq!(grid.row(1).collect::<String>(), ".S.");
+        assert_eq!(grid.column(2).collect::<String>(), "#.");
+        assert_eq!(grid.diagonal(Point::new(0, 0)).count(), 2);
+        assert_eq!(grid.anti_diagonal(Point::new(2, 0)).count(), 2);
+    }
+
+    #[test]
+    /// Ragged rows and rejected characters point at the offending cell.
+    fn test_grid_parse_errors() {
+        use crate::aoc_solution::SolveError;
+        use crate::utils::grid::Grid;
+
+        let ragged = Grid::parse("ab\nabc\n", Ok).unwrap_err();
+        assert!(matches!(
+            ragged,
+            SolveError::Parse {
+                line: 2,
+                column: 3,
+                ..
+            }
+        ));
+
+        let digits = Grid::parse("12\n3x\n", |c| c.to_digit(10).ok_or("expected a digit"));
+        assert!(matches!(
+            digits,
+            Err(SolveError::Parse {
+                line: 2,
+                column: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    /// Rows and columns outside the grid panic instead of reading other cells.
+    fn test_grid_lines_outside() {
+        use crate::utils::grid::Grid;
+        use std::panic::catch_unwind;
+
+        let grid = Grid::parse("ab\ncd\n", Ok).unwrap();
+        assert_eq!(grid.column(1).collect::<String>(), "bd");
+        assert!(catch_unwind(|| grid.column(2).count()).is_err());
+        assert!(catch_unwind(|| grid.row(2).count()).is_err());
+    }
+}
@@ -0,0 +1,370 @@
+use num::PrimInt;
+
+/// A half-open range of integers `start..end`. It is empty if `start >= end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+
+    /// The interval `first..=last`, or `None` if `last` is the largest value of `T`.
+    pub fn inclusive(first: T, last: T) -> Option<Self> {
+        Some(Interval::new(first, last.checked_add(&T::one())?))
+    }
+
+    /// The interval of `length` values from `start`, cut off at the largest value of `T`.
+    pub fn with_length(start: T, length: T) -> Self {
+        Interval::new(start, start.saturating_add(length))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// The number of values in the interval.
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::zero()
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Whether every value of `other` lies in this interval. Empty intervals lie anywhere.
+    pub fn covers(&self, other: &Interval<T>) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    /// Whether the intervals share at least one value.
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// The values in both intervals; possibly empty.
+    pub fn intersection(&self, other: &Interval<T>) -> Interval<T> {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Moves every value `v` to `v - from + to`. Values that would leave the range of `T`
+    /// are dropped. Returns `None` if the distance from `from` to `to` is out of the range
+    /// of `T`, which only happens for signed types.
+    pub fn translate(&self, from: T, to: T) -> Option<Interval<T>> {
+        if to >= from {
+            let offset = to.checked_sub(&from)?;
+            Some(Interval::new(
+                self.start.saturating_add(offset),
+                self.end.saturating_add(offset),
+            ))
+        } else {
+            let offset = from.checked_sub(&to)?;
+            Some(Interval::new(
+                self.start.saturating_sub(offset),
+                self.end.saturating_sub(offset),
+            ))
+        }
+    }
+}
+
+/// A set of integers stored as sorted, disjoint and non-adjacent intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// The intervals of the set in ascending order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of values in the set, saturating at the largest value of `T`.
+    pub fn total_len(&self) -> T {
+        self.iter().fold(T::zero(), |total, interval| {
+            total.saturating_add(interval.len())
+        })
+    }
+
+    /// The smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    /// The largest value in the set.
+    pub fn max(&self) -> Option<T> {
+        self.intervals
+            .last()
+            .map(|interval| interval.end - T::one())
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        // The first interval that ends after `value` is the only one that can contain it.
+        let index = self
+            .intervals
+            .partition_point(|interval| interval.end <= value);
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    /// Adds all values of `interval` to the set.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        *self = self.union(&IntervalSet::from(interval));
+    }
+
+    /// The values in either set.
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        self.iter().chain(other.iter()).collect()
+    }
+
+    /// The values in both sets.
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let (mut i, mut j) = (0, 0);
+        let mut intervals = Vec::new();
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            let overlap = a.intersection(b);
+            if !overlap.is_empty() {
+                intervals.push(overlap);
+            }
+            // Whichever interval ends first cannot overlap anything further on.
+            if a.end <= b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    /// The values in this set but not in `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = Vec::new();
+        let mut others = other.intervals.iter().peekable();
+
+        for &interval in &self.intervals {
+            let mut rest = interval;
+            // Skip the intervals of `other` that end before this one starts.
+            while others.next_if(|cut| cut.end <= rest.start).is_some() {}
+            for cut in others.clone() {
+                if cut.start >= rest.end {
+                    break;
+                }
+                if cut.start > rest.start {
+                    intervals.push(Interval::new(rest.start, cut.start));
+                }
+                rest.start = rest.start.max(cut.end);
+            }
+            if !rest.is_empty() {
+                intervals.push(rest);
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    /// Moves every value `v` to `v - from + to` like [`Interval::translate`], or returns
+    /// `None` if the distance does not fit into `T`.
+    pub fn translate(&self, from: T, to: T) -> Option<IntervalSet<T>> {
+        self.iter()
+            .map(|interval| interval.translate(from, to))
+            .collect()
+    }
+
+    /// Maps the set piece by piece: the values in the source interval of a mapping move so
+    /// that its start lands on the target. The first matching mapping wins; values that
+    /// no mapping covers stay where they are. Returns `None` if a mapping moves values
+    /// further than `T` reaches.
+    pub fn map_piecewise(&self, mappings: &[(Interval<T>, T)]) -> Option<IntervalSet<T>> {
+        let mut unmapped = self.clone();
+        let mut mapped = Vec::new();
+
+        for &(source, target) in mappings {
+            let hit = unmapped.intersection(&IntervalSet::from(source));
+            for piece in hit.iter() {
+                mapped.push(piece.translate(source.start, target)?);
+            }
+            unmapped = unmapped.difference(&hit);
+        }
+
+        Some(mapped.into_iter().chain(unmapped.intervals).collect())
+    }
+}
+
+impl<T: PrimInt> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        std::iter::once(interval).collect()
+    }
+}
+
+/// Collects intervals in any order, merging those that overlap or touch.
+impl<T: PrimInt> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut sorted: Vec<Interval<T>> = iter
+            .into_iter()
+            .filter(|interval| !interval.is_empty())
+            .collect();
+        sorted.sort_by_key(|interval| interval.start);
+
+        let mut intervals: Vec<Interval<T>> = Vec::with_capacity(sorted.len());
+        for interval in sorted {
+            match intervals.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => intervals.push(interval),
+            }
+        }
+        IntervalSet { intervals }
+    }
+}
+
+mod test {
+    #[test]
+    /// Containment, overlap and translation of single intervals.
+    fn test_interval() {
+        use crate::utils::interval::Interval;
+
+        let a = Interval::inclusive(2, 8).unwrap();
+        let b = Interval::new(3u32, 8);
+        assert_eq!(a.len(), 7);
+        assert!(a.covers(&b) && !b.covers(&a));
+        assert!(a.overlaps(&Interval::new(8, 10)));
+        assert!(!a.overlaps(&Interval::new(9, 10)));
+        assert!(Interval::new(5, 2).is_empty());
+        assert_eq!(Interval::inclusive(0, u8::MAX), None);
+        assert_eq!(Interval::with_length(250u8, 10), Interval::new(250, 255));
+        assert_eq!(b.translate(3, 0), Some(Interval::new(0, 5)));
+        assert_eq!(b.translate(5, 0), Some(Interval::new(0, 3))); // 3 and 4 would be negative
+        assert_eq!(Interval::new(0i8, 5).translate(-100, 100), None);
+    }
+
+    #[test]
+    /// Every set operation agrees with the same operation on plain sets of values.
+    fn test_interval_set_properties() {
+        use crate::utils::interval::{Interval, IntervalSet};
+        use proptest::collection::vec;
+        use proptest::test_runner::TestRunner;
+        use std::collections::BTreeSet;
+
+        const LIMIT: u16 = 64;
+        let values = |set: &IntervalSet<u16>| -> BTreeSet<u16> {
+            (0..LIMIT).filter(|&v| set.contains(v)).collect()
+        };
+        let expand = |intervals: &[(u16, u16)]| -> BTreeSet<u16> {
+            intervals
+                .iter()
+                .flat_map(|&(start, end)| start..end)
+                .collect()
+        };
+        let build = |intervals: &[(u16, u16)]| -> IntervalSet<u16> {
+            intervals
+                .iter()
+                .map(|&(start, end)| Interval::new(start, end))
+                .collect()
+        };
+
+        let intervals = || vec((0..LIMIT / 2, 0..LIMIT / 2), 0..6);
+        let mut runner = TestRunner::default();
+        runner
+            .run(
+                &(intervals(), intervals(), 0..LIMIT / 2, 0..LIMIT / 2),
+                |(a, b, from, to)| {
+                    let (set_a, set_b) = (build(&a), build(&b));
+                    let (values_a, values_b) = (expand(&a), expand(&b));
+
+                    // Normalised: sorted, non-empty and neither overlapping nor touching.
+                    for pair in set_a.intervals().windows(2) {
+                        assert!(!pair[0].is_empty() && pair[0].end < pair[1].start);
+                    }
+                    assert_eq!(values(&set_a), values_a);
+                    assert_eq!(set_a.total_len() as usize, values_a.len());
+                    assert_eq!(set_a.min(), values_a.first().copied());
+                    assert_eq!(set_a.max(), values_a.last().copied());
+
+                    let union = set_a.union(&set_b);
+                    assert_eq!(values(&union), &values_a | &values_b);
+                    let intersection = set_a.intersection(&set_b);
+                    assert_eq!(values(&intersection), &values_a & &values_b);
+                    let difference = set_a.difference(&set_b);
+                    assert_eq!(values(&difference), &values_a - &values_b);
+
+                    let moved: BTreeSet<u16> = values_a
+                        .iter()
+                        .filter_map(|v| (v + to).checked_sub(from))
+                        .collect();
+                    assert_eq!(values(&set_a.translate(from, to).unwrap()), moved);
+
+                    // Values of `b` move by the mapping; everything else stays.
+                    let mappings: Vec<_> = set_b.iter().map(|source| (source, to)).collect();
+                    let mapped: BTreeSet<u16> = values_a
+                        .iter()
+                        .filter_map(|&v| match set_b.iter().find(|source| source.contains(v)) {
+                            Some(source) => (v + to).checked_sub(source.start),
+                            None => Some(v),
+                        })
+                        .collect();
+                    assert_eq!(values(&set_a.map_piecewise(&mappings).unwrap()), mapped);
+                    Ok(())
+                },
+            )
+            .unwrap();
+    }
+
+    #[test]
+    /// Translation near the ends of a signed type keeps exactly the values that stay in
+    /// range, and refuses distances the type cannot hold.
+    fn test_interval_translate_extremes() {
+        use crate::utils::interval::Interval;
+        use proptest::strategy::Strategy;
+        use proptest::test_runner::TestRunner;
+
+        let extreme = || {
+            (i8::MIN..=i8::MIN + 4)
+                .prop_union(i8::MAX - 4..=i8::MAX)
+                .or(-4i8..=4)
+        };
+        let mut runner = TestRunner::default();
+        runner
+            .run(
+                &(extreme(), extreme(), extreme(), extreme()),
+                |(start, end, from, to)| {
+                    let interval = Interval::new(start, end);
+                    let offset = i16::from(to) - i16::from(from);
+                    let Some(moved) = interval.translate(from, to) else {
+                        assert!(i8::try_from(offset.abs()).is_err());
+                        return Ok(());
+                    };
+                    assert!(i8::try_from(offset.abs()).is_ok());
+
+                    // The largest value cannot end a half-open interval, so it is dropped too.
+                    let expected: Vec<i8> = (start..end)
+                        .filter_map(|v| i8::try_from(i16::from(v) + offset).ok())
+                        .filter(|&v| v < i8::MAX)
+                        .collect();
+                    let actual: Vec<i8> = (moved.start..moved.end).collect();
+                    assert_eq!(actual, expected);
+                    Ok(())
+                },
+            )
+            .unwrap();
+    }
+}
@@ -0,0 +1,266 @@
+use num::rational::Ratio;
+use num::{Integer, PrimInt, Signed};
+
+pub use num::integer::{gcd, lcm};
+
+/// The greatest common divisor `g` of `a` and `b` together with coefficients `x` and `y`
+/// such that `a * x + b * y == g`. `g` is never negative.
+pub fn extended_gcd<T: PrimInt + Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+
+    while !r.is_zero() {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r.is_negative() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `a` modulo `modulus`, always in `0..modulus`.
+pub fn modulo<T: PrimInt + Signed>(a: T, modulus: T) -> T {
+    let remainder = a % modulus;
+    if remainder.is_negative() {
+        remainder + modulus.abs()
+    } else {
+        remainder
+    }
+}
+
+/// The `x` in `0..modulus` with `a * x ≡ 1 (mod modulus)`, or `None` if `a` and `modulus`
+/// are not coprime.
+pub fn mod_inverse<T: PrimInt + Signed>(a: T, modulus: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(modulo(a, modulus), modulus);
+    g.is_one().then(|| modulo(x, modulus))
+}
+
+/// `a * b` modulo `modulus` for `a` and `b` in `0..modulus`, without overflowing even if
+/// the product does not fit in `T`.
+pub fn mod_mul<T: PrimInt>(a: T, b: T, modulus: T) -> T {
+    if let Some(product) = a.checked_mul(&b) {
+        return product % modulus;
+    }
+    // Double and add, keeping every intermediate value below `modulus`.
+    let add = |x: T, y: T| {
+        if x >= modulus - y {
+            x - (modulus - y)
+        } else {
+            x + y
+        }
+    };
+    let (mut result, mut a, mut b) = (T::zero(), a, b);
+    while !b.is_zero() {
+        if (b & T::one()).is_one() {
+            result = add(result, a);
+        }
+        a = add(a, a);
+        b = b >> 1;
+    }
+    result
+}
+
+/// `base` to the power of `exponent` modulo `modulus`, by repeated squaring. `exponent`
+/// must not be negative.
+pub fn mod_pow<T: PrimInt>(base: T, exponent: T, modulus: T) -> T {
+    let (mut result, mut base, mut exponent) = (T::one() % modulus, base % modulus, exponent);
+    while exponent > T::zero() {
+        if (exponent & T::one()).is_one() {
+            result = mod_mul(result, base, modulus);
+        }
+        base = mod_mul(base, base, modulus);
+        exponent = exponent >> 1;
+    }
+    result
+}
+
+/// Solves the system `x ≡ residue (mod modulus)` for every pair of `congruences` with the
+/// Chinese remainder theorem. The moduli need not be coprime. Returns the smallest
+/// non-negative solution and the modulus it repeats with, or `None` if the congruences
+/// contradict each other or that modulus does not fit in `T`.
+pub fn crt<T: PrimInt + Signed>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    congruences
+        .iter()
+        .try_fold((T::zero(), T::one()), |(r1, m1), &(r2, m2)| {
+            let m2 = m2.abs();
+            let (g, p, _) = extended_gcd(m1, m2);
+            let difference = modulo(r2, m2) - modulo(r1, m2);
+            if !(difference % g).is_zero() {
+                return None;
+            }
+            // r1 + m1 * k solves both for k ≡ (difference / g) * p (mod m2 / g).
+            let step = m2 / g;
+            let k = mod_mul(modulo(difference / g, step), modulo(p, step), step);
+            let modulus = (m1 / g).checked_mul(&m2)?;
+            let residue = modulo(r1, modulus).checked_add(&m1.checked_mul(&k)?)?;
+            Some((residue % modulus, modulus))
+        })
+}
+
+/// The largest integer whose square is at most `n`. `n` must not be negative.
+pub fn isqrt<T: PrimInt>(n: T) -> T {
+    if n < T::one() + T::one() {
+        return n;
+    }
+    // The floating point guess is off by at most a little for large values; correct it.
+    let mut root = n
+        .to_f64()
+        .and_then(|n| T::from(n.sqrt()))
+        .unwrap_or_else(|| {
+            T::one() << ((T::zero().count_zeros() - n.leading_zeros()) as usize / 2)
+        });
+    let fits = |root: T| root.checked_mul(&root).is_some_and(|square| square <= n);
+    while !fits(root) {
+        root = root - T::one();
+    }
+    while fits(root + T::one()) {
+        root = root + T::one();
+    }
+    root
+}
+
+/// Solves `matrix * x = rhs` for a square `matrix` exactly, using fraction-free Gaussian
+/// elimination so that every intermediate value stays an integer. Returns `None` if the
+/// system has no unique solution. Intermediate values grow up to the size of the
+/// determinant; pick a `T` wide enough for that.
+pub fn solve_linear<T: PrimInt + Signed + Integer>(
+    matrix: &[Vec<T>],
+    rhs: &[T],
+) -> Option<Vec<Ratio<T>>> {
+    let n = rhs.len();
+    if matrix.len() != n {
+        return None;
+    }
+    let mut rows: Vec<Vec<T>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &value)| row.iter().copied().chain([value]).collect())
+        .collect();
+    if rows.iter().any(|row| row.len() != n + 1) {
+        return None;
+    }
+
+    // Bareiss elimination: each step divides exactly by the previous pivot.
+    let mut previous = T::one();
+    for k in 0..n {
+        let pivot = (k..n).find(|&i| !rows[i][k].is_zero())?;
+        rows.swap(k, pivot);
+        for i in k + 1..n {
+            for j in k + 1..=n {
+                rows[i][j] = (rows[i][j] * rows[k][k] - rows[i][k] * rows[k][j]) / previous;
+            }
+            rows[i][k] = T::zero();
+        }
+        previous = rows[k][k];
+    }
+
+    // Back substitution on the upper triangular system.
+    let mut solution = vec![Ratio::from_integer(T::zero()); n];
+    for i in (0..n).rev() {
+        let sum = (i + 1..n).fold(Ratio::from_integer(rows[i][n]), |sum, j| {
+            sum - solution[j] * rows[i][j]
+        });
+        solution[i] = sum / rows[i][i];
+    }
+    Some(solution)
+}
+
+mod test {
+    #[test]
+    /// Known values of every function.
+    fn test_math() {
+        use crate::utils::math::{crt, extended_gcd, isqrt, mod_inverse, mod_pow, solve_linear};
+        use num::rational::Ratio;
+
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(-4, 6), (2, 1, 1));
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 10), None);
+        assert_eq!(mod_pow(4u32, 13, 497), 445);
+        assert_eq!(mod_pow(3u64, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX - 1, 2, u64::MAX), 1);
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt::<i64>(&[]), Some((0, 1)));
+        assert_eq!(isqrt(0u8), 0);
+        assert_eq!(isqrt(99), 9);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+
+        let matrix = vec![vec![94, 22], vec![34, 67]];
+        assert_eq!(
+            solve_linear(&matrix, &[8400, 5400]),
+            Some(vec![Ratio::from_integer(80), Ratio::from_integer(40)])
+        );
+        let matrix = vec![vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]];
+        let solution: Vec<Ratio<i64>> = [2, 3, -1].map(Ratio::from_integer).to_vec();
+        assert_eq!(solve_linear(&matrix, &[8, -11, -3]), Some(solution));
+        assert_eq!(
+            solve_linear(&[vec![2, 0], vec![0, 4]], &[1, 1]).unwrap()[1],
+            Ratio::new(1, 4)
+        );
+        assert_eq!(solve_linear(&[vec![1, 2], vec![2, 4]], &[3, 6]), None);
+    }
+
+    #[test]
+    /// The results satisfy the equations they solve.
+    fn test_math_properties() {
+        use crate::utils::math::{crt, extended_gcd, isqrt, mod_inverse, solve_linear};
+        use num::rational::Ratio;
+        use proptest::collection::vec;
+        use proptest::test_runner::TestRunner;
+
+        let mut runner = TestRunner::default();
+        runner
+            .run(
+                &(-1000i64..1000, -1000i64..1000, 1i64..500, 0u64..1 << 40),
+                |(a, b, modulus, n)| {
+                    let (g, x, y) = extended_gcd(a, b);
+                    assert_eq!(a * x + b * y, g);
+                    assert_eq!(g, num::integer::gcd(a, b));
+
+                    match mod_inverse(a, modulus) {
+                        Some(inverse) => assert_eq!((a * inverse).rem_euclid(modulus), 1 % modulus),
+                        None => assert_ne!(num::integer::gcd(a, modulus), 1),
+                    }
+
+                    let root = isqrt(n);
+                    assert!(root * root <= n && n < (root + 1) * (root + 1));
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+        runner
+            .run(&vec((-50i64..50, 1i64..30), 1..4), |congruences| {
+                let brute = (0..30i64.pow(3))
+                    .find(|x| congruences.iter().all(|&(r, m)| (x - r).rem_euclid(m) == 0));
+                assert_eq!(crt(&congruences).map(|(x, _)| x), brute);
+                Ok(())
+            })
+            .unwrap();
+
+        runner
+            .run(&(vec(-20i64..20, 9), vec(-20i64..20, 3)), |(cells, rhs)| {
+                let matrix: Vec<Vec<i64>> = cells.chunks(3).map(|row| row.to_vec()).collect();
+                if let Some(solution) = solve_linear(&matrix, &rhs) {
+                    for (row, &value) in matrix.iter().zip(&rhs) {
+                        let sum = row
+                            .iter()
+                            .zip(&solution)
+                            .fold(Ratio::from_integer(0), |sum, (&a, x)| sum + x * a);
+                        assert_eq!(sum, Ratio::from_integer(value));
+                    }
+                }
+                Ok(())
+            })
+            .unwrap();
+    }
+}
@@ -1 +1,10 @@
+pub mod bounded;
+pub mod circuit;
+pub mod cycle;
+pub mod grid;
+pub mod interval;
+pub mod math;
+pub mod parse;
 pub mod point;
+pub mod search;
+pub mod three_bit;
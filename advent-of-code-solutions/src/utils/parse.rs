@@ -0,0 +1,261 @@
+use std::str::FromStr;
+
+use crate::aoc_solution::SolveError;
+use crate::utils::grid::Grid;
+
+/// A non-blank line of the input. Errors created from it point into the original line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    /// The 1-based line number in the input.
+    pub number: usize,
+    /// The line without leading and trailing whitespace.
+    pub text: &'a str,
+    raw: &'a str,
+}
+
+impl<'a> Line<'a> {
+    fn new(number: usize, raw: &'a str) -> Self {
+        Line {
+            number,
+            text: raw.trim(),
+            raw,
+        }
+    }
+
+    /// A parse error pointing at the start of the line.
+    pub fn error(&self, message: impl Into<String>) -> SolveError {
+        self.error_at(self.text, message)
+    }
+
+    /// A parse error pointing at `token`, which must be a slice of the line.
+    pub fn error_at(&self, token: &str, message: impl Into<String>) -> SolveError {
+        SolveError::parse_at(self.number, self.raw, token, message)
+    }
+
+    /// Parses `token`, a slice of the line, ignoring surrounding whitespace. On failure the
+    /// error points at the token and says what was `expected`.
+    pub fn number<T: FromStr>(&self, token: &'a str, expected: &str) -> Result<T, SolveError> {
+        let token = token.trim();
+        token
+            .parse()
+            .map_err(|_| self.error_at(token, format!("expected {}", expected)))
+    }
+
+    /// Parses every item of `list`, a slice of the line separated by commas and/or
+    /// whitespace.
+    pub fn list<T: FromStr>(&self, list: &'a str, expected: &str) -> Result<Vec<T>, SolveError> {
+        list.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|item| !item.is_empty())
+            .map(|item| self.number(item, expected))
+            .collect()
+    }
+
+    /// Every run of digits in the line, ignoring all other text.
+    pub fn unsigned<T: FromStr>(&self) -> Result<Vec<T>, SolveError> {
+        self.integers(false)
+    }
+
+    /// Every run of digits in the line, ignoring all other text. A `-` right before the
+    /// digits makes the number negative.
+    pub fn signed<T: FromStr>(&self) -> Result<Vec<T>, SolveError> {
+        self.integers(true)
+    }
+
+    fn integers<T: FromStr>(&self, signed: bool) -> Result<Vec<T>, SolveError> {
+        let bytes = self.text.as_bytes();
+        let mut numbers = Vec::new();
+        let mut index = 0;
+
+        while index < bytes.len() {
+            if !bytes[index].is_ascii_digit() {
+                index += 1;
+                continue;
+            }
+            let start = if signed && index > 0 && bytes[index - 1] == b'-' {
+                index - 1
+            } else {
+                index
+            };
+            while index < bytes.len() && bytes[index].is_ascii_digit() {
+                index += 1;
+            }
+            let token = &self.text[start..index];
+            let number = token
+                .parse()
+                .map_err(|_| self.error_at(token, "number out of range"))?;
+            numbers.push(number);
+        }
+        Ok(numbers)
+    }
+
+    /// Splits the line at the first `separator` into two trimmed halves. `expected`
+    /// describes the line for the error if there is no separator.
+    pub fn split_once(
+        &self,
+        separator: &str,
+        expected: &str,
+    ) -> Result<(&'a str, &'a str), SolveError> {
+        self.text
+            .split_once(separator)
+            .map(|(left, right)| (left.trim(), right.trim()))
+            .ok_or_else(|| self.error(format!("expected {}", expected)))
+    }
+
+    /// The trimmed rest of a line that must start with `prefix`, like the value of
+    /// `Register A: 729` for the prefix `Register A:`.
+    pub fn value_of(&self, prefix: &str) -> Result<&'a str, SolveError> {
+        self.text
+            .strip_prefix(prefix)
+            .map(str::trim)
+            .ok_or_else(|| self.error(format!("expected a line starting with '{}'", prefix)))
+    }
+
+    /// Splits a `key: value` line at the first colon.
+    pub fn key_value(&self) -> Result<(&'a str, &'a str), SolveError> {
+        self.split_once(":", "a line like '<key>: <value>'")
+    }
+}
+
+/// The non-blank lines of `input`, numbered from 1.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    numbered(input, 1)
+}
+
+fn numbered(text: &str, first: usize) -> impl Iterator<Item = Line<'_>> {
+    (first..)
+        .zip(text.lines())
+        .filter(|(_, raw)| !raw.trim().is_empty())
+        .map(|(number, raw)| Line::new(number, raw))
+}
+
+/// Every run of digits in the input. See [`Line::unsigned`].
+pub fn unsigned<T: FromStr>(input: &str) -> Result<Vec<T>, SolveError> {
+    lines(input).try_fold(Vec::new(), |mut numbers, line| {
+        numbers.extend(line.unsigned()?);
+        Ok(numbers)
+    })
+}
+
+/// Every run of digits in the input, with signs. See [`Line::signed`].
+pub fn signed<T: FromStr>(input: &str) -> Result<Vec<T>, SolveError> {
+    lines(input).try_fold(Vec::new(), |mut numbers, line| {
+        numbers.extend(line.signed()?);
+        Ok(numbers)
+    })
+}
+
+/// A block of consecutive non-blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// The 1-based number of the first line of the block in the input.
+    pub first_line: usize,
+    /// The lines of the block, unchanged.
+    pub text: &'a str,
+}
+
+impl<'a> Section<'a> {
+    /// The lines of the block, numbered as in the whole input.
+    pub fn lines(&self) -> impl Iterator<Item = Line<'a>> {
+        numbered(self.text, self.first_line)
+    }
+
+    /// A parse error pointing at the start of the block.
+    pub fn error(&self, message: impl Into<String>) -> SolveError {
+        SolveError::parse(self.first_line, 1, message)
+    }
+
+    /// Parses the block as a grid with [`Grid::parse`]; errors carry input line numbers.
+    pub fn grid<T, F>(&self, cell: F) -> Result<Grid<T>, SolveError>
+    where
+        F: FnMut(char) -> Result<T, &'static str>,
+    {
+        Grid::parse(self.text, cell).map_err(|error| match error {
+            SolveError::Parse {
+                line,
+                column,
+                message,
+            } => SolveError::parse(line + self.first_line - 1, column, message),
+            error => error,
+        })
+    }
+}
+
+/// Splits `input` into blocks separated by blank lines.
+pub fn sections(input: &str) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    let mut current: Option<(usize, usize, usize)> = None; // First line, start and end offset
+
+    for (number, raw) in (1..).zip(input.lines()) {
+        let start = raw.as_ptr() as usize - input.as_ptr() as usize;
+        if raw.trim().is_empty() {
+            if let Some((first_line, from, to)) = current.take() {
+                sections.push(Section {
+                    first_line,
+                    text: &input[from..to],
+                });
+            }
+        } else {
+            let (_, _, end) = current.get_or_insert((number, start, start));
+            *end = start + raw.len();
+        }
+    }
+    if let Some((first_line, from, to)) = current {
+        sections.push(Section {
+            first_line,
+            text: &input[from..to],
+        });
+    }
+    sections
+}
+
+mod test {
+    #[test]
+    /// Numbers are extracted from arbitrary text and errors point into the input.
+    fn test_parse_lines() {
+        use crate::aoc_solution::SolveError;
+        use crate::utils::parse::{lines, signed, unsigned};
+
+        let input = "\n  Button A: X+94, Y=-34\n\nRegister A: 729\n";
+        let all: Vec<Vec<i64>> = lines(input).map(|line| line.signed().unwrap()).collect();
+        assert_eq!(all, vec![vec![94, -34], vec![729]]);
+        assert_eq!(unsigned::<u32>(input).unwrap(), vec![94, 34, 729]);
+        assert_eq!(signed::<i32>("p=0,4 v=3,-3").unwrap(), vec![0, 4, 3, -3]);
+
+        let line = lines(input).nth(1).unwrap();
+        assert_eq!(line.number, 4);
+        assert_eq!(line.value_of("Register A:").unwrap(), "729");
+        assert_eq!(line.key_value().unwrap(), ("Register A", "729"));
+        assert!(line.value_of("Program:").is_err());
+
+        let line = lines("300: 10 19\n").next().unwrap();
+        let (key, values) = line.split_once(":", "a test value").unwrap();
+        assert_eq!(line.number::<u64>(key, "a number").unwrap(), 300);
+        assert_eq!(line.list::<u16>(values, "a number").unwrap(), vec![10, 19]);
+        assert_eq!(
+            line.list::<u8>(key, "a small number"),
+            Err(SolveError::parse(1, 1, "expected a small number"))
+        );
+        assert_eq!(
+            lines("  1 99999999999").next().unwrap().unsigned::<u32>(),
+            Err(SolveError::parse(1, 5, "number out of range"))
+        );
+    }
+
+    #[test]
+    /// Blank lines split the input into sections that keep their line numbers.
+    fn test_parse_sections() {
+        use crate::aoc_solution::SolveError;
+        use crate::utils::parse::sections;
+
+        let input = "\n#.\n.#\n\n\n<>\n^v\n  \nx\n";
+        let blocks = sections(input);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!((blocks[1].first_line, blocks[1].text), (6, "<>\n^v"));
+        assert_eq!(blocks[2].lines().next().unwrap().number, 9);
+
+        let grid = blocks[0].grid(|c| Ok(c == '#')).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        let error = blocks[1].grid(|c| if c == 'v' { Err("bad") } else { Ok(c) });
+        assert_eq!(error, Err(SolveError::parse(7, 2, "bad")));
+    }
+}
@@ -1,5 +1,206 @@
-use num::traits::{Num, Signed};
-use std::ops::{Add, Sub};
+use num::traits::{Num, NumCast, One, Signed, ToPrimitive, Zero};
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// What `Point`, `Point3` and `PointN` have in common, so that distances, neighbours and
+/// bounding boxes can be written once for any number of dimensions.
+pub trait Position: Copy + Eq + Hash + Add<Output = Self> + Sub<Output = Self> {
+    type Scalar: Signed + Copy + Ord;
+
+    const DIMENSIONS: usize;
+
+    /// The coordinate along `axis`, counting from 0.
+    fn axis(&self, axis: usize) -> Self::Scalar;
+
+    /// Builds a position from the coordinate of every axis.
+    fn from_axes(axis: impl FnMut(usize) -> Self::Scalar) -> Self;
+
+    /// Calculates the Manhattan distance between two positions.
+    fn manhattan(&self, other: Self) -> Self::Scalar {
+        (0..Self::DIMENSIONS).fold(Self::Scalar::zero(), |sum, i| {
+            sum + (self.axis(i) - other.axis(i)).abs()
+        })
+    }
+
+    /// Calculates the Chebyshev distance: the largest difference along any axis.
+    fn chebyshev(&self, other: Self) -> Self::Scalar {
+        (0..Self::DIMENSIONS).fold(Self::Scalar::zero(), |max, i| {
+            max.max((self.axis(i) - other.axis(i)).abs())
+        })
+    }
+
+    /// The `2 * DIMENSIONS` neighbours one step away along a single axis.
+    fn axis_neighbors(&self) -> Vec<Self> {
+        let one = Self::Scalar::one();
+        (0..Self::DIMENSIONS)
+            .flat_map(|axis| [-one, one].map(|step| (axis, step)))
+            .map(|(axis, step)| {
+                *self
+                    + Self::from_axes(|i| {
+                        if i == axis {
+                            step
+                        } else {
+                            Self::Scalar::zero()
+                        }
+                    })
+            })
+            .collect()
+    }
+
+    /// The `3^DIMENSIONS - 1` neighbours that differ by at most one along every axis.
+    fn all_neighbors(&self) -> Vec<Self> {
+        let count = 3usize.pow(Self::DIMENSIONS as u32);
+        (0..count)
+            .filter(|&index| index != count / 2) // The offset of all zeros
+            .map(|index| {
+                // Read `index` as base-3 digits 0, 1, 2 for the steps -1, 0, 1.
+                let offset = Self::from_axes(|i| match index / 3usize.pow(i as u32) % 3 {
+                    0 => -Self::Scalar::one(),
+                    1 => Self::Scalar::zero(),
+                    _ => Self::Scalar::one(),
+                });
+                *self + offset
+            })
+            .collect()
+    }
+
+    /// The smallest coordinate of both positions along every axis.
+    fn min_axes(&self, other: Self) -> Self {
+        Self::from_axes(|i| self.axis(i).min(other.axis(i)))
+    }
+
+    /// The largest coordinate of both positions along every axis.
+    fn max_axes(&self, other: Self) -> Self {
+        Self::from_axes(|i| self.axis(i).max(other.axis(i)))
+    }
+}
+
+/// The smallest and the largest corner of the box enclosing all `positions`,
+/// or `None` if there are none.
+pub fn bounding_box<P: Position>(positions: impl IntoIterator<Item = P>) -> Option<(P, P)> {
+    positions.into_iter().fold(None, |bounds, p| match bounds {
+        None => Some((p, p)),
+        Some((min, max)) => Some((min.min_axes(p), max.max_axes(p))),
+    })
+}
+
+/// A compass direction on a grid whose rows grow downwards, so north is `y - 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction {
+    /// The four orthogonal directions, clockwise from north.
+    pub const ORTHOGONAL: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+
+    /// All eight directions, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::N,
+        Direction::NE,
+        Direction::E,
+        Direction::SE,
+        Direction::S,
+        Direction::SW,
+        Direction::W,
+        Direction::NW,
+    ];
+
+    /// Parses one of the arrows `^`, `>`, `v` and `<`.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Direction::N),
+            '>' => Some(Direction::E),
+            'v' => Some(Direction::S),
+            '<' => Some(Direction::W),
+            _ => None,
+        }
+    }
+
+    /// The arrow of an orthogonal direction.
+    pub fn arrow(self) -> Option<char> {
+        match self {
+            Direction::N => Some('^'),
+            Direction::E => Some('>'),
+            Direction::S => Some('v'),
+            Direction::W => Some('<'),
+            _ => None,
+        }
+    }
+
+    /// Parses one of the letters `U`, `R`, `D` and `L`.
+    pub fn from_letter(c: char) -> Option<Self> {
+        match c {
+            'U' => Some(Direction::N),
+            'R' => Some(Direction::E),
+            'D' => Some(Direction::S),
+            'L' => Some(Direction::W),
+            _ => None,
+        }
+    }
+
+    /// The letter of an orthogonal direction.
+    pub fn letter(self) -> Option<char> {
+        match self {
+            Direction::N => Some('U'),
+            Direction::E => Some('R'),
+            Direction::S => Some('D'),
+            Direction::W => Some('L'),
+            _ => None,
+        }
+    }
+
+    /// Turns 90 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        self.turn(2)
+    }
+
+    /// Turns 90 degrees counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        self.turn(6)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// Turns clockwise in steps of 45 degrees.
+    fn turn(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % 8]
+    }
+}
+
+/// The unit vector of a direction.
+impl<T> From<Direction> for Point<T>
+where
+    T: Signed + Copy,
+{
+    fn from(direction: Direction) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        let (x, y) = match direction {
+            Direction::N => (zero, -one),
+            Direction::NE => (one, -one),
+            Direction::E => (one, zero),
+            Direction::SE => (one, one),
+            Direction::S => (zero, one),
+            Direction::SW => (-one, one),
+            Direction::W => (-one, zero),
+            Direction::NW => (-one, -one),
+        };
+        Point { x, y }
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Point<T> {
@@ -37,6 +238,39 @@ where
     pub fn abs(&self) -> Point<T> {
         Point::new(self.x.abs(), self.y.abs())
     }
+
+    /// The four orthogonal neighbours, clockwise from north.
+    pub fn neighbors4(&self) -> [Point<T>; 4] {
+        Direction::ORTHOGONAL.map(|direction| *self + direction.into())
+    }
+
+    /// The eight surrounding neighbours, clockwise from north.
+    pub fn neighbors8(&self) -> [Point<T>; 8] {
+        Direction::ALL.map(|direction| *self + direction.into())
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Signed + Copy + Ord,
+{
+    /// Calculates the Chebyshev distance: the number of king moves between two points.
+    pub fn chebyshev(&self, other: Point<T>) -> T {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+}
+
+impl<T> Point<T>
+where
+    T: ToPrimitive + Copy,
+{
+    /// Converts to another integer width, or `None` if a coordinate does not fit.
+    pub fn checked_cast<U: NumCast>(&self) -> Option<Point<U>> {
+        Some(Point {
+            x: U::from(self.x)?,
+            y: U::from(self.y)?,
+        })
+    }
 }
 
 impl<T> Point<T>
@@ -76,3 +310,285 @@ where
         }
     }
 }
+
+impl<T> AddAssign for Point<T>
+where
+    T: Num + Copy,
+{
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T> SubAssign for Point<T>
+where
+    T: Num + Copy,
+{
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+/// Scales both coordinates.
+impl<T> Mul<T> for Point<T>
+where
+    T: Num + Copy,
+{
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self {
+            x: self.x * factor,
+            y: self.y * factor,
+        }
+    }
+}
+
+impl<T> Neg for Point<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T> Position for Point<T>
+where
+    T: Signed + Copy + Ord + Hash,
+{
+    type Scalar = T;
+
+    const DIMENSIONS: usize = 2;
+
+    fn axis(&self, axis: usize) -> T {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => panic!("a point has no axis {}", axis),
+        }
+    }
+
+    fn from_axes(mut axis: impl FnMut(usize) -> T) -> Self {
+        Point {
+            x: axis(0),
+            y: axis(1),
+        }
+    }
+}
+
+/// A point in three dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T> Position for Point3<T>
+where
+    T: Signed + Copy + Ord + Hash,
+{
+    type Scalar = T;
+
+    const DIMENSIONS: usize = 3;
+
+    fn axis(&self, axis: usize) -> T {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            _ => panic!("a 3D point has no axis {}", axis),
+        }
+    }
+
+    fn from_axes(mut axis: impl FnMut(usize) -> T) -> Self {
+        Point3 {
+            x: axis(0),
+            y: axis(1),
+            z: axis(2),
+        }
+    }
+}
+
+impl<T> Add for Point3<T>
+where
+    T: Num + Copy,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point3 {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl<T> Sub for Point3<T>
+where
+    T: Num + Copy,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point3 {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+/// Scales all three coordinates.
+impl<T> Mul<T> for Point3<T>
+where
+    T: Num + Copy,
+{
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Point3 {
+            x: self.x * factor,
+            y: self.y * factor,
+            z: self.z * factor,
+        }
+    }
+}
+
+/// A point with `N` coordinates, for puzzles in four or more dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PointN<T, const N: usize>(pub [T; N]);
+
+impl<T, const N: usize> Position for PointN<T, N>
+where
+    T: Signed + Copy + Ord + Hash,
+{
+    type Scalar = T;
+
+    const DIMENSIONS: usize = N;
+
+    fn axis(&self, axis: usize) -> T {
+        self.0[axis]
+    }
+
+    fn from_axes(axis: impl FnMut(usize) -> T) -> Self {
+        PointN(std::array::from_fn(axis))
+    }
+}
+
+impl<T, const N: usize> Add for PointN<T, N>
+where
+    T: Num + Copy,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        PointN(std::array::from_fn(|i| self.0[i] + other.0[i]))
+    }
+}
+
+impl<T, const N: usize> Sub for PointN<T, N>
+where
+    T: Num + Copy,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        PointN(std::array::from_fn(|i| self.0[i] - other.0[i]))
+    }
+}
+
+/// Scales every coordinate.
+impl<T, const N: usize> Mul<T> for PointN<T, N>
+where
+    T: Num + Copy,
+{
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        PointN(self.0.map(|coordinate| coordinate * factor))
+    }
+}
+
+mod test {
+    #[test]
+    /// Directions turn, convert to unit vectors and round-trip through their characters.
+    fn test_direction() {
+        use crate::utils::point::{Direction, Point};
+
+        assert_eq!(Direction::N.turn_right(), Direction::E);
+        assert_eq!(Direction::N.turn_left(), Direction::W);
+        assert_eq!(Direction::NE.opposite(), Direction::SW);
+        assert_eq!(Point::<i32>::from(Direction::SE), Point::new(1, 1));
+
+        for direction in Direction::ORTHOGONAL {
+            let arrow = direction.arrow().unwrap();
+            assert_eq!(Direction::from_arrow(arrow), Some(direction));
+            let letter = direction.letter().unwrap();
+            assert_eq!(Direction::from_letter(letter), Some(direction));
+        }
+        assert_eq!(Direction::NW.arrow(), None);
+    }
+
+    #[test]
+    /// Point arithmetic, distances and width conversions.
+    fn test_point_algebra() {
+        use crate::utils::point::Point;
+
+        let mut p = Point::new(2i32, -3);
+        assert_eq!(p * 2, Point::new(4, -6));
+        assert_eq!(-p, Point::new(-2, 3));
+        p += Point::new(1, 1);
+        assert_eq!(p, Point::new(3, -2));
+        assert_eq!(p.chebyshev(Point::new(0, 0)), 3);
+        assert_eq!(p.manhattan(Point::new(0, 0)), 5);
+        assert_eq!(p.neighbors4().len(), 4);
+        assert!(p.neighbors8().contains(&Point::new(4, -1)));
+
+        assert_eq!(p.checked_cast::<i8>(), Some(Point::new(3i8, -2)));
+        assert_eq!(p.checked_cast::<u8>(), None);
+        assert_eq!(Point::new(300i32, 0).checked_cast::<u8>(), None);
+    }
+
+    #[test]
+    /// Distances, neighbours and bounding boxes agree across dimensions.
+    fn test_positions() {
+        use crate::utils::point::{bounding_box, Point, Point3, PointN, Position};
+
+        let a = Point3::new(1i32, 2, 3);
+        let b = Point3::new(2, 0, 3);
+        assert_eq!(Position::manhattan(&a, b), 3);
+        assert_eq!(Position::chebyshev(&a, b), 2);
+        assert_eq!(a.axis_neighbors().len(), 6);
+        assert_eq!(a.all_neighbors().len(), 26);
+        assert!(!a.all_neighbors().contains(&a));
+
+        // The trait agrees with the two-dimensional helpers.
+        let p = Point::new(0i32, 0);
+        assert_eq!(Position::manhattan(&p, Point::new(3, -4)), 7);
+        assert_eq!(p.axis_neighbors().len(), p.neighbors4().len());
+        assert_eq!(p.all_neighbors().len(), p.neighbors8().len());
+
+        let q = PointN([0i64, 0, 0, 0]);
+        assert_eq!(q.axis_neighbors().len(), 8);
+        assert_eq!(q.all_neighbors().len(), 80);
+        assert_eq!(Position::manhattan(&q, PointN([1, -1, 2, 0])), 4);
+
+        let corners = bounding_box([a, b, Point3::new(0, 5, 1)]);
+        assert_eq!(corners, Some((Point3::new(0, 0, 1), Point3::new(2, 5, 3))));
+        assert_eq!(bounding_box(Vec::<Point3<i32>>::new()), None);
+    }
+}
@@ -0,0 +1,418 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+
+use hashbrown::{HashMap, HashSet};
+use pathfinding::prelude as pf;
+
+use crate::utils::grid::Grid;
+use crate::utils::point::Point;
+
+/// Anything that can list the successors of a node: grids, state spaces and adjacency maps.
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    /// The nodes one step away from `node`, together with the cost of that step.
+    /// Breadth-first searches ignore the costs.
+    fn successors(&self, node: &Self::Node) -> impl Iterator<Item = (Self::Node, u64)>;
+}
+
+/// A grid seen as a graph: every cell leads to those orthogonal neighbours that `step`
+/// allows, at a cost of one.
+pub struct GridGraph<'a, T, F> {
+    grid: &'a Grid<T>,
+    step: F,
+}
+
+impl<'a, T, F> GridGraph<'a, T, F>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    /// `step` decides from the current and the next cell whether the step is possible.
+    pub fn new(grid: &'a Grid<T>, step: F) -> Self {
+        GridGraph { grid, step }
+    }
+}
+
+impl<T, F> Graph for GridGraph<'_, T, F>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    type Node = Point<i32>;
+
+    fn successors(&self, node: &Point<i32>) -> impl Iterator<Item = (Point<i32>, u64)> {
+        let from = &self.grid[*node];
+        self.grid
+            .neighbors4(*node)
+            .filter(move |&next| (self.step)(from, &self.grid[next]))
+            .map(|next| (next, 1))
+    }
+}
+
+/// An undirected or directed graph given by the neighbours of every node.
+impl<N> Graph for HashMap<N, HashSet<N>>
+where
+    N: Clone + Eq + Hash,
+{
+    type Node = N;
+
+    fn successors(&self, node: &N) -> impl Iterator<Item = (N, u64)> {
+        self.get(node)
+            .into_iter()
+            .flatten()
+            .map(|next| (next.clone(), 1))
+    }
+}
+
+/// The result of a shortest-path search: the distance of every reached node and, for
+/// every node but the start, all its predecessors on shortest paths.
+#[derive(Debug)]
+pub struct Paths<N> {
+    start: N,
+    distances: HashMap<N, u64>,
+    predecessors: HashMap<N, Vec<N>>,
+}
+
+impl<N> Paths<N>
+where
+    N: Clone + Eq + Hash,
+{
+    fn new(start: N) -> Self {
+        Paths {
+            distances: HashMap::from([(start.clone(), 0)]),
+            start,
+            predecessors: HashMap::new(),
+        }
+    }
+
+    /// Records that `next` can be reached from `node` at `distance`. Returns whether this
+    /// is the first or a shorter way to `next`, so that it must be explored (again).
+    fn relax(&mut self, node: &N, next: N, distance: u64) -> bool {
+        match self.distances.get(&next) {
+            Some(&known) if known < distance => false,
+            Some(&known) if known == distance => {
+                if let Some(predecessors) = self.predecessors.get_mut(&next) {
+                    predecessors.push(node.clone());
+                }
+                false
+            }
+            _ => {
+                self.distances.insert(next.clone(), distance);
+                self.predecessors.insert(next, vec![node.clone()]);
+                true
+            }
+        }
+    }
+
+    pub fn start(&self) -> &N {
+        &self.start
+    }
+
+    /// The length of a shortest path to `node`, if it was reached.
+    pub fn distance(&self, node: &N) -> Option<u64> {
+        self.distances.get(node).copied()
+    }
+
+    /// The distance of every reached node.
+    pub fn distances(&self) -> &HashMap<N, u64> {
+        &self.distances
+    }
+
+    /// The nodes right before `node` on its shortest paths; empty for the start.
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// One shortest path from the start to `goal`, both included.
+    pub fn path_to(&self, goal: &N) -> Option<Vec<N>> {
+        self.distances.get(goal)?;
+        let mut path = vec![goal.clone()];
+        while let Some(previous) = self.predecessors(path.last()?).first() {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every shortest path from the start to `goal`. Their number can grow exponentially
+    /// with the length; use `on_shortest_paths` if only the nodes matter.
+    pub fn all_paths_to(&self, goal: &N) -> Vec<Vec<N>> {
+        if !self.distances.contains_key(goal) {
+            return Vec::new();
+        }
+        if *goal == self.start {
+            return vec![vec![goal.clone()]];
+        }
+        self.predecessors(goal)
+            .iter()
+            .flat_map(|previous| self.all_paths_to(previous))
+            .map(|mut path| {
+                path.push(goal.clone());
+                path
+            })
+            .collect()
+    }
+
+    /// All nodes on any shortest path to any of `goals`.
+    pub fn on_shortest_paths(&self, goals: &[N]) -> HashSet<N> {
+        let mut seen = HashSet::new();
+        let mut stack: Vec<N> = goals
+            .iter()
+            .filter(|goal| self.distances.contains_key(*goal))
+            .cloned()
+            .collect();
+        while let Some(node) = stack.pop() {
+            if seen.insert(node.clone()) {
+                stack.extend(self.predecessors(&node).iter().cloned());
+            }
+        }
+        seen
+    }
+}
+
+/// The goals found by `astar` at the lowest cost, and the paths that lead to them.
+#[derive(Debug)]
+pub struct Found<N> {
+    pub cost: u64,
+    pub goals: Vec<N>,
+    pub paths: Paths<N>,
+}
+
+/// Breadth-first search from `start`, counting every step as one.
+pub fn bfs<G: Graph>(graph: &G, start: G::Node) -> Paths<G::Node> {
+    let mut paths = Paths::new(start.clone());
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for (next, _) in graph.successors(&node) {
+            if paths.relax(&node, next.clone(), distance + 1) {
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    paths
+}
+
+/// Dijkstra's algorithm from `start`: the cheapest path to every reachable node.
+pub fn dijkstra<G: Graph>(graph: &G, start: G::Node) -> Paths<G::Node> {
+    best_first(graph, start, |_| 0, |_| false).0
+}
+
+/// A* search from `start` to the cheapest nodes matching `is_goal`. The `heuristic` must
+/// never overestimate the remaining cost, or the result may not be the cheapest.
+/// Exploration stops once every goal of the lowest cost has been found.
+pub fn astar<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    heuristic: impl Fn(&G::Node) -> u64,
+    is_goal: impl Fn(&G::Node) -> bool,
+) -> Option<Found<G::Node>> {
+    let (paths, goals) = best_first(graph, start, heuristic, is_goal);
+    let cost = paths.distance(goals.first()?)?;
+    Some(Found { cost, goals, paths })
+}
+
+/// The search behind `dijkstra` and `astar`. Returns the paths and the goals reached.
+fn best_first<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    heuristic: impl Fn(&G::Node) -> u64,
+    is_goal: impl Fn(&G::Node) -> bool,
+) -> (Paths<G::Node>, Vec<G::Node>) {
+    let mut paths = Paths::new(start.clone());
+    let mut goals = Vec::new();
+    let mut best_goal = None;
+
+    // The heap refers to nodes by their index in `nodes`, so nodes need not be ordered.
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+    let mut nodes = vec![start];
+
+    while let Some(Reverse((estimate, distance, index))) = heap.pop() {
+        if best_goal.is_some_and(|best| estimate > best) {
+            break;
+        }
+        let node = nodes[index].clone();
+        if paths.distance(&node).is_some_and(|known| distance > known) {
+            continue; // A shorter way to this node was found after it was queued
+        }
+        if is_goal(&node) {
+            best_goal = Some(distance);
+            goals.push(node);
+            continue;
+        }
+
+        for (next, cost) in graph.successors(&node) {
+            if paths.relax(&node, next.clone(), distance + cost) {
+                heap.push(Reverse((
+                    distance + cost + heuristic(&next),
+                    distance + cost,
+                    nodes.len(),
+                )));
+                nodes.push(next);
+            }
+        }
+    }
+    (paths, goals)
+}
+
+/// The successors of `node` without their costs. The pathfinding callbacks cannot
+/// return iterators that borrow the node they are given.
+fn successors<G: Graph>(graph: &G, node: &G::Node) -> Vec<G::Node> {
+    graph.successors(node).map(|(next, _)| next).collect()
+}
+
+/// All nodes reachable from `start`, including `start`.
+pub fn flood_fill<G: Graph>(graph: &G, start: G::Node) -> HashSet<G::Node> {
+    pf::bfs_reach(start, |node| successors(graph, node)).collect()
+}
+
+/// Splits `nodes` into groups that are connected to each other. The graph should be
+/// undirected, i.e. every step must also be possible backwards.
+pub fn connected_components<G: Graph>(graph: &G, nodes: &[G::Node]) -> Vec<Vec<G::Node>> {
+    pf::connected_components(nodes, |node| successors(graph, node))
+        .into_iter()
+        .map(|component| component.into_iter().collect())
+        .collect()
+}
+
+/// Orders `nodes` and everything reachable from them so that every node comes before its
+/// successors. Returns a node on a cycle if there is no such order.
+pub fn topological_sort<G: Graph>(graph: &G, nodes: &[G::Node]) -> Result<Vec<G::Node>, G::Node> {
+    pf::topological_sort(nodes, |node| successors(graph, node))
+}
+
+/// The number of distinct paths from `start` to nodes matching `is_goal`. The part of the
+/// graph reachable from `start` must be acyclic.
+pub fn count_paths<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(&G::Node) -> bool,
+) -> usize {
+    pf::count_paths(start, |node| successors(graph, node), |node| is_goal(node))
+}
+
+/// A largest set of `nodes` that are all connected to each other, found with the
+/// Bron-Kerbosch algorithm with pivoting. The graph must be undirected.
+pub fn max_clique<G: Graph>(graph: &G, nodes: &[G::Node]) -> Vec<G::Node> {
+    let neighbours: HashMap<G::Node, HashSet<G::Node>> = nodes
+        .iter()
+        .map(|node| {
+            let successors = graph.successors(node).map(|(next, _)| next).collect();
+            (node.clone(), successors)
+        })
+        .collect();
+
+    let mut best = Vec::new();
+    bron_kerbosch(
+        &neighbours,
+        &mut Vec::new(),
+        nodes.iter().cloned().collect(),
+        HashSet::new(),
+        &mut best,
+    );
+    best
+}
+
+/// Extends the clique `r` with candidates from `p`, never with nodes from `x`, and keeps
+/// the largest maximal clique in `best`.
+fn bron_kerbosch<N: Clone + Eq + Hash>(
+    neighbours: &HashMap<N, HashSet<N>>,
+    r: &mut Vec<N>,
+    mut p: HashSet<N>,
+    mut x: HashSet<N>,
+    best: &mut Vec<N>,
+) {
+    if p.is_empty() {
+        if x.is_empty() && r.len() > best.len() {
+            *best = r.clone();
+        }
+        return;
+    }
+
+    // Nodes connected to the pivot are found through one of its non-neighbours anyway.
+    let empty = HashSet::new();
+    let neighbours_of = |node: &N| neighbours.get(node).unwrap_or(&empty);
+    let pivot = p
+        .iter()
+        .chain(&x)
+        .max_by_key(|node| neighbours_of(node).len())
+        .cloned()
+        .expect("p is not empty");
+    let candidates: Vec<N> = p.difference(neighbours_of(&pivot)).cloned().collect();
+
+    for node in candidates {
+        let node_neighbours = neighbours_of(&node);
+        r.push(node.clone());
+        bron_kerbosch(
+            neighbours,
+            r,
+            p.intersection(node_neighbours).cloned().collect(),
+            x.intersection(node_neighbours).cloned().collect(),
+            best,
+        );
+        r.pop();
+        p.remove(&node);
+        x.insert(node);
+    }
+}
+
+mod test {
+    #[test]
+    /// Shortest paths on a small grid with two equally short ways around a wall.
+    fn test_search_paths() {
+        use crate::utils::grid::Grid;
+        use crate::utils::point::Point;
+        use crate::utils::search::{astar, bfs, dijkstra, GridGraph};
+
+        let grid = Grid::parse("...\n.#.\n...\n", Ok).unwrap();
+        let graph = GridGraph::new(&grid, |_, &to| to != '#');
+        let (start, goal) = (Point::new(0, 0), Point::new(2, 2));
+
+        let paths = bfs(&graph, start);
+        assert_eq!(paths.distance(&goal), Some(4));
+        assert_eq!(paths.distance(&Point::new(1, 1)), None);
+        assert_eq!(paths.path_to(&goal).unwrap().len(), 5);
+        assert_eq!(paths.all_paths_to(&goal).len(), 2);
+        assert_eq!(paths.on_shortest_paths(&[goal]).len(), 8);
+
+        assert_eq!(dijkstra(&graph, start).distances(), paths.distances());
+
+        let found = astar(&graph, start, |p| p.manhattan(goal) as u64, |&p| p == goal).unwrap();
+        assert_eq!((found.cost, found.goals), (4, vec![goal]));
+        assert_eq!(found.paths.on_shortest_paths(&[goal]).len(), 8);
+    }
+
+    #[test]
+    /// Components, orderings, path counts and cliques of small adjacency maps.
+    fn test_search_structure() {
+        use crate::utils::search::{
+            connected_components, count_paths, flood_fill, max_clique, topological_sort,
+        };
+        use hashbrown::{HashMap, HashSet};
+
+        let graph = |edges: &[(u8, u8)], undirected: bool| {
+            let mut graph: HashMap<u8, HashSet<u8>> = HashMap::new();
+            for &(a, b) in edges {
+                graph.entry(a).or_default().insert(b);
+                if undirected {
+                    graph.entry(b).or_default().insert(a);
+                }
+            }
+            graph
+        };
+
+        let dag = graph(&[(1, 2), (1, 3), (2, 4), (3, 4)], false);
+        assert_eq!(topological_sort(&dag, &[1]).unwrap()[0], 1);
+        assert_eq!(count_paths(&dag, 1, |&n| n == 4), 2);
+        assert_eq!(flood_fill(&dag, 2).len(), 2);
+        let cyclic = graph(&[(1, 2), (2, 1)], false);
+        assert!(topological_sort(&cyclic, &[1]).is_err());
+
+        let network = graph(&[(1, 2), (2, 3), (1, 3), (3, 4), (5, 6)], true);
+        let nodes: Vec<u8> = (1..=6).collect();
+        assert_eq!(connected_components(&network, &nodes).len(), 2);
+        let mut clique = max_clique(&network, &nodes);
+        clique.sort();
+        assert_eq!(clique, vec![1, 2, 3]);
+    }
+}
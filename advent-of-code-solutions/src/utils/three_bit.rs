@@ -0,0 +1,380 @@
+use std::fmt;
+
+/// The eight instructions of the 3-bit computer, numbered by their opcode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    /// A = A >> combo
+    Adv,
+    /// B = B ^ literal
+    Bxl,
+    /// B = combo % 8
+    Bst,
+    /// Jump to literal if A is not zero.
+    Jnz,
+    /// B = B ^ C; the operand is ignored.
+    Bxc,
+    /// Outputs combo % 8.
+    Out,
+    /// B = A >> combo
+    Bdv,
+    /// C = A >> combo
+    Cdv,
+}
+
+impl Opcode {
+    /// The instruction with opcode `bits`, if it is a 3-bit value.
+    pub fn from_bits(bits: u8) -> Option<Self> {
+        use Opcode::*;
+        [Adv, Bxl, Bst, Jnz, Bxc, Out, Bdv, Cdv]
+            .get(bits as usize)
+            .copied()
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            Opcode::Adv => "adv",
+            Opcode::Bxl => "bxl",
+            Opcode::Bst => "bst",
+            Opcode::Jnz => "jnz",
+            Opcode::Bxc => "bxc",
+            Opcode::Out => "out",
+            Opcode::Bdv => "bdv",
+            Opcode::Cdv => "cdv",
+        }
+    }
+
+    /// Whether the operand is a combo operand, which may name a register.
+    pub fn takes_combo(&self) -> bool {
+        matches!(
+            self,
+            Opcode::Adv | Opcode::Bst | Opcode::Out | Opcode::Bdv | Opcode::Cdv
+        )
+    }
+}
+
+/// An opcode together with its operand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub operand: u8,
+}
+
+/// Formats the instruction like `adv 3`, `out a` or `bxc`. Combo operands 4 to 6 are the
+/// registers; the reserved combo operand 7 is shown as `?7`.
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mnemonic = self.opcode.mnemonic();
+        match (self.opcode, self.operand) {
+            (Opcode::Bxc, _) => write!(f, "{}", mnemonic),
+            (opcode, 4) if opcode.takes_combo() => write!(f, "{} a", mnemonic),
+            (opcode, 5) if opcode.takes_combo() => write!(f, "{} b", mnemonic),
+            (opcode, 6) if opcode.takes_combo() => write!(f, "{} c", mnemonic),
+            (opcode, 7) if opcode.takes_combo() => write!(f, "{} ?7", mnemonic),
+            (_, operand) => write!(f, "{} {}", mnemonic, operand),
+        }
+    }
+}
+
+/// A program: a sequence of 3-bit values that alternate between opcodes and operands.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Program {
+    code: Vec<u8>,
+}
+
+impl Program {
+    /// The program made of `code`, or `None` if a value does not fit in 3 bits.
+    pub fn new(code: Vec<u8>) -> Option<Self> {
+        code.iter()
+            .all(|&value| value < 8)
+            .then_some(Program { code })
+    }
+
+    /// The 3-bit values of the program.
+    pub fn code(&self) -> &[u8] {
+        &self.code
+    }
+
+    /// The instruction at `address`, or `None` if the computer halts there.
+    pub fn instruction(&self, address: usize) -> Option<Instruction> {
+        match self.code.get(address..address.checked_add(2)?)? {
+            &[opcode, operand] => Some(Instruction {
+                opcode: Opcode::from_bits(opcode)?,
+                operand,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Lists every instruction with its address, one per line, like `  4: out a`.
+    /// An odd trailing value is listed as data.
+    pub fn disassemble(&self) -> String {
+        let mut listing = String::new();
+        for address in (0..self.code.len()).step_by(2) {
+            let line = match self.instruction(address) {
+                Some(instruction) => format!("{:3}: {}\n", address, instruction),
+                None => format!("{:3}: .{}\n", address, self.code[address]),
+            };
+            listing.push_str(&line);
+        }
+        listing
+    }
+}
+
+/// The three registers of the computer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Registers {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+}
+
+/// One executed instruction, as reported by [`Machine::step`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    /// Where the instruction was.
+    pub address: usize,
+    pub instruction: Instruction,
+    /// The registers after the instruction.
+    pub registers: Registers,
+    /// The value the instruction output, if it was an `out`.
+    pub output: Option<u8>,
+}
+
+/// Why [`Machine::run`] returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// The instruction pointer left the program.
+    Halted,
+    /// The next instruction is at one of the breakpoints.
+    Breakpoint(usize),
+    /// The step limit was reached first.
+    StepLimit,
+}
+
+/// A 3-bit computer running a program.
+#[derive(Debug, Clone)]
+pub struct Machine<'a> {
+    program: &'a Program,
+    pub registers: Registers,
+    /// The address of the next instruction.
+    pub pointer: usize,
+    output: Vec<u8>,
+    steps: usize,
+}
+
+impl<'a> Machine<'a> {
+    pub fn new(program: &'a Program, registers: Registers) -> Self {
+        Machine {
+            program,
+            registers,
+            pointer: 0,
+            output: Vec::new(),
+            steps: 0,
+        }
+    }
+
+    /// Everything output so far.
+    pub fn output(&self) -> &[u8] {
+        &self.output
+    }
+
+    /// How many instructions have been executed.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Whether there is no instruction left to execute.
+    pub fn is_halted(&self) -> bool {
+        self.program.instruction(self.pointer).is_none()
+    }
+
+    /// The value of a combo operand: 0 to 3 are literals, 4 to 6 the registers. The
+    /// reserved operand 7 reads as 0.
+    fn combo(&self, operand: u8) -> u64 {
+        match operand {
+            0..=3 => operand as u64,
+            4 => self.registers.a,
+            5 => self.registers.b,
+            6 => self.registers.c,
+            _ => 0,
+        }
+    }
+
+    /// A divided by 2 to the power of the combo operand. Large powers yield 0.
+    fn divide_a(&self, operand: u8) -> u64 {
+        u32::try_from(self.combo(operand))
+            .ok()
+            .and_then(|shift| self.registers.a.checked_shr(shift))
+            .unwrap_or(0)
+    }
+
+    /// Executes the next instruction, or returns `None` if the machine has halted.
+    pub fn step(&mut self) -> Option<Step> {
+        let address = self.pointer;
+        let instruction = self.program.instruction(address)?;
+        let operand = instruction.operand;
+        let mut output = None;
+        self.pointer += 2;
+
+        match instruction.opcode {
+            Opcode::Adv => self.registers.a = self.divide_a(operand),
+            Opcode::Bxl => self.registers.b ^= operand as u64,
+            Opcode::Bst => self.registers.b = self.combo(operand) % 8,
+            Opcode::Jnz if self.registers.a != 0 => self.pointer = operand as usize,
+            Opcode::Jnz => {}
+            Opcode::Bxc => self.registers.b ^= self.registers.c,
+            Opcode::Out => output = Some((self.combo(operand) % 8) as u8),
+            Opcode::Bdv => self.registers.b = self.divide_a(operand),
+            Opcode::Cdv => self.registers.c = self.divide_a(operand),
+        }
+
+        self.output.extend(output);
+        self.steps += 1;
+        Some(Step {
+            address,
+            instruction,
+            registers: self.registers,
+            output,
+        })
+    }
+
+    /// Runs until the machine halts or has executed `limit` more instructions.
+    pub fn run(&mut self, limit: usize) -> Stop {
+        self.run_to(&[], limit)
+    }
+
+    /// Like [`Machine::run`], but also stops before executing an instruction at one of
+    /// the `breakpoints`. The first instruction is always executed, so calling this again
+    /// continues past the breakpoint.
+    pub fn run_to(&mut self, breakpoints: &[usize], limit: usize) -> Stop {
+        for executed in 0..limit {
+            if executed > 0 && breakpoints.contains(&self.pointer) && !self.is_halted() {
+                return Stop::Breakpoint(self.pointer);
+            }
+            if self.step().is_none() {
+                return Stop::Halted;
+            }
+        }
+        if self.is_halted() {
+            Stop::Halted
+        } else {
+            Stop::StepLimit
+        }
+    }
+
+    /// The executed instructions, one per call of [`Iterator::next`], until the machine
+    /// halts. Use [`Iterator::take`] for programs that may not halt.
+    pub fn trace(&mut self) -> impl Iterator<Item = Step> + use<'_, 'a> {
+        std::iter::from_fn(move || self.step())
+    }
+}
+
+/// Runs `program` from `registers` and returns its output, or `None` if it does not halt
+/// within `limit` instructions.
+pub fn run(program: &Program, registers: Registers, limit: usize) -> Option<Vec<u8>> {
+    let mut machine = Machine::new(program, registers);
+    match machine.run(limit) {
+        Stop::Halted => Some(machine.output),
+        _ => None,
+    }
+}
+
+/// Finds the lowest positive value of register A for which `program` outputs `target`.
+/// Each candidate runs for at most `limit` instructions.
+///
+/// Works for any program that, like the quines of the puzzle, outputs one value per loop
+/// and shifts A right by 3 bits each time, so that the value output for the last loop
+/// only depends on the highest 3 bits of A. The search builds A from the highest 3 bits
+/// downwards, keeping only the choices that produce the end of `target`, and backtracks
+/// when a choice leads nowhere. Every answer is checked by running the program.
+pub fn find_register_a(
+    program: &Program,
+    registers: Registers,
+    target: &[u8],
+    limit: usize,
+) -> Option<u64> {
+    fn search(
+        program: &Program,
+        registers: Registers,
+        target: &[u8],
+        limit: usize,
+        prefix: u64,
+        matched: usize,
+    ) -> Option<u64> {
+        if matched == target.len() {
+            return (prefix > 0).then_some(prefix);
+        }
+        let suffix = &target[target.len() - matched - 1..];
+        (0..8).find_map(|chunk| {
+            let a = prefix.checked_mul(8)? | chunk;
+            let output = run(program, Registers { a, ..registers }, limit)?;
+            if output != suffix {
+                return None;
+            }
+            search(program, registers, target, limit, a, matched + 1)
+        })
+    }
+
+    search(program, registers, target, limit, 0, 0)
+}
+
+mod test {
+    #[test]
+    /// The disassembler names registers for combo operands only.
+    fn test_three_bit_disassemble() {
+        use crate::utils::three_bit::Program;
+
+        let program = Program::new(vec![2, 4, 1, 5, 7, 5, 4, 3, 5, 6, 0, 3, 3, 0, 1]).unwrap();
+        assert_eq!(
+            program.disassemble(),
+            "  0: bst a\n  2: bxl 5\n  4: cdv b\n  6: bxc\n  8: out c\n 10: adv 3\n 12: jnz 0\n 14: .1\n"
+        );
+        assert_eq!(Program::new(vec![0, 8]), None);
+    }
+
+    #[test]
+    /// Stepping, tracing, breakpoints and the reverse search on the examples of the puzzle.
+    fn test_three_bit_machine() {
+        use crate::utils::three_bit::{
+            find_register_a, run, Machine, Opcode, Program, Registers, Stop,
+        };
+
+        let program = Program::new(vec![0, 1, 5, 4, 3, 0]).unwrap();
+        let registers = Registers {
+            a: 729,
+            ..Registers::default()
+        };
+        assert_eq!(
+            run(&program, registers, 1000),
+            Some(vec![4, 6, 3, 5, 6, 3, 5, 2, 1, 0])
+        );
+
+        let mut machine = Machine::new(&program, registers);
+        let step = machine.step().unwrap();
+        assert_eq!((step.address, step.instruction.opcode), (0, Opcode::Adv));
+        assert_eq!(step.registers.a, 364);
+        assert_eq!(machine.run_to(&[4], 100), Stop::Breakpoint(4));
+        assert_eq!(machine.output(), &[4]);
+        let outputs: Vec<u8> = machine.trace().filter_map(|step| step.output).collect();
+        assert_eq!(outputs, vec![6, 3, 5, 6, 3, 5, 2, 1, 0]);
+        assert!(machine.is_halted());
+
+        // A program that never halts hits the step limit
+        let endless = Program::new(vec![3, 0]).unwrap();
+        let mut machine = Machine::new(&endless, Registers { a: 1, b: 0, c: 0 });
+        assert_eq!(machine.run(50), Stop::StepLimit);
+        assert_eq!(machine.steps(), 50);
+
+        // Registers hold values beyond 32 bits
+        let quine = Program::new(vec![0, 3, 5, 4, 3, 0]).unwrap();
+        let a = find_register_a(&quine, Registers::default(), quine.code(), 1000);
+        assert_eq!(a, Some(117440));
+        let wide = Program::new(vec![2, 4, 1, 7, 7, 5, 0, 3, 4, 4, 1, 7, 5, 5, 3, 0]).unwrap();
+        let a = find_register_a(&wide, Registers::default(), wide.code(), 1000).unwrap();
+        let registers = Registers {
+            a,
+            ..Registers::default()
+        };
+        assert_eq!(run(&wide, registers, 1000).as_deref(), Some(wide.code()));
+    }
+}
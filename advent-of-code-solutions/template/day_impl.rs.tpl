@@ -1,42 +1,41 @@
-use super::day{day}::Day{day};
+use crate::aoc_solution::{Answer, Puzzle, SolveError};
 
-impl Day{day} {
-    pub fn part_1(&self, _input: &str) -> String {
-        todo!()
+/// Day {day_number}: TBD.
+#[derive(Default)]
+pub struct Day{day};
+
+impl Puzzle for Day{day} {
+    const YEAR: u32 = {year};
+    const DAY: u32 = {day_number};
+    const TITLE: &'static str = "TBD";
+
+    fn part_1(&self, _input: &str) -> Result<Answer, SolveError> {
+        Err(SolveError::unsupported("part 1 is not solved yet"))
     }
-    pub fn part_2(&self, _input: &str) -> String {
-        todo!()
+
+    fn part_2(&self, _input: &str) -> Result<Answer, SolveError> {
+        Err(SolveError::unsupported("part 2 is not solved yet"))
     }
 }
 
 mod test {
     #[test]
     /// Test for part 1 of Day{day} for AoC {year}.
-    /// Ensures correct calculation of the sum of first and last digits in each line.
     fn test_aoc{year}_day{day}_part_1() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc{year}::Day{day};
-        let day{day} = Day{day} {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day{day} = Day{day};
         let input = r#""#;
-        assert_eq!(day{day}.part_1(input), "TBD"); // Asserts if the function output matches the expected result.
+        assert_eq!(day{day}.part_1(input).unwrap(), "TBD"); // Asserts if the function output matches the expected result.
     }
 
     #[test]
     /// Test for part 2 of Day{day} for AoC {year}.
-    /// Verifies correct handling and sum of lines with spelled-out numbers.
     fn test_aoc{year}_day{day}_part_2() {
+        use crate::aoc_solution::Puzzle;
         use crate::solutions::aoc{year}::Day{day};
-        let day{day} = Day{day} {
-            day: 0,
-            year: 0,
-            desc: "".to_string(),
-            code: "".to_string(),
-        };
+        let day{day} = Day{day};
         let input = r#""#;
-        assert_eq!(day{day}.part_2(input), "TBD"); // Asserts if the function output matches the expected result.
+        assert_eq!(day{day}.part_2(input).unwrap(), "TBD"); // Asserts if the function output matches the expected result.
     }
 }